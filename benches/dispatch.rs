@@ -0,0 +1,33 @@
+use anatomy_of_stark::{
+    config::Thresholds,
+    field::{presets::Preset, Field},
+    polynomial::Polynomial,
+};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// Compares the naive and fast evaluate paths across sizes; this is what the
+// evaluate threshold in config::Thresholds is tuned against.
+fn evaluate_benchmark(c: &mut Criterion) {
+    let f = Field::from_preset(Preset::Tutorial);
+    let mut group = c.benchmark_group("evaluate");
+    group.sample_size(10);
+    for n in [256usize, 512, 1024, 2048, 4096, 8192] {
+        let polynomial = Polynomial::new(f.powers(&f.generator(), n));
+        let domain: Vec<_> = (1..=n as u64).map(|i| f.element(i)).collect();
+        for (name, evaluate) in [("naive", usize::MAX), ("fast", 0)] {
+            let thresholds = Thresholds {
+                evaluate,
+                ..Thresholds::default()
+            };
+            group.bench_with_input(BenchmarkId::new(name, n), &n, |b, _| {
+                b.iter(|| {
+                    black_box(&polynomial).evaluate_domain_with(black_box(&domain), &thresholds)
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, evaluate_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use anatomy_of_stark::field::{presets::Preset, Field, Reduction};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use primitive_types::U256;
+
+fn field_benchmark(c: &mut Criterion) {
+    let fields = [
+        ("tutorial", Field::from_preset(Preset::Tutorial)),
+        (
+            "tutorial-montgomery",
+            Field::from_preset(Preset::Tutorial).with_reduction(Reduction::Montgomery),
+        ),
+        ("wide", Field::new(U256::MAX - (U256::one() << 32) - 976)),
+        (
+            "wide-montgomery",
+            Field::new(U256::MAX - (U256::one() << 32) - 976).with_reduction(Reduction::Montgomery),
+        ),
+        (
+            "tutorial-barrett",
+            Field::from_preset(Preset::Tutorial).with_reduction(Reduction::Barrett),
+        ),
+        ("babybear", Field::from_preset(Preset::BabyBear)),
+    ];
+    for (name, f) in fields {
+        let x = f.generator();
+        let y = x.inv();
+        c.bench_function(&format!("{}/mul", name), |b| {
+            b.iter(|| black_box(x) * black_box(y))
+        });
+        c.bench_function(&format!("{}/pow", name), |b| {
+            b.iter(|| black_box(x) ^ black_box(f.p() - 2u64))
+        });
+        c.bench_function(&format!("{}/inv", name), |b| b.iter(|| black_box(x).inv()));
+    }
+}
+
+criterion_group!(benches, field_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+// Binds a message into the Fiat-Shamir transcript ahead of a FRI proof, so the
+// proof only verifies against that message. This is not a signature: anyone
+// can produce such a proof for any low-degree codeword, and a real scheme
+// (e.g. the tutorial's Rescue-based RPSSS) needs a STARK prover.
+use anatomy_of_stark::{
+    channel::{ProverChannel, VerifierChannel},
+    consts::PRIME,
+    element::FieldElement,
+    field::Field,
+    fri::FRI,
+    merkle,
+    polynomial::Polynomial,
+    proofstream::Object,
+    Result,
+};
+use std::process;
+
+const DOMAIN_LENGTH: usize = 64;
+const EXPANSION_FACTOR: usize = 4;
+const NUM_COLINEARITY_TESTS: usize = 4;
+
+fn fri() -> FRI {
+    let field = Field::new(*PRIME);
+    FRI::new(
+        field.generator(),
+        field.primitive_nth_root(DOMAIN_LENGTH.into()),
+        DOMAIN_LENGTH,
+        EXPANSION_FACTOR,
+        NUM_COLINEARITY_TESTS,
+    )
+}
+
+fn secret_polynomial(secret_key: &[u8]) -> Polynomial {
+    let field = Field::new(*PRIME);
+    Polynomial::new(
+        (0..DOMAIN_LENGTH / EXPANSION_FACTOR)
+            .map(|i| {
+                let mut seed = secret_key.to_vec();
+                seed.extend(i.to_be_bytes());
+                field.sample(&merkle::hash(&seed))
+            })
+            .collect(),
+    )
+}
+
+fn bind(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    let fri = fri();
+    let codeword: Vec<FieldElement> =
+        secret_polynomial(secret_key).evaluate_domain(&fri.eval_domain());
+    let mut channel = ProverChannel::new();
+    channel.push_hash(merkle::hash(message));
+    fri.prove(&codeword, &mut channel)?;
+    Ok(channel.serialize())
+}
+
+fn verify(message: &[u8], proof: &[u8]) -> Result<bool> {
+    let mut channel = VerifierChannel::deserialize(proof)?;
+    match channel.pull()? {
+        Object::HASH(digest) if digest == merkle::hash(message) => {}
+        _ => return Ok(false),
+    }
+    fri().verify(&mut channel, vec![])
+}
+
+fn main() {
+    let secret_key = b"correct horse battery staple";
+    let message = b"hello, stark";
+
+    let proof = bind(secret_key, message).unwrap_or_else(|e| {
+        eprintln!("failed to prove: {}", e);
+        process::exit(1);
+    });
+    println!("proof: {} bytes", proof.len());
+
+    for candidate in [&message[..], b"hello, snark"] {
+        match verify(candidate, &proof) {
+            Ok(valid) => println!("{:?}: {}", String::from_utf8_lossy(candidate), valid),
+            Err(e) => println!("{:?}: {}", String::from_utf8_lossy(candidate), e),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Interpolates a Fibonacci trace and FRI-proves that its low-degree extension
+// has low degree. No transition constraint is checked: this is a FRI
+// commitment to the trace, not a proof of the Fibonacci computation.
+use anatomy_of_stark::{
+    channel::ProverChannel,
+    consts::PRIME,
+    field::Field,
+    fri::FRI,
+    ntt::{fast_coset_evaluate, intt},
+    polynomial::Polynomial,
+};
+use std::{env, fs, process};
+
+const TRACE_LENGTH: usize = 16;
+const EXPANSION_FACTOR: usize = 4;
+const NUM_COLINEARITY_TESTS: usize = 4;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| env::temp_dir().join("trace.proof").display().to_string());
+
+    let field = Field::new(*PRIME);
+    let mut trace = vec![field.one(), field.one()];
+    while trace.len() < TRACE_LENGTH {
+        trace.push(trace[trace.len() - 2] + trace[trace.len() - 1]);
+    }
+    println!("fib({}) = {}", TRACE_LENGTH, trace[TRACE_LENGTH - 1].value);
+
+    let omicron = field.primitive_nth_root(TRACE_LENGTH.into());
+    let trace_polynomial = match intt(&omicron, &trace) {
+        Ok(coefficients) => Polynomial::new(coefficients),
+        Err(e) => {
+            eprintln!("failed to interpolate the trace: {}", e);
+            process::exit(1);
+        }
+    };
+    println!("trace polynomial degree: {}", trace_polynomial.degree());
+
+    let domain_length = TRACE_LENGTH * EXPANSION_FACTOR;
+    let fri = FRI::new(
+        field.generator(),
+        field.primitive_nth_root(domain_length.into()),
+        domain_length,
+        EXPANSION_FACTOR,
+        NUM_COLINEARITY_TESTS,
+    );
+    let codeword =
+        match fast_coset_evaluate(&trace_polynomial, &fri.offset, &fri.omega, domain_length) {
+            Ok(codeword) => codeword,
+            Err(e) => {
+                eprintln!("failed to extend the trace: {}", e);
+                process::exit(1);
+            }
+        };
+
+    let mut channel = ProverChannel::new();
+    if let Err(e) = fri.prove(&codeword, &mut channel) {
+        eprintln!("failed to prove: {}", e);
+        process::exit(1);
+    }
+    let proof = channel.serialize();
+    println!(
+        "proof: {} objects, {} bytes",
+        channel.proof().objects.len(),
+        proof.len()
+    );
+
+    if let Err(e) = fs::write(&path, &proof) {
+        eprintln!("failed to write {}: {}", path, e);
+        process::exit(1);
+    }
+    println!("wrote {}", path);
+}
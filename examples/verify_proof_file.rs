@@ -0,0 +1,40 @@
+use anatomy_of_stark::{channel::VerifierChannel, consts::PRIME, field::Field, fri::FRI};
+use std::{env, fs, process};
+
+const DOMAIN_LENGTH: usize = 64;
+const EXPANSION_FACTOR: usize = 4;
+const NUM_COLINEARITY_TESTS: usize = 4;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| env::temp_dir().join("trace.proof").display().to_string());
+
+    let bytes = fs::read(&path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {}", path, e);
+        process::exit(1);
+    });
+
+    let field = Field::new(*PRIME);
+    let fri = FRI::new(
+        field.generator(),
+        field.primitive_nth_root(DOMAIN_LENGTH.into()),
+        DOMAIN_LENGTH,
+        EXPANSION_FACTOR,
+        NUM_COLINEARITY_TESTS,
+    );
+
+    let verified = VerifierChannel::deserialize(&bytes)
+        .and_then(|mut channel| fri.verify(&mut channel, vec![]));
+    match verified {
+        Ok(true) => println!("{}: proof verified", path),
+        Ok(false) => {
+            eprintln!("{}: proof rejected", path);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
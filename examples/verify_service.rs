@@ -0,0 +1,132 @@
+// cargo run --features server --example verify_service [addr]
+// curl -X POST -H 'content-type: application/json' \
+//     --data '{"proof": "<hex of ProofStream::serialize_compact>", "public_inputs": []}' \
+//     localhost:3000/verify
+//
+// The FRI parameters are fixed here, matching verify_proof_file; clients only
+// send the proof stream and the public (index, value) pairs it opens.
+use anatomy_of_stark::{
+    channel::VerifierChannel, consts::PRIME, element::FieldElement, field::Field, fri::FRI, Error,
+};
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use std::{env, sync::Arc};
+
+const MAX_BODY_BYTES: usize = 4 << 20;
+const DOMAIN_LENGTH: usize = 64;
+const EXPANSION_FACTOR: usize = 4;
+const NUM_COLINEARITY_TESTS: usize = 4;
+
+#[derive(Deserialize)]
+struct VerifyRequest {
+    proof: String,
+    #[serde(default)]
+    public_inputs: Vec<PublicInput>,
+}
+
+#[derive(Deserialize)]
+struct PublicInput {
+    index: usize,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct VerifyResponse {
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn server_fri() -> FRI {
+    let field = Field::new(*PRIME);
+    FRI::new(
+        field.generator(),
+        field.primitive_nth_root(DOMAIN_LENGTH.into()),
+        DOMAIN_LENGTH,
+        EXPANSION_FACTOR,
+        NUM_COLINEARITY_TESTS,
+    )
+}
+
+// Checks the compact header against the server field before anything in the
+// stream reaches `Field::try_new`.
+fn check_header(fri: &FRI, bytes: &[u8]) -> Result<(), String> {
+    let width = fri.field.p().bits().div_ceil(8);
+    let mut modulus = [0u8; 32];
+    fri.field.p().to_big_endian(&mut modulus);
+    match bytes.get(2..3 + width) {
+        Some([w, p @ ..]) if *w as usize == width && p == &modulus[32 - width..] => Ok(()),
+        _ => Err(format!("proof must be over the field {}", fri.field)),
+    }
+}
+
+fn public_inputs(fri: &FRI, inputs: &[PublicInput]) -> Result<Vec<(usize, FieldElement)>, String> {
+    inputs
+        .iter()
+        .map(|input| {
+            if input.index >= fri.domain_length {
+                return Err(format!("index {} is outside the domain", input.index));
+            }
+            match U256::from_str_radix(&input.value, 16) {
+                Ok(value) if value < fri.field.p() => {
+                    Ok((input.index, FieldElement::new(value, fri.field)))
+                }
+                _ => Err(format!("invalid public value {:?}", input.value)),
+            }
+        })
+        .collect()
+}
+
+fn verify_proof(fri: &FRI, request: VerifyRequest) -> Result<bool, (StatusCode, String)> {
+    let bad_request = |e: Error| (StatusCode::BAD_REQUEST, e.to_string());
+    let unprocessable = |e: String| (StatusCode::UNPROCESSABLE_ENTITY, e);
+    let bytes = hex::decode(&request.proof)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("proof is not hex: {}", e)))?;
+    check_header(fri, &bytes).map_err(unprocessable)?;
+    let values = public_inputs(fri, &request.public_inputs).map_err(unprocessable)?;
+    let mut channel = VerifierChannel::deserialize_compact(&bytes).map_err(bad_request)?;
+    fri.verify(&mut channel, values).map_err(bad_request)
+}
+
+async fn verify(
+    State(fri): State<Arc<FRI>>,
+    Json(request): Json<VerifyRequest>,
+) -> (StatusCode, Json<VerifyResponse>) {
+    let (status, valid, error) =
+        match tokio::task::spawn_blocking(move || verify_proof(&fri, request)).await {
+            Ok(Ok(valid)) => (StatusCode::OK, valid, None),
+            Ok(Err((status, e))) => (status, false, Some(e)),
+            Err(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                false,
+                Some("verifier panicked".to_string()),
+            ),
+        };
+    (status, Json(VerifyResponse { valid, error }))
+}
+
+#[tokio::main]
+async fn main() {
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+    let app = Router::new()
+        .route("/verify", post(verify))
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+        .with_state(Arc::new(server_fri()));
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("failed to bind {}: {}", addr, e);
+            std::process::exit(1);
+        });
+    println!("listening on {}", addr);
+    axum::serve(listener, app).await.unwrap();
+}
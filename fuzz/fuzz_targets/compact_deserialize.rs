@@ -0,0 +1,11 @@
+#![no_main]
+
+use anatomy_of_stark::{element::FieldElement, proofstream::ProofStream};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(stream) = ProofStream::<Vec<FieldElement>>::deserialize_compact(data) {
+        let encoded = stream.serialize_compact().unwrap();
+        assert_eq!(ProofStream::deserialize_compact(&encoded).unwrap(), stream);
+    }
+});
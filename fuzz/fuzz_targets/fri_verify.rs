@@ -0,0 +1,18 @@
+#![no_main]
+
+use anatomy_of_stark::{channel::VerifierChannel, element::FieldElement, field::Field, fri::FRI};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let f = Field::new(17.into());
+    let fri = FRI::new(
+        FieldElement::new(1.into(), f),
+        FieldElement::new(6.into(), f),
+        16,
+        2,
+        2,
+    );
+    if let Ok(mut channel) = VerifierChannel::deserialize(data) {
+        let _ = fri.verify(&mut channel, vec![]);
+    }
+});
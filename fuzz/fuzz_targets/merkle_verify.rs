@@ -0,0 +1,19 @@
+#![no_main]
+
+use anatomy_of_stark::merkle::{Direction, Merkle, MerkleProof};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Vec<u8>, usize, Vec<(bool, Vec<u8>)>, Vec<u8>)| {
+    let (root, index, nodes, leaf) = input;
+    let proof = MerkleProof::new(
+        nodes
+            .into_iter()
+            .map(|(left, node)| {
+                let direction = if left { Direction::Left } else { Direction::Right };
+                (direction, node)
+            })
+            .collect(),
+    );
+    Merkle::verify(&root, index, &proof, &leaf);
+    Merkle::verify_membership(&root, &proof, &leaf);
+});
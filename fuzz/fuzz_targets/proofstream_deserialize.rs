@@ -0,0 +1,13 @@
+#![no_main]
+
+use anatomy_of_stark::{channel::VerifierChannel, element::FieldElement};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(mut channel) = VerifierChannel::<Vec<FieldElement>>::deserialize(data) {
+        while !channel.is_exhausted() {
+            channel.pull().unwrap();
+        }
+        channel.fiat_shamir(32);
+    }
+});
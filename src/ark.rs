@@ -0,0 +1,80 @@
+use crate::{consts::*, element::FieldElement, field::Field};
+use ark_ff::{
+    fields::{Fp128, MontBackend, MontConfig},
+    BigInt, PrimeField,
+};
+use primitive_types::U256;
+
+#[derive(MontConfig)]
+#[modulus = "270497897142230380135924736767050121217"]
+#[generator = "3"]
+pub struct TutorialFieldConfig;
+
+pub type ArkFieldElement = Fp128<MontBackend<TutorialFieldConfig, 2>>;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ArkConversionError {
+    FieldMismatch,
+    ValueOutOfRange,
+}
+
+impl TryFrom<FieldElement> for ArkFieldElement {
+    type Error = ArkConversionError;
+
+    fn try_from(element: FieldElement) -> Result<Self, Self::Error> {
+        if element.field.p() != *PRIME {
+            return Err(ArkConversionError::FieldMismatch);
+        }
+        let limbs = element.value.0;
+        if limbs[2] != 0 || limbs[3] != 0 {
+            return Err(ArkConversionError::ValueOutOfRange);
+        }
+        ArkFieldElement::from_bigint(BigInt::new([limbs[0], limbs[1]]))
+            .ok_or(ArkConversionError::ValueOutOfRange)
+    }
+}
+
+impl From<ArkFieldElement> for FieldElement {
+    fn from(element: ArkFieldElement) -> Self {
+        let limbs = element.into_bigint().0;
+        FieldElement::new(U256([limbs[0], limbs[1], 0, 0]), Field::new(*PRIME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{FftField, Field as ArkField};
+
+    #[test]
+    fn ark_conversion_test() {
+        let f = Field::new(*PRIME);
+        let a = f.generator();
+        let b = f.element(123456789);
+
+        let ark_a = ArkFieldElement::try_from(a).unwrap();
+        let ark_b = ArkFieldElement::try_from(b).unwrap();
+        assert_eq!(FieldElement::from(ark_a), a);
+        assert_eq!(FieldElement::from(ark_a * ark_b), a * b);
+        assert_eq!(FieldElement::from(ark_a + ark_b), a + b);
+        assert_eq!(FieldElement::from(ark_a - ark_b), a - b);
+        assert_eq!(FieldElement::from(ark_b.inverse().unwrap()), b.inv());
+
+        assert_eq!(ArkFieldElement::TWO_ADICITY, 119);
+        let root = FieldElement::from(ArkFieldElement::get_root_of_unity(1 << 10).unwrap());
+        assert_eq!(root ^ (1u64 << 10).into(), f.one());
+        assert_ne!(root ^ (1u64 << 9).into(), f.one());
+
+        assert_eq!(
+            ArkFieldElement::try_from(FieldElement::new(ONE, Field::new(17.into()))),
+            Err(ArkConversionError::FieldMismatch)
+        );
+        assert_eq!(
+            ArkFieldElement::try_from(FieldElement {
+                value: *PRIME,
+                ..f.one()
+            }),
+            Err(ArkConversionError::ValueOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::field::windowed_pow;
+use primitive_types::{U256, U512};
+
+pub trait ModularInt: Copy + Eq {
+    fn from_u256(value: U256) -> Self;
+    fn to_u256(self) -> U256;
+    fn add_mod(self, rhs: Self, p: Self) -> Self;
+    fn sub_mod(self, rhs: Self, p: Self) -> Self;
+    fn mul_mod(self, rhs: Self, p: Self) -> Self;
+    fn neg_mod(self, p: Self) -> Self;
+
+    fn pow_mod(self, exponent: U256, p: Self) -> Self {
+        windowed_pow(self, Self::from_u256(U256::one()), exponent, |a, b| {
+            a.mul_mod(b, p)
+        })
+    }
+}
+
+#[cfg(not(feature = "crypto-bigint"))]
+pub type Backend = U256;
+#[cfg(feature = "crypto-bigint")]
+pub type Backend = crypto_bigint::U256;
+
+impl ModularInt for U256 {
+    fn from_u256(value: U256) -> Self {
+        value
+    }
+
+    fn to_u256(self) -> U256 {
+        self
+    }
+
+    fn add_mod(self, rhs: Self, p: Self) -> Self {
+        let (sum, overflow) = self.overflowing_add(rhs);
+        if overflow {
+            sum.overflowing_sub(p).0
+        } else {
+            sum % p
+        }
+    }
+
+    fn sub_mod(self, rhs: Self, p: Self) -> Self {
+        if self >= rhs {
+            (self - rhs) % p
+        } else {
+            p - (rhs - self)
+        }
+    }
+
+    fn mul_mod(self, rhs: Self, p: Self) -> Self {
+        if p.bits() <= 128 {
+            return (self * rhs) % p;
+        }
+        let product = self.full_mul(rhs) % U512::from(p);
+        U256::try_from(product).expect("reduced product fits the modulus")
+    }
+
+    fn neg_mod(self, p: Self) -> Self {
+        (p - self) % p
+    }
+}
+
+#[cfg(feature = "crypto-bigint")]
+impl ModularInt for crypto_bigint::U256 {
+    fn from_u256(value: U256) -> Self {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        crypto_bigint::U256::from_be_slice(&bytes)
+    }
+
+    fn to_u256(self) -> U256 {
+        use crypto_bigint::Encoding;
+        U256::from_big_endian(&self.to_be_bytes())
+    }
+
+    fn add_mod(self, rhs: Self, p: Self) -> Self {
+        crypto_bigint::U256::add_mod(&self, &rhs, &p)
+    }
+
+    fn sub_mod(self, rhs: Self, p: Self) -> Self {
+        crypto_bigint::U256::sub_mod(&self, &rhs, &p)
+    }
+
+    fn mul_mod(self, rhs: Self, p: Self) -> Self {
+        use crypto_bigint::modular::runtime_mod::DynResidue;
+        match residue_params(p) {
+            Some(params) => DynResidue::new(&self, params)
+                .mul(&DynResidue::new(&rhs, params))
+                .retrieve(),
+            None => crypto_bigint::U256::const_rem_wide(self.mul_wide(&rhs), &p).0,
+        }
+    }
+
+    fn neg_mod(self, p: Self) -> Self {
+        crypto_bigint::U256::neg_mod(&self, &p)
+    }
+
+    fn pow_mod(self, exponent: U256, p: Self) -> Self {
+        use crypto_bigint::modular::runtime_mod::DynResidue;
+        match residue_params(p) {
+            Some(params) => DynResidue::new(&self, params)
+                .pow(&Self::from_u256(exponent))
+                .retrieve(),
+            None => windowed_pow(self, Self::ONE, exponent, |a, b| a.mul_mod(b, p)),
+        }
+    }
+}
+
+#[cfg(feature = "crypto-bigint")]
+fn residue_params(
+    p: crypto_bigint::U256,
+) -> Option<crypto_bigint::modular::runtime_mod::DynResidueParams<{ crypto_bigint::U256::LIMBS }>> {
+    use crypto_bigint::modular::runtime_mod::DynResidueParams;
+    use std::{cell::RefCell, collections::HashMap};
+
+    // Keyed by modulus so that alternating between fields keeps every entry.
+    thread_local! {
+        static PARAMS: RefCell<
+            HashMap<crypto_bigint::U256, DynResidueParams<{ crypto_bigint::U256::LIMBS }>>,
+        > = RefCell::new(HashMap::new());
+    }
+    if p.as_words()[0] & 1 == 0 {
+        return None;
+    }
+    PARAMS.with(|cache| {
+        Some(
+            *cache
+                .borrow_mut()
+                .entry(p)
+                .or_insert_with(|| DynResidueParams::new(&p)),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    fn check<T: ModularInt + std::fmt::Debug>(p: U256) {
+        let p_ = T::from_u256(p);
+        let values = [ZERO, ONE, p - ONE, p >> 1, (p >> 3) + 12345].map(|value| value % p);
+        for a in values {
+            assert_eq!(T::from_u256(a).to_u256(), a);
+            for b in values {
+                let (a_, b_) = (T::from_u256(a), T::from_u256(b));
+                let expected_sum = (U512::from(a) + U512::from(b)) % U512::from(p);
+                let expected_product = a.full_mul(b) % U512::from(p);
+                assert_eq!(U512::from(a_.add_mod(b_, p_).to_u256()), expected_sum);
+                assert_eq!(U512::from(a_.mul_mod(b_, p_).to_u256()), expected_product);
+                assert_eq!(
+                    a_.sub_mod(b_, p_).add_mod(b_, p_).to_u256(),
+                    a,
+                    "{:?} - {:?}",
+                    a,
+                    b
+                );
+            }
+            let square = U256::try_from(a.full_mul(a) % U512::from(p)).unwrap();
+            let cube = U256::try_from(square.full_mul(a) % U512::from(p)).unwrap();
+            assert_eq!(T::from_u256(a).pow_mod(3.into(), p_).to_u256(), cube);
+            assert_eq!(T::from_u256(a).pow_mod(ZERO, p_).to_u256(), ONE % p);
+            assert_eq!(
+                T::from_u256(a)
+                    .neg_mod(p_)
+                    .add_mod(T::from_u256(a), p_)
+                    .to_u256(),
+                ZERO
+            );
+        }
+    }
+
+    #[test]
+    fn modular_int_test() {
+        let large = U256::MAX - (ONE << 32) - 976;
+        for p in [*PRIME, U256::from(17), U256::from(2), large] {
+            check::<U256>(p);
+            check::<Backend>(p);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use anatomy_of_stark::vectors;
+use std::{env, fs, process};
+
+const DEFAULT_PATH: &str = "tests/vectors/kat.json";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let command = args.get(1).map(String::as_str);
+    let path = args.get(2).map(String::as_str).unwrap_or(DEFAULT_PATH);
+
+    match command {
+        Some("generate") => {
+            fs::write(path, vectors::to_json(&vectors::generate()) + "\n").unwrap_or_else(|e| {
+                eprintln!("failed to write {}: {}", path, e);
+                process::exit(1);
+            });
+            println!("wrote {}", path);
+        }
+        Some("check") => {
+            let json = fs::read_to_string(path).unwrap_or_else(|e| {
+                eprintln!("failed to read {}: {}", path, e);
+                process::exit(1);
+            });
+            match vectors::from_json(&json).and_then(|v| vectors::replay(&v)) {
+                Ok(()) => println!("{}: ok", path),
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    process::exit(1);
+                }
+            }
+        }
+        _ => {
+            eprintln!("usage: {} <generate|check> [path]", args[0]);
+            process::exit(2);
+        }
+    }
+}
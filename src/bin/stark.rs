@@ -0,0 +1,27 @@
+use anatomy_of_stark::inspect::Inspection;
+use std::{env, fs, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let command = args.get(1).map(String::as_str);
+
+    match (command, args.get(2)) {
+        (Some("inspect"), Some(path)) => {
+            let bytes = fs::read(path).unwrap_or_else(|e| {
+                eprintln!("failed to read {}: {}", path, e);
+                process::exit(1);
+            });
+            match Inspection::from_bytes(&bytes) {
+                Ok(inspection) => print!("{}", inspection),
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    process::exit(1);
+                }
+            }
+        }
+        _ => {
+            eprintln!("usage: {} inspect <proof>", args[0]);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    element::FieldElement,
+    hash::{HashKind, Hasher},
+    merkle::MerkleProof,
+    proofstream::{Object, ProofStream},
+    transcript::Transcript,
+    Result,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+pub struct ProverChannel<T> {
+    stream: ProofStream<T>,
+    transcript: Option<Transcript>,
+}
+
+impl<'a, T: Clone + Serialize + Deserialize<'a>> Default for ProverChannel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Clone + Serialize + Deserialize<'a>> ProverChannel<T> {
+    pub fn new() -> Self {
+        ProverChannel::with_hash(HashKind::default())
+    }
+
+    pub fn with_hash(hash: HashKind) -> Self {
+        ProverChannel::with_hasher(hash.into())
+    }
+
+    pub fn with_hasher(hasher: Hasher) -> Self {
+        ProverChannel {
+            stream: ProofStream::with_hasher(hasher),
+            transcript: None,
+        }
+    }
+
+    pub fn with_transcript(mut self) -> Self {
+        self.transcript = Some(Transcript::new());
+        self
+    }
+
+    pub fn transcript(&self) -> Option<&Transcript> {
+        self.transcript.as_ref()
+    }
+
+    fn push(&mut self, object: Object<T>) {
+        if let Some(transcript) = &mut self.transcript {
+            transcript.absorb(self.stream.hash, &object);
+        }
+        self.stream.push(object);
+    }
+
+    pub fn hash(&self) -> HashKind {
+        self.stream.hash
+    }
+
+    pub fn hasher(&self) -> Hasher {
+        self.stream.hasher()
+    }
+
+    pub fn push_hash(&mut self, hash: Vec<u8>) {
+        self.push(Object::HASH(hash));
+    }
+
+    pub fn push_obj(&mut self, obj: T) {
+        self.push(Object::OBJ(obj));
+    }
+
+    pub fn push_path(&mut self, path: MerkleProof) {
+        self.push(Object::PATH(path));
+    }
+
+    pub fn push_leafs(&mut self, leafs: T) {
+        self.push(Object::LEAF(leafs));
+    }
+
+    pub fn fiat_shamir(&mut self, num_bytes: usize) -> Vec<u8> {
+        let challenge = self.stream.prover_fiat_shamir(num_bytes);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.squeeze(&challenge);
+        }
+        challenge
+    }
+
+    pub fn challenge(&mut self) -> Vec<u8> {
+        self.fiat_shamir(self.stream.digest_length)
+    }
+
+    pub fn proof(&self) -> &ProofStream<T> {
+        &self.stream
+    }
+
+    pub fn into_proof(self) -> ProofStream<T> {
+        self.stream
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        self.stream.serialize()
+    }
+}
+
+#[derive(Debug)]
+pub struct VerifierChannel<T> {
+    stream: ProofStream<T>,
+    transcript: Option<Transcript>,
+}
+
+impl<'a, T: Clone + Serialize + Deserialize<'a>> VerifierChannel<T> {
+    pub fn new(mut proof: ProofStream<T>) -> Self {
+        proof.read_index = 0;
+        VerifierChannel {
+            stream: proof,
+            transcript: None,
+        }
+    }
+
+    pub fn with_transcript(mut self) -> Self {
+        self.transcript = Some(Transcript::new());
+        self
+    }
+
+    pub fn transcript(&self) -> Option<&Transcript> {
+        self.transcript.as_ref()
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        Ok(VerifierChannel::new(ProofStream::deserialize(data)?))
+    }
+
+    pub fn hash(&self) -> HashKind {
+        self.stream.hash
+    }
+
+    pub fn hasher(&self) -> Hasher {
+        self.stream.hasher()
+    }
+
+    pub fn pull(&mut self) -> Result<Object<T>> {
+        let object = self.stream.pull()?;
+        if let Some(transcript) = &mut self.transcript {
+            transcript.absorb(self.stream.hash, &object);
+        }
+        Ok(object)
+    }
+
+    pub fn fiat_shamir(&mut self, num_bytes: usize) -> Vec<u8> {
+        let challenge = self.stream.verifier_fiat_shamir(num_bytes);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.squeeze(&challenge);
+        }
+        challenge
+    }
+
+    pub fn challenge(&mut self) -> Vec<u8> {
+        self.fiat_shamir(self.stream.digest_length)
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.stream.read_index == self.stream.objects.len()
+    }
+
+    pub fn proof(&self) -> &ProofStream<T> {
+        &self.stream
+    }
+}
+
+impl ProverChannel<Vec<FieldElement>> {
+    pub fn serialize_compact(&self) -> Result<Vec<u8>> {
+        self.stream.serialize_compact()
+    }
+}
+
+impl VerifierChannel<Vec<FieldElement>> {
+    pub fn deserialize_compact(data: &[u8]) -> Result<Self> {
+        Ok(VerifierChannel::new(ProofStream::deserialize_compact(
+            data,
+        )?))
+    }
+}
+
+impl<'a, T: Clone + Serialize + Deserialize<'a>> From<ProverChannel<T>> for VerifierChannel<T> {
+    fn from(channel: ProverChannel<T>) -> Self {
+        VerifierChannel::new(channel.into_proof())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::*, field::Field, transcript::Event};
+
+    #[test]
+    fn channel_test() {
+        let f = Field::new(*PRIME);
+        let mut prover = ProverChannel::new();
+        prover.push_obj(f.one());
+        let challenge = prover.fiat_shamir(32);
+        prover.push_obj(f.generator());
+
+        let mut verifier: VerifierChannel<FieldElement> =
+            VerifierChannel::deserialize(&prover.serialize()).unwrap();
+        assert_eq!(verifier.pull().unwrap(), Object::OBJ(f.one()));
+        assert_eq!(verifier.fiat_shamir(32), challenge);
+        assert_eq!(verifier.pull().unwrap(), Object::OBJ(f.generator()));
+        assert!(verifier.is_exhausted());
+        assert!(verifier.pull().is_err());
+
+        let verifier = VerifierChannel::from(prover);
+        assert_eq!(verifier.proof().objects.len(), 2);
+        assert_eq!(verifier.hash(), HashKind::Legacy);
+        assert_eq!(verifier.hasher(), Hasher::default());
+
+        let hasher = Hasher::new(HashKind::Sha3, 48).unwrap();
+        let mut prover = ProverChannel::with_hasher(hasher);
+        prover.push_obj(f.one());
+        let challenge = prover.challenge();
+        assert_eq!(challenge.len(), 48);
+        let mut verifier: VerifierChannel<FieldElement> =
+            VerifierChannel::deserialize(&prover.serialize()).unwrap();
+        assert_eq!(verifier.hasher(), hasher);
+        verifier.pull().unwrap();
+        assert_eq!(verifier.challenge(), challenge);
+    }
+
+    #[test]
+    fn transcript_test() {
+        let f = Field::new(*PRIME);
+        let mut prover = ProverChannel::new().with_transcript();
+        prover.push_obj(f.one());
+        prover.fiat_shamir(32);
+        prover.push_obj(f.generator());
+        prover.fiat_shamir(16);
+        let recorded = prover.transcript().unwrap().clone();
+        assert_eq!(recorded.events.len(), 4);
+
+        let mut verifier =
+            VerifierChannel::<FieldElement>::new(prover.proof().clone()).with_transcript();
+        verifier.pull().unwrap();
+        verifier.fiat_shamir(32);
+        verifier.pull().unwrap();
+        verifier.fiat_shamir(16);
+        assert_eq!(
+            Transcript::diff(&recorded, verifier.transcript().unwrap()),
+            None
+        );
+
+        let mut diverging = VerifierChannel::<FieldElement>::from(prover).with_transcript();
+        diverging.pull().unwrap();
+        diverging.pull().unwrap();
+        diverging.fiat_shamir(32);
+        let divergence = Transcript::diff(&recorded, diverging.transcript().unwrap()).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert!(matches!(
+            divergence.prover,
+            Some(Event::Squeeze { num_bytes: 32, .. })
+        ));
+        assert!(matches!(divergence.verifier, Some(Event::Absorb { .. })));
+
+        let path = std::env::temp_dir().join("anatomy_of_stark_transcript_test.json");
+        recorded.write(&path).unwrap();
+        assert_eq!(Transcript::read(&path).unwrap(), recorded);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,8 +1,12 @@
+use crate::field::presets::Preset;
 use once_cell::sync::Lazy;
 use primitive_types::U256;
 
+pub const PROTOCOL_VERSION: u32 = 2;
 pub const ZERO: U256 = U256::zero();
 pub const ONE: U256 = U256::one();
 pub static TWO: Lazy<U256> = Lazy::new(|| 2.into());
-pub static PRIME: Lazy<U256> = Lazy::new(|| (1u128 + 407 * (1 << 119)).into());
-pub static GENERATOR: Lazy<U256> = Lazy::new(|| 85408008396924667383611388730472331217u128.into());
+// The tutorial field predates the presets; these read from `Preset::Tutorial`.
+pub static PRIME: Lazy<U256> = Lazy::new(|| Preset::Tutorial.prime());
+pub static GENERATOR: Lazy<U256> = Lazy::new(|| Preset::Tutorial.generator());
+pub static PRIME_FACTORS: Lazy<Vec<(U256, u32)>> = Lazy::new(|| Preset::Tutorial.factors());
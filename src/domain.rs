@@ -0,0 +1,323 @@
+use crate::{
+    element::FieldElement,
+    field::{FiniteField, FiniteFieldElement},
+    polynomial::Polynomial,
+    Error, Result,
+};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Coset<E: FiniteFieldElement = FieldElement> {
+    pub offset: E,
+    pub generator: E,
+    pub size: usize,
+}
+
+impl<E: FiniteFieldElement> Coset<E> {
+    pub fn new(offset: E, generator: E, size: usize) -> Self {
+        Coset {
+            offset,
+            generator,
+            size,
+        }
+    }
+
+    pub fn elements(&self) -> Vec<E> {
+        let mut point = self.offset;
+        (0..self.size)
+            .map(|_| {
+                let current = point;
+                point *= self.generator;
+                current
+            })
+            .collect()
+    }
+
+    pub fn squared(&self) -> Self {
+        Coset {
+            offset: self.offset * self.offset,
+            generator: self.generator * self.generator,
+            size: self.size / 2,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.generator.pow(self.size.into()) == self.generator.field().one()
+    }
+}
+
+pub fn vanishing_poly<E: FiniteFieldElement>(coset: &Coset<E>) -> Polynomial<E> {
+    let field = coset.offset.field();
+    let mut coefficients = vec![field.zero(); coset.size + 1];
+    coefficients[0] = -coset.offset.pow(coset.size.into());
+    coefficients[coset.size] = field.one();
+    Polynomial::new(coefficients)
+}
+
+pub fn evaluate_vanishing<E: FiniteFieldElement>(coset: &Coset<E>, point: &E) -> E {
+    point.pow(coset.size.into()) - coset.offset.pow(coset.size.into())
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DomainInverses<E: FiniteFieldElement = FieldElement> {
+    pub coset: Coset<E>,
+    indices: Vec<usize>,
+    points: Vec<E>,
+    inverses: Vec<E>,
+    half_inverse: E,
+}
+
+impl<E: FiniteFieldElement> DomainInverses<E> {
+    pub fn new(coset: Coset<E>) -> Result<Self> {
+        DomainInverses::build(coset, (0..coset.size).collect(), coset.elements())
+    }
+
+    pub fn sparse(coset: Coset<E>, indices: &[usize]) -> Result<Self> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if let Some(index) = indices.iter().find(|index| **index >= coset.size) {
+            return Err(Error::IndexOutOfRange {
+                index: *index,
+                len: coset.size,
+            });
+        }
+        let points = indices
+            .iter()
+            .map(|index| coset.offset * coset.generator.pow((*index).into()))
+            .collect();
+        DomainInverses::build(coset, indices, points)
+    }
+
+    fn build(coset: Coset<E>, indices: Vec<usize>, points: Vec<E>) -> Result<Self> {
+        let field = coset.offset.field();
+        if coset.size < 2
+            || !coset.size.is_multiple_of(2)
+            || coset.generator.pow((coset.size / 2).into()) != -field.one()
+        {
+            return Err(Error::BadDomainSize(coset.size));
+        }
+        // Folding halves each pair sum, which characteristic 2 cannot do.
+        let two = field.integer(2);
+        if two.is_zero() {
+            return Err(Error::BadDomainSize(coset.size));
+        }
+        let inverses = field.batch_inv(&points).ok_or(Error::DivisionByZero)?;
+        Ok(DomainInverses {
+            coset,
+            indices,
+            points,
+            inverses,
+            half_inverse: two.inv(),
+        })
+    }
+
+    fn position(&self, index: usize) -> Option<usize> {
+        if self.indices.len() == self.coset.size {
+            (index < self.coset.size).then_some(index)
+        } else {
+            self.indices.binary_search(&index).ok()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn point(&self, index: usize) -> Option<E> {
+        self.position(index).map(|position| self.points[position])
+    }
+
+    pub fn inverse(&self, index: usize) -> Option<E> {
+        self.position(index).map(|position| self.inverses[position])
+    }
+
+    pub fn pair_inverse(&self, index: usize) -> Option<E> {
+        if index >= self.coset.size / 2 {
+            return None;
+        }
+        self.inverse(index)
+            .map(|inverse| inverse * self.half_inverse)
+    }
+
+    pub fn fold(&self, index: usize, a: &E, b: &E, alpha: &E) -> Option<E> {
+        let pair_inverse = self.pair_inverse(index)?;
+        Some((*a + *b) * self.half_inverse + *alpha * (*a - *b) * pair_inverse)
+    }
+
+    pub fn fold_codeword(&self, codeword: &[E], alpha: &E) -> Result<Vec<E>> {
+        if self.indices.len() != self.coset.size || codeword.len() != self.coset.size {
+            return Err(Error::LengthMismatch {
+                expected: self.coset.size,
+                actual: codeword.len(),
+            });
+        }
+        let field = alpha.field();
+        let (low, high) = codeword.split_at(codeword.len() / 2);
+        let weights: Vec<E> = self.inverses[..low.len()]
+            .iter()
+            .map(|inverse| *alpha * *inverse)
+            .collect();
+        let combined = field.batch_add(
+            &field.batch_add(low, high),
+            &field.batch_mul(&field.batch_sub(low, high), &weights),
+        );
+        Ok(field.batch_mul(&combined, &vec![self.half_inverse; low.len()]))
+    }
+
+    pub fn squared(&self) -> Self {
+        let half = self.coset.size / 2;
+        let mut entries: Vec<_> = self
+            .indices
+            .iter()
+            .zip(self.points.iter().zip(&self.inverses))
+            .map(|(index, (point, inverse))| (index % half, (*point * *point, *inverse * *inverse)))
+            .collect();
+        entries.sort_unstable_by_key(|(index, _)| *index);
+        entries.dedup_by_key(|(index, _)| *index);
+        let (indices, (points, inverses)) = entries.into_iter().unzip();
+        DomainInverses {
+            coset: self.coset.squared(),
+            indices,
+            points,
+            inverses,
+            half_inverse: self.half_inverse,
+        }
+    }
+
+    pub fn evaluate(&self, values: &[E], point: &E) -> Result<E> {
+        if self.indices.len() != self.coset.size || values.len() != self.coset.size {
+            return Err(Error::LengthMismatch {
+                expected: self.coset.size,
+                actual: values.len(),
+            });
+        }
+        let field = point.field();
+        if let Some(i) = self.points.iter().position(|x| x == point) {
+            return Ok(values[i]);
+        }
+        let differences: Vec<E> = self.points.iter().map(|x| *point - *x).collect();
+        let differences = field.batch_inv(&differences).ok_or(Error::DivisionByZero)?;
+        let sum = values
+            .iter()
+            .zip(&self.points)
+            .zip(&differences)
+            .fold(field.zero(), |acc, ((value, x), difference)| {
+                acc + ((*value * *x) * *difference)
+            });
+        let offset_power = self.coset.offset.pow(self.coset.size.into());
+        let scale = field.integer(self.coset.size) * offset_power;
+        Ok((evaluate_vanishing(&self.coset, point) * sum) / scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::*, field::Field};
+
+    #[test]
+    fn vanishing_test() {
+        let f = Field::new(*PRIME);
+        let coset = Coset::new(f.generator(), f.primitive_nth_root(16.into()), 16);
+        assert!(coset.is_closed());
+        let elements = coset.elements();
+
+        let vanishing = vanishing_poly(&coset);
+        assert_eq!(vanishing.degree(), 16);
+        assert_eq!(vanishing, Polynomial::zerofier_domain(&elements).unwrap());
+        for e in &elements {
+            assert!(evaluate_vanishing(&coset, e).is_zero());
+        }
+        let point = f.element(12345);
+        assert_eq!(
+            evaluate_vanishing(&coset, &point),
+            vanishing.evaluate(&point)
+        );
+
+        let squared = coset.squared();
+        assert_eq!(squared.size, 8);
+        assert_eq!(
+            squared.elements(),
+            elements[..8].iter().map(|e| e * e).collect::<Vec<_>>()
+        );
+        assert!(!Coset::new(f.one(), f.primitive_nth_root(16.into()), 8).is_closed());
+    }
+
+    #[test]
+    fn domain_inverses_test() {
+        let f = Field::new(*PRIME);
+        let coset = Coset::new(f.generator(), f.primitive_nth_root(16.into()), 16);
+        let elements = coset.elements();
+        let inverses = DomainInverses::new(coset).unwrap();
+        assert_eq!(inverses.len(), 16);
+        for (i, x) in elements.iter().enumerate() {
+            assert_eq!(inverses.point(i), Some(*x));
+            assert_eq!(inverses.inverse(i).unwrap() * x, f.one());
+        }
+        for i in 0..8 {
+            let difference = elements[i] - elements[i + 8];
+            assert_eq!(inverses.pair_inverse(i).unwrap() * difference, f.one());
+        }
+        assert_eq!(inverses.pair_inverse(8), None);
+        assert_eq!(inverses.inverse(16), None);
+
+        let alpha = f.element(12345);
+        let polynomial = Polynomial::new((1..=6).map(|c| FieldElement::new(c.into(), f)).collect());
+        let codeword = polynomial.evaluate_domain(&elements);
+        let folded = inverses.fold_codeword(&codeword, &alpha).unwrap();
+        let squared = inverses.squared();
+        assert_eq!(squared.coset, coset.squared());
+        assert_eq!(squared, DomainInverses::new(coset.squared()).unwrap());
+        let two = f.one() + f.one();
+        for i in 0..8 {
+            let ratio = alpha / elements[i];
+            let expected =
+                (((f.one() + ratio) * codeword[i]) + ((f.one() - ratio) * codeword[i + 8])) / two;
+            assert_eq!(folded[i], expected);
+        }
+
+        let point = f.element(987654321);
+        assert_eq!(
+            inverses.evaluate(&codeword, &point).unwrap(),
+            polynomial.evaluate(&point)
+        );
+        assert_eq!(
+            inverses.evaluate(&codeword, &elements[5]).unwrap(),
+            codeword[5]
+        );
+        assert!(inverses.evaluate(&codeword[1..], &point).is_err());
+
+        let sparse = DomainInverses::sparse(coset, &[13, 3, 5, 3]).unwrap();
+        assert_eq!(sparse.len(), 3);
+        assert_eq!(sparse.inverse(5), inverses.inverse(5));
+        assert_eq!(sparse.inverse(4), None);
+        assert_eq!(
+            sparse.fold(3, &codeword[3], &codeword[11], &alpha),
+            Some(folded[3])
+        );
+        let squared = sparse.squared();
+        assert_eq!(squared.len(), 2);
+        assert_eq!(
+            squared.inverse(5),
+            DomainInverses::new(coset.squared()).unwrap().inverse(5)
+        );
+        assert!(sparse.fold_codeword(&codeword, &alpha).is_err());
+
+        assert!(matches!(
+            DomainInverses::sparse(coset, &[16]),
+            Err(Error::IndexOutOfRange { index: 16, len: 16 })
+        ));
+        assert!(matches!(
+            DomainInverses::new(Coset::new(f.zero(), coset.generator, 16)),
+            Err(Error::DivisionByZero)
+        ));
+        assert!(matches!(
+            DomainInverses::new(Coset::new(f.one(), coset.generator, 8)),
+            Err(Error::BadDomainSize(8))
+        ));
+    }
+}
@@ -1,7 +1,4 @@
-use crate::{
-    consts::{ONE, ZERO},
-    field::Field,
-};
+use crate::{consts::ZERO, field::Field, Error};
 use primitive_types::U256;
 use serde::{
     de,
@@ -11,30 +8,101 @@ use serde::{
 };
 use std::fmt;
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+pub const ELEMENT_BYTES: usize = 32;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct FieldElement {
     pub value: U256,
-    pub field: Field,
+    pub field: &'static Field,
 }
 
 impl FieldElement {
     pub fn new(value: U256, field: Field) -> Self {
-        FieldElement { value, field }
+        FieldElement {
+            value: if value >= field.p() {
+                value % field.p()
+            } else {
+                value
+            },
+            field: field.interned(),
+        }
+    }
+
+    pub fn from_u64(value: u64, field: Field) -> Self {
+        FieldElement::new(value.into(), field)
+    }
+
+    pub fn from_u128(value: u128, field: Field) -> Self {
+        FieldElement::new(value.into(), field)
+    }
+
+    pub fn try_new(value: U256, field: Field) -> crate::Result<Self> {
+        if value >= field.p() {
+            return Err(Error::NonCanonical);
+        }
+        Ok(FieldElement::new(value, field))
+    }
+
+    pub fn to_bytes(&self) -> [u8; ELEMENT_BYTES] {
+        let mut bytes = [0u8; ELEMENT_BYTES];
+        self.value.to_little_endian(&mut bytes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8], field: Field) -> crate::Result<Self> {
+        if bytes.len() != ELEMENT_BYTES {
+            return Err(Error::LengthMismatch {
+                expected: ELEMENT_BYTES,
+                actual: bytes.len(),
+            });
+        }
+        FieldElement::try_new(U256::from_little_endian(bytes), field)
+    }
+
+    pub fn from_str_radix(s: &str, radix: u32, field: Field) -> crate::Result<Self> {
+        let digits = match radix {
+            16 => s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s),
+            _ => s,
+        };
+        let value = U256::from_str_radix(digits, radix).map_err(|_| {
+            Error::InvalidParameters(format!("invalid base-{} integer {:?}", radix, s))
+        })?;
+        FieldElement::try_new(value, field)
     }
 
     pub fn inv(&self) -> FieldElement {
-        self.field.inv(&self)
+        self.field.inv(self)
+    }
+
+    pub fn pow(&self, exponent: U256) -> FieldElement {
+        self.field.pow(self, exponent)
+    }
+
+    pub fn pow_signed(&self, exponent: i128) -> FieldElement {
+        self.field.pow_signed(self, exponent)
+    }
+
+    pub fn frobenius(&self) -> FieldElement {
+        self.field.pow_p(self)
     }
 
     pub fn is_zero(&self) -> bool {
         self.value == ZERO
     }
+
+    pub fn is_square(&self) -> bool {
+        self.field.legendre(self) >= 0
+    }
 }
 
 impl std::ops::Add<&FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn add(self, rhs: &FieldElement) -> FieldElement {
+        debug_assert_eq!(self.field, rhs.field, "elements belong to different fields");
         self.field.add(self, rhs)
     }
 }
@@ -43,6 +111,7 @@ impl std::ops::Sub<&FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn sub(self, rhs: &FieldElement) -> FieldElement {
+        debug_assert_eq!(self.field, rhs.field, "elements belong to different fields");
         self.field.sub(self, rhs)
     }
 }
@@ -51,6 +120,7 @@ impl std::ops::Mul<&FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn mul(self, rhs: &FieldElement) -> FieldElement {
+        debug_assert_eq!(self.field, rhs.field, "elements belong to different fields");
         self.field.mul(self, rhs)
     }
 }
@@ -59,10 +129,64 @@ impl std::ops::Div<&FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn div(self, rhs: &FieldElement) -> FieldElement {
+        debug_assert_eq!(self.field, rhs.field, "elements belong to different fields");
         self.field.div(self, rhs)
     }
 }
 
+macro_rules! forward_binop {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        impl std::ops::$op<FieldElement> for FieldElement {
+            type Output = FieldElement;
+
+            fn $method(self, rhs: FieldElement) -> FieldElement {
+                std::ops::$op::$method(&self, &rhs)
+            }
+        }
+
+        impl std::ops::$op<&FieldElement> for FieldElement {
+            type Output = FieldElement;
+
+            fn $method(self, rhs: &FieldElement) -> FieldElement {
+                std::ops::$op::$method(&self, rhs)
+            }
+        }
+
+        impl std::ops::$op<FieldElement> for &FieldElement {
+            type Output = FieldElement;
+
+            fn $method(self, rhs: FieldElement) -> FieldElement {
+                std::ops::$op::$method(self, &rhs)
+            }
+        }
+
+        impl std::ops::$assign<FieldElement> for FieldElement {
+            fn $assign_method(&mut self, rhs: FieldElement) {
+                *self = std::ops::$op::$method(&*self, &rhs);
+            }
+        }
+
+        impl std::ops::$assign<&FieldElement> for FieldElement {
+            fn $assign_method(&mut self, rhs: &FieldElement) {
+                *self = std::ops::$op::$method(&*self, rhs);
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add, AddAssign, add_assign);
+forward_binop!(Sub, sub, SubAssign, sub_assign);
+forward_binop!(Mul, mul, MulAssign, mul_assign);
+forward_binop!(Div, div, DivAssign, div_assign);
+
+impl std::ops::Neg for FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        -&self
+    }
+}
+
 impl std::ops::Neg for &FieldElement {
     type Output = FieldElement;
 
@@ -75,26 +199,15 @@ impl std::ops::BitXor<U256> for &FieldElement {
     type Output = FieldElement;
 
     fn bitxor(self, rhs: U256) -> FieldElement {
-        let mut acc = self.field.one();
-
-        let mut i: U256 = 128.into();
-        while i > ZERO {
-            i -= ONE;
-            if (rhs >> i) & ONE == ONE {
-                break;
-            }
-        }
+        self.pow(rhs)
+    }
+}
 
-        i += ONE;
-        while i > ZERO {
-            i -= ONE;
-            acc = &acc * &acc;
-            if (ONE << i) & rhs != ZERO {
-                acc = &acc * &self;
-            }
-        }
+impl std::ops::BitXor<U256> for FieldElement {
+    type Output = FieldElement;
 
-        acc
+    fn bitxor(self, rhs: U256) -> FieldElement {
+        self.pow(rhs)
     }
 }
 
@@ -121,11 +234,11 @@ impl<'de> Deserialize<'de> for FieldElement {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Fields {
-            FIELD,
-            LLOW,
-            HLOW,
-            LHIGH,
-            HHIGH,
+            Field,
+            Llow,
+            Hlow,
+            Lhigh,
+            Hhigh,
         }
 
         struct FieldElementVisitor;
@@ -148,34 +261,34 @@ impl<'de> Deserialize<'de> for FieldElement {
 
                 while let Some(key) = map.next_key()? {
                     match key {
-                        Fields::FIELD => {
+                        Fields::Field => {
                             if llow.is_some() {
                                 return Err(de::Error::duplicate_field("field"));
                             }
                             field = Some(map.next_value()?);
                         }
-                        Fields::LLOW => {
+                        Fields::Llow => {
                             if llow.is_some() {
                                 return Err(de::Error::duplicate_field("llow"));
                             }
                             let v: i64 = map.next_value()?;
                             llow = Some(v as u64);
                         }
-                        Fields::HLOW => {
+                        Fields::Hlow => {
                             if hlow.is_some() {
                                 return Err(de::Error::duplicate_field("hlow"));
                             }
                             let v: i64 = map.next_value()?;
                             hlow = Some(v as u64);
                         }
-                        Fields::LHIGH => {
+                        Fields::Lhigh => {
                             if lhigh.is_some() {
                                 return Err(de::Error::duplicate_field("lhigh"));
                             }
                             let v: i64 = map.next_value()?;
                             lhigh = Some(v as u64);
                         }
-                        Fields::HHIGH => {
+                        Fields::Hhigh => {
                             if hhigh.is_some() {
                                 return Err(de::Error::duplicate_field("hhigh"));
                             }
@@ -185,9 +298,7 @@ impl<'de> Deserialize<'de> for FieldElement {
                     }
                 }
 
-                let field = field
-                    .ok_or_else(|| de::Error::missing_field("field"))?
-                    .into();
+                let field = field.ok_or_else(|| de::Error::missing_field("field"))?;
                 let mut value: U256 = llow.ok_or_else(|| de::Error::missing_field("llow"))?.into();
                 let hlow: U256 = hlow.ok_or_else(|| de::Error::missing_field("hlow"))?.into();
                 let lhigh: U256 = lhigh
@@ -197,11 +308,11 @@ impl<'de> Deserialize<'de> for FieldElement {
                     .ok_or_else(|| de::Error::missing_field("hhigh"))?
                     .into();
 
-                value = value | (hlow << 64);
-                value = value | (lhigh << 128);
-                value = value | (hhigh << 192);
+                value |= hlow << 64;
+                value |= lhigh << 128;
+                value |= hhigh << 192;
 
-                Ok(FieldElement { value, field })
+                FieldElement::try_new(value, field).map_err(de::Error::custom)
             }
         }
 
@@ -212,7 +323,7 @@ impl<'de> Deserialize<'de> for FieldElement {
 
 #[cfg(test)]
 mod tests {
-    use crate::PRIME;
+    use crate::{consts::ONE, field::Inversion, PRIME};
 
     use super::*;
 
@@ -229,20 +340,135 @@ mod tests {
         assert_ne!(e2, e1);
     }
 
+    #[test]
+    fn interning_test() {
+        let f = Field::new(*PRIME);
+        let e1 = FieldElement::new(ONE, f);
+        let e2 = FieldElement::new(3.into(), Field::new(*PRIME));
+        assert!(std::ptr::eq(e1.field, e2.field));
+        assert!(std::ptr::eq((e1 * e2).field, e1.field));
+        assert!(std::ptr::eq(f.interned(), e1.field));
+        assert_eq!(std::mem::size_of::<FieldElement>(), 40);
+
+        let fermat = FieldElement::new(ONE, f.with_inversion(Inversion::Fermat));
+        assert!(!std::ptr::eq(fermat.field, e1.field));
+        assert_eq!(fermat.field.inversion, Inversion::Fermat);
+        assert_eq!(fermat, e1);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn by_value_test() {
+        let f = Field::new(*PRIME);
+        let (a, b) = (f.generator(), FieldElement::new(12345.into(), f));
+        assert_eq!(a + b, f.add(&a, &b));
+        assert_eq!(a - &b, f.sub(&a, &b));
+        assert_eq!(&a * b, f.mul(&a, &b));
+        assert_eq!(a / b, f.div(&a, &b));
+        assert_eq!(-a, f.neg(&a));
+        assert_eq!(a ^ 3.into(), f.pow(&a, 3.into()));
+
+        let mut c = a;
+        c += b;
+        c -= &a;
+        assert_eq!(c, b);
+        c *= a;
+        c /= &b;
+        assert_eq!(c, a);
+    }
+
     #[test]
     fn arithmetic_test() {
         let f = Field::new(7.into());
         let e1 = FieldElement::new(ONE, f);
         let e2 = FieldElement::new(3.into(), f);
-        assert_eq!((&e1 + &e2).value, 4.into());
-        assert_eq!((&e1 - &e2).value, 5.into());
-        assert_eq!((&e1 * &e2).value, 3.into());
-        assert_eq!((&e1 / &e2).value, 5.into());
+        assert_eq!((e1 + e2).value, 4.into());
+        assert_eq!((e1 - e2).value, 5.into());
+        assert_eq!((e1 * e2).value, 3.into());
+        assert_eq!((e1 / e2).value, 5.into());
         assert_eq!((-&e1).value, 6.into());
-        assert_eq!((&e2.inv()).value, 5.into());
-        assert_eq!((&e2 ^ 4.into()).value, 4.into());
-        assert_eq!((&e2 ^ 2.into()).value, 2.into());
-        assert_eq!((&e1 ^ 2.into()).value, 1.into());
+        assert_eq!(e2.inv().value, 5.into());
+        assert_eq!((e2 ^ 4.into()).value, 4.into());
+        assert_eq!((e2 ^ 2.into()).value, 2.into());
+        assert_eq!((e1 ^ 2.into()).value, 1.into());
+    }
+
+    #[test]
+    fn constructors_test() {
+        let f = Field::new(*PRIME);
+        assert_eq!(f.element(7), FieldElement::new(7.into(), f));
+        assert_eq!(f.element(7u64), FieldElement::from_u64(7, f));
+        assert_eq!(f.element(u128::MAX), FieldElement::from_u128(u128::MAX, f));
+        assert_eq!(f.element(*PRIME + 7), f.element(7u8));
+        assert_eq!(FieldElement::from_u128(PRIME.as_u128(), f), f.zero());
+    }
+
+    #[test]
+    fn canonical_test() {
+        let f = Field::new(*PRIME);
+        let a = FieldElement::new(*PRIME + 5, f);
+        assert_eq!(a.value, 5.into());
+        assert_eq!(a, FieldElement::new(5.into(), f));
+        assert!(matches!(
+            FieldElement::try_new(*PRIME, f),
+            Err(Error::NonCanonical)
+        ));
+        assert_eq!(FieldElement::try_new(5.into(), f).unwrap(), a);
+
+        let mut forged = a;
+        forged.value = *PRIME + 5;
+        let serialized = serde_pickle::to_vec(&forged, Default::default()).unwrap();
+        assert!(serde_pickle::from_slice::<FieldElement>(&serialized, Default::default()).is_err());
+    }
+
+    #[test]
+    fn from_str_radix_test() {
+        let f = Field::new(*PRIME);
+        let minus_one = FieldElement::new(*PRIME - 1, f);
+        assert_eq!(
+            FieldElement::from_str_radix("270497897142230380135924736767050121216", 10, f).unwrap(),
+            minus_one
+        );
+        assert_eq!(
+            FieldElement::from_str_radix("0xcb800000000000000000000000000000", 16, f).unwrap(),
+            minus_one
+        );
+        assert_eq!(
+            FieldElement::from_str_radix("0XCB800000000000000000000000000000", 16, f).unwrap(),
+            minus_one
+        );
+        assert_eq!(
+            FieldElement::from_str_radix("ff", 16, f).unwrap(),
+            FieldElement::new(255.into(), f)
+        );
+        assert!(matches!(
+            FieldElement::from_str_radix("cb800000000000000000000000000001", 16, f),
+            Err(Error::NonCanonical)
+        ));
+        assert!(FieldElement::from_str_radix("12a", 10, f).is_err());
+    }
+
+    #[test]
+    fn bytes_test() {
+        let f = Field::new(*PRIME);
+        let g = f.generator();
+        let bytes = g.to_bytes();
+        assert_eq!(bytes.len(), ELEMENT_BYTES);
+        assert_eq!(bytes[0], g.value.low_u64() as u8);
+        assert_eq!(FieldElement::from_bytes(&bytes, f).unwrap(), g);
+        assert!(matches!(
+            FieldElement::from_bytes(&bytes[1..], f),
+            Err(Error::LengthMismatch {
+                expected: 32,
+                actual: 31
+            })
+        ));
+        let mut p = [0u8; 32];
+        PRIME.to_little_endian(&mut p);
+        assert!(matches!(
+            FieldElement::from_bytes(&p, f),
+            Err(Error::NonCanonical)
+        ));
     }
 
     #[test]
@@ -0,0 +1,41 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("elements belong to different fields")]
+    FieldMismatch,
+    #[error("bad domain size {0}")]
+    BadDomainSize(usize),
+    #[error("length mismatch: expected {expected}, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    #[error("value is not below the modulus")]
+    NonCanonical,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("division leaves a non-zero remainder")]
+    InexactDivision,
+    #[error("index {index} out of range for length {len}")]
+    IndexOutOfRange { index: usize, len: usize },
+    #[error("cannot sample {number} distinct indices out of {size}")]
+    TooManySamples { number: usize, size: usize },
+    #[error("gave up sampling after {attempts} attempts")]
+    SamplingFailed { attempts: usize },
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+    #[error("transition constraint {constraint} violated at row {row}")]
+    ConstraintViolation { row: usize, constraint: usize },
+    #[error("malformed proof: {0}")]
+    MalformedProof(&'static str),
+    #[error("malformed test vector: {0}")]
+    MalformedVector(String),
+    #[error("{kind} vector {index} does not match")]
+    VectorMismatch { kind: &'static str, index: usize },
+    #[error("serialization error: {0}")]
+    Serialization(#[from] serde_pickle::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum Step {
+    Domain {
+        length: usize,
+        offset: String,
+        omega: String,
+        rounds: usize,
+    },
+    DegreeCorrection {
+        max_degree: usize,
+        shift: usize,
+        beta: String,
+    },
+    Commit {
+        round: usize,
+        length: usize,
+        root: String,
+    },
+    Challenge {
+        round: usize,
+        alpha: String,
+    },
+    Fold {
+        round: usize,
+        length: usize,
+    },
+    LastCodeword {
+        length: usize,
+        max_degree: usize,
+    },
+    SampleIndices {
+        indices: Vec<usize>,
+    },
+    Query {
+        round: usize,
+        indices: Vec<usize>,
+    },
+}
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Explanation {
+    pub steps: Vec<Step>,
+}
+
+// Receives prover steps; `()` drops them unbuilt so plain proving never
+// formats them.
+pub(crate) trait Explainer {
+    fn explain(&mut self, _step: impl FnOnce() -> Step) {}
+}
+
+impl Explainer for () {}
+
+impl Explainer for Explanation {
+    fn explain(&mut self, step: impl FnOnce() -> Step) {
+        self.steps.push(step());
+    }
+}
+
+impl Explanation {
+    pub fn new() -> Self {
+        Explanation { steps: vec![] }
+    }
+
+    pub fn record(&mut self, step: Step) {
+        self.steps.push(step);
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
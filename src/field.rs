@@ -1,52 +1,527 @@
-use crate::{consts::*, element::FieldElement, xgcd};
-use primitive_types::U256;
+use crate::{
+    bigint::{Backend, ModularInt},
+    binary_inverse,
+    consts::*,
+    element::FieldElement,
+    hash, xgcd, Error,
+};
+use once_cell::sync::{Lazy, OnceCell};
+use primitive_types::{U256, U512};
 use serde::{
     de,
     de::{MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Serialize,
 };
-use std::fmt;
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fmt,
+    sync::Mutex,
+};
+
+static TWO_ADIC_ROOTS: Lazy<Mutex<HashMap<U256, Vec<U256>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static GENERATORS: Lazy<Mutex<HashMap<U256, U256>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Failed factorizations are cached too, so a hard p - 1 spends its rho
+// budget once.
+static FACTORS: Lazy<Mutex<HashMap<U256, Option<Factors>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static GENERATOR_POWERS: Lazy<Mutex<HashMap<U256, Vec<U256>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Every distinct field is leaked once, so new moduli registered through
+// `try_new` are capped. Fields built with `Field::new` do not count.
+pub const MAX_FIELDS: usize = 1 << 12;
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+type FieldKey = (U256, Inversion, Reduction, [bool; 3]);
+
+type Factors = Vec<(U256, u32)>;
+
+#[derive(Default)]
+struct Registry {
+    fields: HashMap<FieldKey, &'static OnceCell<Field>>,
+    untrusted: usize,
+}
+
+impl Registry {
+    fn cell(&mut self, key: FieldKey, bounded: bool) -> crate::Result<&'static OnceCell<Field>> {
+        if let Some(cell) = self.fields.get(&key) {
+            return Ok(*cell);
+        }
+        if bounded {
+            if self.untrusted >= MAX_FIELDS {
+                return Err(Error::InvalidParameters(format!(
+                    "more than {} untrusted fields",
+                    MAX_FIELDS
+                )));
+            }
+            self.untrusted += 1;
+        }
+        Ok(*self
+            .fields
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(OnceCell::new()))))
+    }
+}
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(Registry::default()));
+
+static PRIMES: Lazy<Mutex<HashSet<U256>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+pub mod babybear;
+pub mod barrett;
+pub mod binary;
+pub mod constant;
+pub mod factor;
+pub mod finite;
+pub mod mersenne31;
+pub mod montgomery;
+pub mod presets;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod word;
+
+use barrett::Barrett;
+pub use finite::{FiniteField, FiniteFieldElement};
+use montgomery::Montgomery;
+use presets::Preset;
+use word::Word128;
+
+const WINDOW_BITS: usize = 4;
+
+// Upper bound on the small values tried when searching for generators.
+const MAX_CANDIDATES: u32 = 1 << 16;
+
+/// Left-to-right sliding-window exponentiation over precomputed odd powers.
+pub(crate) fn windowed_pow<T, F>(base: T, one: T, exponent: U256, mul: F) -> T
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+{
+    // Short exponents do not amortize the table, so fall back to plain square-and-multiply.
+    let window_bits = if exponent.bits() < 4 * WINDOW_BITS {
+        1
+    } else {
+        WINDOW_BITS
+    };
+    let mut odd = vec![base; 1 << (window_bits - 1)];
+    if odd.len() > 1 {
+        let square = mul(base, base);
+        for k in 1..odd.len() {
+            odd[k] = mul(odd[k - 1], square);
+        }
+    }
+    let mut acc = one;
+    let mut i = exponent.bits();
+    while i > 0 {
+        if !exponent.bit(i - 1) {
+            acc = mul(acc, acc);
+            i -= 1;
+            continue;
+        }
+        let mut low = i.saturating_sub(window_bits);
+        while !exponent.bit(low) {
+            low += 1;
+        }
+        let mut window = 0;
+        for bit in (low..i).rev() {
+            acc = mul(acc, acc);
+            window = (window << 1) | exponent.bit(bit) as usize;
+        }
+        acc = mul(acc, odd[window >> 1]);
+        i = low;
+    }
+    acc
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub enum Inversion {
+    #[default]
+    Xgcd,
+    Fermat,
+    BinaryGcd,
+}
+
+/// Elements stay canonical under every reduction, so `Montgomery` mainly
+/// pays off in `pow`, which keeps its accumulator in Montgomery form.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub enum Reduction {
+    #[default]
+    Plain,
+    Montgomery,
+    Barrett,
+}
+
+impl Reduction {
+    fn kernels(&self, p: U256) -> (Option<Montgomery>, Option<Barrett>, Option<Word128>) {
+        match self {
+            Reduction::Barrett => (None, Barrett::new(p), None),
+            // crypto-bigint residues carry both plain and Montgomery arithmetic.
+            _ if cfg!(feature = "crypto-bigint") => (None, None, None),
+            Reduction::Plain => (None, None, Word128::new(p)),
+            Reduction::Montgomery => (Montgomery::new(p), None, Word128::new(p)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct Field {
-    pub p: U256,
+    // Private so the kernels below, which are derived from them, cannot go stale.
+    p: U256,
+    pub inversion: Inversion,
+    reduction: Reduction,
+    montgomery: Option<Montgomery>,
+    barrett: Option<Barrett>,
+    word: Option<Word128>,
+    handle: Option<Handle>,
+}
+
+// Points at the registered copy of a field, which points at itself.
+#[derive(Clone, Copy)]
+struct Handle(&'static OnceCell<Field>);
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Handle")
+    }
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p
+    }
+}
+
+impl Eq for Field {}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", self.p)
+    }
+}
+
+impl std::str::FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let s = s.trim();
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let p = U256::from_str_radix(digits, 16)
+            .map_err(|_| Error::InvalidParameters(format!("invalid hex modulus {:?}", s)))?;
+        Field::try_new(p)
+    }
 }
 
 impl Field {
+    /// Trusts `p` and ignores `MAX_FIELDS`; moduli from untrusted input
+    /// should go through `try_new`.
     pub fn new(p: U256) -> Self {
-        Field { p }
+        *Field::unregistered(p).intern()
+    }
+
+    pub fn try_new(p: U256) -> crate::Result<Self> {
+        if p < 3.into() || !p.bit(0) {
+            return Err(Error::InvalidParameters(format!(
+                "modulus {} is not an odd prime",
+                p
+            )));
+        }
+        let known = PRIMES.lock().unwrap().contains(&p);
+        if !known && !factor::is_probable_prime(p) {
+            return Err(Error::InvalidParameters(format!(
+                "modulus {} is composite",
+                p
+            )));
+        }
+        let field = *Field::unregistered(p).register(true)?;
+        PRIMES.lock().unwrap().insert(p);
+        Ok(field)
+    }
+
+    fn unregistered(p: U256) -> Self {
+        let reduction = Reduction::default();
+        let (montgomery, barrett, word) = reduction.kernels(p);
+        Field {
+            p,
+            inversion: Inversion::default(),
+            reduction,
+            montgomery,
+            barrett,
+            word,
+            handle: None,
+        }
+    }
+
+    fn key(&self) -> FieldKey {
+        (
+            self.p,
+            self.inversion,
+            self.reduction,
+            [
+                self.montgomery.is_some(),
+                self.barrett.is_some(),
+                self.word.is_some(),
+            ],
+        )
+    }
+
+    fn intern(&self) -> &'static Field {
+        match self.register(false) {
+            Ok(field) => field,
+            Err(_) => unreachable!("unbounded registration cannot fail"),
+        }
+    }
+
+    fn register(&self, bounded: bool) -> crate::Result<&'static Field> {
+        let cell = REGISTRY.lock().unwrap().cell(self.key(), bounded)?;
+        Ok(cell.get_or_init(|| Field {
+            handle: Some(Handle(cell)),
+            ..*self
+        }))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.p.to_little_endian(&mut bytes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        if bytes.len() != 32 {
+            return Err(Error::LengthMismatch {
+                expected: 32,
+                actual: bytes.len(),
+            });
+        }
+        Field::try_new(U256::from_little_endian(bytes))
+    }
+
+    pub fn p(&self) -> U256 {
+        self.p
+    }
+
+    pub fn reduction(&self) -> Reduction {
+        self.reduction
+    }
+
+    pub fn order(&self) -> U256 {
+        self.p
+    }
+
+    pub fn bits(&self) -> usize {
+        self.p.bits()
+    }
+
+    pub fn interned(&self) -> &'static Field {
+        match self.handle.and_then(|handle| handle.0.get()) {
+            Some(field) if field.key() == self.key() => field,
+            _ => self.intern(),
+        }
+    }
+
+    pub fn from_preset(preset: Preset) -> Self {
+        Field::new(preset.prime())
+    }
+
+    pub fn preset(&self) -> Option<Preset> {
+        Preset::from_prime(self.p)
+    }
+
+    pub fn with_inversion(mut self, inversion: Inversion) -> Self {
+        self.inversion = inversion;
+        *self.interned()
+    }
+
+    /// Falls back to plain division when the modulus does not support the
+    /// requested reduction (even moduli for Montgomery, 256-bit ones for Barrett).
+    pub fn with_reduction(mut self, reduction: Reduction) -> Self {
+        self.reduction = reduction;
+        (self.montgomery, self.barrett, self.word) = reduction.kernels(self.p);
+        *self.interned()
+    }
+
+    pub fn element(&self, value: impl Into<U256>) -> FieldElement {
+        FieldElement::new(value.into(), *self)
     }
 
     pub fn zero(&self) -> FieldElement {
         FieldElement {
             value: ZERO,
-            field: *self,
+            field: self.interned(),
         }
     }
 
     pub fn one(&self) -> FieldElement {
         FieldElement {
             value: ONE,
-            field: *self,
+            field: self.interned(),
         }
     }
 
+    /// Panicking shorthand for `try_generator`.
     pub fn generator(&self) -> FieldElement {
-        assert!(self.p == *PRIME);
-        return FieldElement::new(*GENERATOR, *self);
+        self.try_generator().expect("no two-adic generator")
+    }
+
+    /// The tutorial's coset offset: an element of order 2^s generating the
+    /// two-adic subgroup, where s is `two_adicity`. Presets return their
+    /// published constant, which is such an element, and every other prime
+    /// gets `two_adic_generator(s)`. This is not a generator of the whole
+    /// multiplicative group unless p - 1 is a power of two; use
+    /// `multiplicative_generator` for that.
+    pub fn try_generator(&self) -> Option<FieldElement> {
+        match self.preset() {
+            Some(preset) => Some(FieldElement::new(preset.generator(), *self)),
+            None => self.two_adic_generator(self.two_adicity()),
+        }
     }
 
+    /// The smallest generator of the multiplicative group, for every prime.
+    /// Gives up with `None` once factoring p - 1 exhausts `factor::RHO_BUDGET`.
+    pub fn multiplicative_generator(&self) -> Option<FieldElement> {
+        if let Some(value) = GENERATORS.lock().unwrap().get(&self.p) {
+            return Some(FieldElement::new(*value, *self));
+        }
+        let generator = self.multiplicative_generator_with(&self.factors()?)?;
+        GENERATORS.lock().unwrap().insert(self.p, generator.value);
+        Some(generator)
+    }
+
+    pub fn generator_powers(&self, n: usize) -> Option<Vec<FieldElement>> {
+        let generator = self.try_generator()?;
+        let mut cache = GENERATOR_POWERS.lock().unwrap();
+        let powers = cache.entry(self.p).or_insert_with(|| vec![ONE]);
+        while powers.len() < n {
+            let last = FieldElement::new(*powers.last().unwrap(), *self);
+            powers.push((last * generator).value);
+        }
+        Some(
+            powers[..n]
+                .iter()
+                .map(|value| FieldElement::new(*value, *self))
+                .collect(),
+        )
+    }
+
+    pub fn half(&self) -> FieldElement {
+        assert!(self.p.bit(0));
+        FieldElement::new((self.p >> 1) + ONE, *self)
+    }
+
+    /// Panicking shorthand for `nth_root`.
     pub fn primitive_nth_root(&self, n: U256) -> FieldElement {
-        assert!(self.p == *PRIME);
-        assert!(n <= (1u128 << 119).into() && n & (n - 1) == ZERO);
-        let mut root = self.generator();
-        let mut order: U256 = (1u128 << 119).into();
-        while order != n {
-            root = &root ^ *TWO;
-            order = order >> 1;
+        self.nth_root(n).expect("n must divide p - 1")
+    }
+
+    pub fn nth_root(&self, n: U256) -> Option<FieldElement> {
+        if n.is_zero() || (self.p - ONE) % n != ZERO {
+            return None;
         }
-        root
+        if n & (n - 1) == ZERO {
+            return self.two_adic_generator(n.trailing_zeros());
+        }
+        self.primitive_nth_root_with(n, &self.factors()?)
+    }
+
+    pub fn factors(&self) -> Option<Vec<(U256, u32)>> {
+        match self.preset() {
+            Some(preset) => Some(preset.factors()),
+            None => FACTORS
+                .lock()
+                .unwrap()
+                .entry(self.p)
+                .or_insert_with(|| factor::factor(self.p - ONE))
+                .clone(),
+        }
+    }
+
+    pub fn two_adicity(&self) -> u32 {
+        (self.p - ONE).trailing_zeros()
+    }
+
+    pub fn two_adic_generator(&self, k: u32) -> Option<FieldElement> {
+        if k > self.two_adicity() {
+            return None;
+        }
+        let mut cache = TWO_ADIC_ROOTS.lock().unwrap();
+        let roots = match cache.entry(self.p) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.two_adic_roots()?),
+        };
+        Some(FieldElement::new(roots[k as usize], *self))
+    }
+
+    fn two_adic_roots(&self) -> Option<Vec<U256>> {
+        let s = self.two_adicity();
+        let mut root = match self.preset() {
+            Some(_) => self.generator(),
+            None => {
+                let minus_one = FieldElement::new(self.p - ONE, *self);
+                let non_residue = self
+                    .candidates()
+                    .find(|g| g ^ ((self.p - ONE) >> 1) == minus_one)?;
+                non_residue ^ ((self.p - ONE) >> s)
+            }
+        };
+        let mut roots = vec![ZERO; s as usize + 1];
+        for k in (0..=s as usize).rev() {
+            roots[k] = root.value;
+            root = root * root;
+        }
+        Some(roots)
+    }
+
+    pub fn multiplicative_generator_with(&self, factors: &[(U256, u32)]) -> Option<FieldElement> {
+        let order = self.p - ONE;
+        let mut product = ONE;
+        for (q, e) in factors {
+            for _ in 0..*e {
+                product = product.checked_mul(*q)?;
+            }
+        }
+        if product != order {
+            return None;
+        }
+        self.candidates()
+            .find(|g| factors.iter().all(|(q, _)| g ^ (order / *q) != self.one()))
+    }
+
+    fn candidates(&self) -> impl Iterator<Item = FieldElement> + '_ {
+        let bound = self.p.min(MAX_CANDIDATES.into()).low_u32();
+        (2..bound).map(|g| FieldElement::new(g.into(), *self))
+    }
+
+    pub fn primitive_nth_root_with(
+        &self,
+        n: U256,
+        factors: &[(U256, u32)],
+    ) -> Option<FieldElement> {
+        let order = self.p - ONE;
+        if n.is_zero() || order % n != ZERO {
+            return None;
+        }
+        Some(self.multiplicative_generator_with(factors)? ^ (order / n))
+    }
+
+    pub fn powers(&self, base: &FieldElement, n: usize) -> Vec<FieldElement> {
+        let mut power = self.one();
+        (0..n)
+            .map(|_| {
+                let current = power;
+                power *= base;
+                current
+            })
+            .collect()
+    }
+
+    pub fn batch_pow(&self, bases: &[FieldElement], exponent: U256) -> Vec<FieldElement> {
+        bases.iter().map(|base| self.pow(base, exponent)).collect()
     }
 
     pub fn sample(&self, byte_array: &[u8]) -> FieldElement {
@@ -57,48 +532,339 @@ impl Field {
         FieldElement::new(acc % self.p, *self)
     }
 
+    pub fn hash_to_field(&self, domain_tag: &[u8], msg: &[u8]) -> FieldElement {
+        let num_bytes = (self.p.bits() + hash::SECURITY_BITS).div_ceil(8);
+        let bytes = hash::expand_message_xof(domain_tag, msg, num_bytes)
+            .expect("expansion length is bounded by the modulus size");
+        self.sample_wide(&bytes)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::RngCore>(&self, rng: &mut R) -> FieldElement {
+        let mask = U256::MAX >> (256 - self.p.bits());
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            let value = U256::from_big_endian(&bytes) & mask;
+            if value < self.p {
+                return FieldElement::new(value, *self);
+            }
+        }
+    }
+
+    pub fn sample_wide(&self, byte_array: &[u8]) -> FieldElement {
+        let mut acc = U512::zero();
+        byte_array.iter().for_each(|b| {
+            acc = (acc << 8) ^ (*b).into();
+        });
+        let value = U256::try_from(acc % U512::from(self.p)).unwrap();
+        FieldElement::new(value, *self)
+    }
+
+    /// Accumulates full-width products and reduces once at the end, falling
+    /// back to an intermediate reduction only if the accumulator would overflow.
+    pub fn sum_of_products<I>(&self, terms: I) -> FieldElement
+    where
+        I: IntoIterator<Item = (FieldElement, FieldElement)>,
+    {
+        let modulus = U512::from(self.p);
+        let mut acc = U512::zero();
+        for (left, right) in terms {
+            let product = left.value.full_mul(right.value);
+            acc = match acc.overflowing_add(product) {
+                (sum, false) => sum,
+                (_, true) => acc % modulus + product,
+            };
+        }
+        FieldElement {
+            value: U256::try_from(acc % modulus).unwrap(),
+            field: self.interned(),
+        }
+    }
+
     pub fn add(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        let value = match (self.word, self.barrett) {
+            (Some(word), _) => word.add(left.value, right.value),
+            (None, Some(barrett)) => barrett.add(left.value, right.value),
+            (None, None) => return self.apply(left, right, <Backend as ModularInt>::add_mod),
+        };
         FieldElement {
-            value: (left.value + right.value) % self.p,
-            field: *self,
+            value,
+            field: left.field,
         }
     }
     pub fn sub(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
-        FieldElement {
-            value: (self.p + left.value - right.value) % self.p,
-            field: *self,
+        match self.word {
+            Some(word) => FieldElement {
+                value: word.sub(left.value, right.value),
+                field: left.field,
+            },
+            None => self.apply(left, right, <Backend as ModularInt>::sub_mod),
         }
     }
     pub fn mul(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        if let Some(word) = self.word {
+            return FieldElement {
+                value: word.mul_canonical(left.value, right.value),
+                field: left.field,
+            };
+        }
+        let value = match (self.montgomery, self.barrett) {
+            (Some(montgomery), _) => montgomery.mul_canonical(left.value, right.value),
+            (None, Some(barrett)) => barrett.mul(left.value, right.value),
+            (None, None) => return self.apply(left, right, <Backend as ModularInt>::mul_mod),
+        };
+        FieldElement {
+            value,
+            field: left.field,
+        }
+    }
+    pub fn pow(&self, base: &FieldElement, exponent: U256) -> FieldElement {
+        let value = match (self.word, self.montgomery, self.barrett) {
+            (Some(word), _, _) => word.pow(base.value, exponent),
+            (None, Some(montgomery), _) => montgomery
+                .from_montgomery(montgomery.pow(montgomery.to_montgomery(base.value), exponent)),
+            (None, None, Some(barrett)) => {
+                windowed_pow(base.value, ONE, exponent, |a, b| barrett.mul(a, b))
+            }
+            (None, None, None) => Backend::from_u256(base.value)
+                .pow_mod(exponent, Backend::from_u256(self.p))
+                .to_u256(),
+        };
         FieldElement {
-            value: (left.value * right.value) % self.p,
-            field: *self,
+            value,
+            field: base.field,
         }
     }
+    fn check(&self, left: &FieldElement, right: &FieldElement) -> crate::Result<()> {
+        if left.field != self || right.field != self {
+            return Err(Error::FieldMismatch);
+        }
+        Ok(())
+    }
+    pub fn checked_add(
+        &self,
+        left: &FieldElement,
+        right: &FieldElement,
+    ) -> crate::Result<FieldElement> {
+        self.check(left, right)?;
+        Ok(self.add(left, right))
+    }
+    pub fn checked_sub(
+        &self,
+        left: &FieldElement,
+        right: &FieldElement,
+    ) -> crate::Result<FieldElement> {
+        self.check(left, right)?;
+        Ok(self.sub(left, right))
+    }
+    pub fn checked_mul(
+        &self,
+        left: &FieldElement,
+        right: &FieldElement,
+    ) -> crate::Result<FieldElement> {
+        self.check(left, right)?;
+        Ok(self.mul(left, right))
+    }
+    pub fn checked_div(
+        &self,
+        left: &FieldElement,
+        right: &FieldElement,
+    ) -> crate::Result<FieldElement> {
+        self.check(left, right)?;
+        if right.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        Ok(self.div(left, right))
+    }
+    pub fn pow_signed(&self, base: &FieldElement, exponent: i128) -> FieldElement {
+        let magnitude = exponent.unsigned_abs().into();
+        if exponent < 0 {
+            assert!(!base.is_zero());
+            self.pow(&self.inv(base), magnitude)
+        } else {
+            self.pow(base, magnitude)
+        }
+    }
+    /// Frobenius map x -> x^p. Fermat's little theorem makes it the identity
+    /// on a prime field, so no exponentiation is needed.
+    pub fn pow_p(&self, operand: &FieldElement) -> FieldElement {
+        *operand
+    }
     pub fn div(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
         assert!(right.value != ZERO);
-        let (a, _, _, a_neg, _) = xgcd(right.value, self.p);
+        self.mul(left, &self.inv(right))
+    }
+    pub fn neg(&self, operand: &FieldElement) -> FieldElement {
         FieldElement {
-            value: if a_neg {
-                self.p - (left.value * a) % self.p
-            } else {
-                left.value * a
-            } % self.p,
-            field: *self,
+            value: ModularInt::neg_mod(
+                Backend::from_u256(operand.value),
+                Backend::from_u256(self.p),
+            )
+            .to_u256(),
+            field: operand.field,
         }
     }
-    pub fn neg(&self, operand: &FieldElement) -> FieldElement {
+
+    fn apply<F>(&self, left: &FieldElement, right: &FieldElement, operation: F) -> FieldElement
+    where
+        F: Fn(Backend, Backend, Backend) -> Backend,
+    {
+        let value = operation(
+            Backend::from_u256(left.value),
+            Backend::from_u256(right.value),
+            Backend::from_u256(self.p),
+        );
         FieldElement {
-            value: (self.p - operand.value) % self.p,
-            field: *self,
+            value: value.to_u256(),
+            field: left.field,
         }
     }
 
     pub fn inv(&self, operand: &FieldElement) -> FieldElement {
+        match self.inversion {
+            Inversion::Xgcd => self.inv_xgcd(operand),
+            Inversion::Fermat => self.inv_pow(operand),
+            Inversion::BinaryGcd => self.inv_binary(operand),
+        }
+    }
+
+    pub fn batch_add(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        #[cfg(feature = "simd")]
+        if let Some(packed) = simd::Packed31::new(self.p) {
+            return self.packed(left, right, |l, r| packed.add(l, r));
+        }
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.add(l, r))
+            .collect()
+    }
+
+    pub fn batch_sub(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        #[cfg(feature = "simd")]
+        if let Some(packed) = simd::Packed31::new(self.p) {
+            return self.packed(left, right, |l, r| packed.sub(l, r));
+        }
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.sub(l, r))
+            .collect()
+    }
+
+    pub fn batch_mul(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        #[cfg(feature = "simd")]
+        if let Some(packed) = simd::Packed31::new(self.p) {
+            return self.packed(left, right, |l, r| packed.mul(l, r));
+        }
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.mul(l, r))
+            .collect()
+    }
+
+    #[cfg(feature = "simd")]
+    fn packed<F>(
+        &self,
+        left: &[FieldElement],
+        right: &[FieldElement],
+        kernel: F,
+    ) -> Vec<FieldElement>
+    where
+        F: Fn(&[u32], &[u32]) -> Vec<u32>,
+    {
+        let lanes = |values: &[FieldElement]| -> Vec<u32> {
+            values.iter().map(|value| value.value.low_u32()).collect()
+        };
+        let field = self.interned();
+        kernel(&lanes(left), &lanes(right))
+            .into_iter()
+            .map(|value| FieldElement {
+                value: value.into(),
+                field,
+            })
+            .collect()
+    }
+
+    pub fn batch_inv(&self, values: &[FieldElement]) -> Option<Vec<FieldElement>> {
+        let mut prefix = Vec::with_capacity(values.len());
+        let mut acc = self.one();
+        for value in values {
+            if value.is_zero() {
+                return None;
+            }
+            prefix.push(acc);
+            acc *= value;
+        }
+        let mut inverse = self.inv(&acc);
+        let mut inverses = vec![self.zero(); values.len()];
+        for i in (0..values.len()).rev() {
+            inverses[i] = inverse * prefix[i];
+            inverse *= values[i];
+        }
+        Some(inverses)
+    }
+
+    pub fn legendre(&self, operand: &FieldElement) -> i8 {
+        if operand.is_zero() {
+            return 0;
+        }
+        if operand ^ ((self.p - ONE) >> 1) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn sqrt(&self, operand: &FieldElement) -> Option<FieldElement> {
+        if operand.is_zero() {
+            return Some(self.zero());
+        }
+        if self.legendre(operand) != 1 {
+            return None;
+        }
+        let mut m = self.two_adicity();
+        let q = (self.p - ONE) >> m;
+        let mut t = operand ^ q;
+        let mut r = operand ^ ((q + ONE) >> 1);
+        if t == self.one() {
+            return Some(r);
+        }
+        let mut c = self.two_adic_generator(m)?;
+        while t != self.one() {
+            let mut i = 0;
+            let mut square = t;
+            while square != self.one() {
+                square = square * square;
+                i += 1;
+            }
+            let mut b = c;
+            for _ in 0..m - i - 1 {
+                b = b * b;
+            }
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+        Some(r)
+    }
+
+    pub fn inv_binary(&self, operand: &FieldElement) -> FieldElement {
+        FieldElement {
+            value: binary_inverse(operand.value, self.p),
+            field: operand.field,
+        }
+    }
+
+    pub fn inv_pow(&self, operand: &FieldElement) -> FieldElement {
+        operand ^ (self.p - *TWO)
+    }
+
+    pub fn inv_xgcd(&self, operand: &FieldElement) -> FieldElement {
         let (a, _, _, a_neg, _) = xgcd(operand.value, self.p);
         FieldElement {
             value: if a_neg { self.p - a } else { a } % self.p,
-            field: *self,
+            field: operand.field,
         }
     }
 }
@@ -126,10 +892,10 @@ impl<'de> Deserialize<'de> for Field {
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Fields {
-            LLOW,
-            HLOW,
-            LHIGH,
-            HHIGH,
+            Llow,
+            Hlow,
+            Lhigh,
+            Hhigh,
         }
 
         struct FieldVisitor;
@@ -151,28 +917,28 @@ impl<'de> Deserialize<'de> for Field {
 
                 while let Some(key) = map.next_key()? {
                     match key {
-                        Fields::LLOW => {
+                        Fields::Llow => {
                             if llow.is_some() {
                                 return Err(de::Error::duplicate_field("llow"));
                             }
                             let v: i64 = map.next_value()?;
                             llow = Some(v as u64);
                         }
-                        Fields::HLOW => {
+                        Fields::Hlow => {
                             if hlow.is_some() {
                                 return Err(de::Error::duplicate_field("hlow"));
                             }
                             let v: i64 = map.next_value()?;
                             hlow = Some(v as u64);
                         }
-                        Fields::LHIGH => {
+                        Fields::Lhigh => {
                             if lhigh.is_some() {
                                 return Err(de::Error::duplicate_field("lhigh"));
                             }
                             let v: i64 = map.next_value()?;
                             lhigh = Some(v as u64);
                         }
-                        Fields::HHIGH => {
+                        Fields::Hhigh => {
                             if hhigh.is_some() {
                                 return Err(de::Error::duplicate_field("hhigh"));
                             }
@@ -191,11 +957,11 @@ impl<'de> Deserialize<'de> for Field {
                     .ok_or_else(|| de::Error::missing_field("hhigh"))?
                     .into();
 
-                p = p | (hlow << 64);
-                p = p | (lhigh << 128);
-                p = p | (hhigh << 192);
+                p |= hlow << 64;
+                p |= lhigh << 128;
+                p |= hhigh << 192;
 
-                Ok(Field { p })
+                Field::try_new(p).map_err(de::Error::custom)
             }
         }
 
@@ -225,11 +991,592 @@ mod tests {
             (*GENERATOR * *GENERATOR % *PRIME) * (*GENERATOR * *GENERATOR % *PRIME) % *PRIME
         );
 
+        for n in [11u128, 37, 407, 37 << 3, 11 << 119] {
+            let root = f.primitive_nth_root(n.into());
+            assert_eq!(root ^ n.into(), f.one());
+            for q in [2u128, 11, 37] {
+                if n % q == 0 {
+                    assert_ne!(root ^ (n / q).into(), f.one());
+                }
+            }
+        }
+        assert!(f
+            .primitive_nth_root_with(3.into(), &PRIME_FACTORS)
+            .is_none());
+        assert!(f
+            .primitive_nth_root_with(11.into(), &[(2.into(), 119), (11.into(), 1)])
+            .is_none());
+
+        let small = Field::new(17.into());
+        let factors = [(*TWO, 4)];
+        assert_eq!(
+            small.multiplicative_generator_with(&factors),
+            Some(small.element(3))
+        );
+        assert!(small.multiplicative_generator_with(&[(*TWO, 3)]).is_none());
+        let root = small.primitive_nth_root_with(8.into(), &factors).unwrap();
+        assert_eq!(root ^ 8.into(), small.one());
+        assert_ne!(root ^ 4.into(), small.one());
+
         let gen = f.generator();
         assert_eq!(gen.value, *GENERATOR);
 
         let s = f.sample(&[1u8, 2u8, 3u8]);
         assert_eq!(s.value, 66051.into());
+        assert_eq!(f.sample_wide(&[1u8, 2u8, 3u8]), s);
+        let wide = f.sample_wide(&[0xff; 64]);
+        assert_eq!(
+            wide.value,
+            U256::try_from(U512::MAX % U512::from(*PRIME)).unwrap()
+        );
+        assert_ne!(wide, f.sample(&[0xff; 64]));
+    }
+
+    #[test]
+    fn inversion_test() {
+        let f = Field::new(*PRIME);
+        let fermat = f.with_inversion(Inversion::Fermat);
+        assert_eq!(f, fermat);
+        for value in [1u128, 2, 1 << 100, PRIME.as_u128() - 1] {
+            let x = FieldElement::new(value.into(), f);
+            assert_eq!(f.inv_pow(&x), f.inv_xgcd(&x));
+            assert_eq!(f.inv_binary(&x), f.inv_xgcd(&x));
+            let y = FieldElement::new(value.into(), fermat);
+            assert_eq!(y.inv(), x.inv());
+            assert_eq!(y * y.inv(), fermat.one());
+        }
+
+        for inversion in [Inversion::Fermat, Inversion::BinaryGcd] {
+            let small = Field::new(17.into()).with_inversion(inversion);
+            for value in 1..17u64 {
+                let x = FieldElement::new(value.into(), small);
+                assert_eq!(x * x.inv(), small.one());
+            }
+        }
+    }
+
+    #[test]
+    fn custom_prime_roots_test() {
+        let f = Field::new(17.into());
+        assert_eq!(f.factors(), Some(vec![(*TWO, 4)]));
+        let f = Field::new(7.into());
+        assert_eq!(f.factors(), Some(vec![(*TWO, 1), (3.into(), 1)]));
+        let root = f.primitive_nth_root(3.into());
+        assert_eq!(root ^ 3.into(), f.one());
+        assert_ne!(root, f.one());
+
+        let f = Field::new(1_000_003.into());
+        let root = f.primitive_nth_root(166_667.into());
+        assert_eq!(root ^ 166_667.into(), f.one());
+        assert_ne!(root, f.one());
+        let root = f.primitive_nth_root(6.into());
+        assert_eq!(root ^ 6.into(), f.one());
+        assert_ne!(root ^ 3.into(), f.one());
+        assert_ne!(root ^ *TWO, f.one());
+    }
+
+    #[test]
+    fn nth_root_test() {
+        let f = Field::new(*PRIME);
+        for n in [88u64, 11 * 37, 37 * 1024] {
+            let root = f.nth_root(n.into()).unwrap();
+            assert_eq!(root ^ n.into(), f.one());
+            for q in [2u64, 11, 37] {
+                if n % q == 0 {
+                    assert_ne!(root ^ (n / q).into(), f.one());
+                }
+            }
+        }
+        assert_eq!(
+            f.nth_root(1024.into()),
+            Some(f.primitive_nth_root(1024.into()))
+        );
+        assert_eq!(f.nth_root((ONE << 61) - ONE), None);
+        assert_eq!(f.nth_root(ZERO), None);
+
+        let g = Field::new(1_000_003.into());
+        let root = g.nth_root(3.into()).unwrap();
+        assert_ne!(root, g.one());
+        assert_eq!(root ^ 3.into(), g.one());
+    }
+
+    #[test]
+    fn hard_factorization_test() {
+        // p - 1 = 2 * (2^61 - 1) * q with q a 65-bit prime, beyond the rho budget.
+        let f = Field::new(U256::from(0x4000_0000_0000_0007_3fff_ffff_ffff_ffb7u128));
+        assert_eq!(f.factors(), None);
+        assert_eq!(f.multiplicative_generator(), None);
+        assert_eq!(f.try_generator(), Some(-f.one()));
+        assert_eq!(f.nth_root((ONE << 61) - ONE), None);
+        assert_eq!(f.nth_root(*TWO), Some(-f.one()));
+    }
+
+    #[test]
+    fn generator_test() {
+        for p in [7u64, 17, 97, 1_000_003, 0x7fff_ffff] {
+            let f = Field::new(p.into());
+            let g = f.multiplicative_generator().unwrap();
+            for (q, _) in f.factors().unwrap() {
+                assert_ne!(g ^ ((f.p - ONE) / q), f.one());
+            }
+            if p < 1 << 12 {
+                let powers: std::collections::BTreeSet<U256> = f
+                    .powers(&g, p as usize - 1)
+                    .iter()
+                    .map(|x| x.value)
+                    .collect();
+                assert_eq!(powers.len() as u64, p - 1);
+            }
+            assert!(f.candidates().take_while(|c| *c != g).all(|c| f
+                .factors()
+                .unwrap()
+                .iter()
+                .any(|(q, _)| c ^ ((f.p - ONE) / *q) == f.one())));
+        }
+
+        for preset in presets::ALL {
+            let f = Field::from_preset(preset);
+            let order = f.p - ONE;
+            let g = f.multiplicative_generator().unwrap();
+            for (q, _) in preset.factors() {
+                assert_ne!(g ^ (order / q), f.one(), "{}", preset.name());
+            }
+            let offset = f.generator();
+            let s = f.two_adicity();
+            assert_eq!(offset ^ (ONE << s), f.one());
+            assert_ne!(offset ^ (ONE << (s - 1)), f.one());
+        }
+        assert_eq!(
+            Field::new(*PRIME).multiplicative_generator(),
+            Some(Field::new(*PRIME).element(3))
+        );
+
+        let f = Field::new(97.into());
+        let offset = f.generator();
+        assert_eq!(offset, f.two_adic_generator(f.two_adicity()).unwrap());
+        assert_eq!(offset ^ 32.into(), f.one());
+        assert_ne!(offset ^ 16.into(), f.one());
+
+        // A factor of one rules out every candidate, so the search runs out below p.
+        let small = Field::new(7.into());
+        let factors = [(ONE, 1), (2.into(), 1), (3.into(), 1)];
+        assert_eq!(small.candidates().count(), 5);
+        assert_eq!(small.multiplicative_generator_with(&factors), None);
+    }
+
+    #[test]
+    fn try_new_test() {
+        let f = Field::try_new(*PRIME).unwrap();
+        assert_eq!(f, Field::new(*PRIME));
+        assert_eq!(f.order(), *PRIME);
+        assert_eq!(f.bits(), 128);
+        assert_eq!(Field::try_new(17.into()).unwrap().bits(), 5);
+        for p in [0u64, 1, 2, 16, 561, 65537 * 65539] {
+            assert!(matches!(
+                Field::try_new(p.into()),
+                Err(Error::InvalidParameters(_))
+            ));
+        }
+        assert!(Field::try_new(*PRIME + *TWO).is_err());
+
+        // Composites without small factors reach Miller-Rabin, which must
+        // not register them.
+        let composites = (1_000_001u64..)
+            .step_by(2)
+            .filter(|n| (3..=37).all(|q| n % q != 0) && !factor::is_probable_prime((*n).into()))
+            .take(MAX_FIELDS + 1);
+        for n in composites {
+            assert!(Field::try_new(n.into()).is_err());
+        }
+        assert_eq!(Field::try_new(*PRIME).unwrap(), f);
+    }
+
+    #[test]
+    fn constants_test() {
+        for p in [*PRIME, 17.into(), 1_000_003.into()] {
+            let f = Field::new(p);
+            let two = FieldElement::new(*TWO, f);
+            assert_eq!(f.half(), two.inv());
+            assert_eq!(f.generator(), f.generator());
+            assert_eq!(f.generator_powers(6), Some(f.powers(&f.generator(), 6)));
+            assert_eq!(f.generator_powers(3), Some(f.powers(&f.generator(), 3)));
+            assert_eq!(f.generator_powers(0), Some(vec![]));
+        }
+    }
+
+    #[test]
+    fn bytes_test() {
+        let f = Field::new(*PRIME);
+        let bytes = f.to_bytes();
+        assert_eq!(&bytes[16..], &[0u8; 16]);
+        assert_eq!(Field::from_bytes(&bytes).unwrap(), f);
+        assert!(Field::from_bytes(&bytes[..16]).is_err());
+        assert!(Field::from_bytes(&Field::new(16.into()).to_bytes()).is_err());
+    }
+
+    #[test]
+    fn hash_to_field_test() {
+        let f = Field::new(*PRIME);
+        let a = f.hash_to_field(b"stark-fs", b"msg");
+        assert_eq!(a, f.hash_to_field(b"stark-fs", b"msg"));
+        assert_ne!(a, f.hash_to_field(b"stark-pi", b"msg"));
+        assert_ne!(a, f.hash_to_field(b"stark-fs", b"msh"));
+        assert!(a.value < f.p);
+
+        let wide = Field::new(U256::MAX - 188);
+        assert!(wide.hash_to_field(b"tag", b"").value < wide.p);
+    }
+
+    #[test]
+    fn hex_test() {
+        let f = Field::new(*PRIME);
+        assert_eq!(f.to_string(), "0xcb800000000000000000000000000001");
+        assert_eq!(f.to_string().parse::<Field>().unwrap(), f);
+        assert_eq!(
+            "cb800000000000000000000000000001".parse::<Field>().unwrap(),
+            f
+        );
+        assert_eq!(" 0X11 ".parse::<Field>().unwrap(), Field::new(17.into()));
+        assert!("0x10".parse::<Field>().is_err());
+        assert!("0xzz".parse::<Field>().is_err());
+        assert!("".parse::<Field>().is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic_test() {
+        let f = Field::new(*PRIME);
+        let small = Field::new(17.into());
+        let x = f.element(5);
+        let y = f.element(3);
+        let z = small.element(3);
+        assert_eq!(f.checked_add(&x, &y).unwrap(), x + y);
+        assert_eq!(f.checked_sub(&x, &y).unwrap(), x - y);
+        assert_eq!(f.checked_mul(&x, &y).unwrap(), x * y);
+        assert_eq!(f.checked_div(&x, &y).unwrap(), x / y);
+        assert!(matches!(f.checked_add(&x, &z), Err(Error::FieldMismatch)));
+        assert!(matches!(f.checked_mul(&z, &x), Err(Error::FieldMismatch)));
+        assert!(matches!(
+            small.checked_sub(&x, &y),
+            Err(Error::FieldMismatch)
+        ));
+        assert!(matches!(
+            f.checked_div(&x, &f.zero()),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn mixed_fields_test() {
+        let x = FieldElement::new(5.into(), Field::new(*PRIME));
+        let y = FieldElement::new(3.into(), Field::new(17.into()));
+        let _ = x * y;
+    }
+
+    #[test]
+    fn pow_signed_test() {
+        let f = Field::new(*PRIME);
+        let g = f.generator();
+        assert_eq!(g.pow_signed(0), f.one());
+        assert_eq!(g.pow_signed(5), g ^ 5.into());
+        assert_eq!(g.pow_signed(-1), g.inv());
+        assert_eq!(g.pow_signed(-5), (g ^ 5.into()).inv());
+        assert_eq!(g.pow_signed(-7) * g.pow_signed(7), f.one());
+        assert_eq!(g.pow_signed(i128::MIN), (g ^ (ONE << 127)).inv());
+        assert_eq!(f.zero().pow_signed(3), f.zero());
+    }
+
+    #[test]
+    fn frobenius_test() {
+        for p in [*PRIME, 17.into(), U256::MAX - (ONE << 32) - 976] {
+            let f = Field::new(p);
+            for x in [f.zero(), f.one(), FieldElement::new(p >> 3, f)] {
+                assert_eq!(f.pow_p(&x), x ^ p);
+                assert_eq!(x.frobenius(), x);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_test() {
+        let f = Field::new(*PRIME);
+        for value in [0u128, 1, 4, 1 << 100, PRIME.as_u128() - 1] {
+            let x = FieldElement::new(value.into(), f);
+            let square = x * x;
+            let root = f.sqrt(&square).unwrap();
+            assert_eq!(root * root, square);
+        }
+        assert_eq!(f.sqrt(&f.generator()), None);
+        assert_eq!(f.legendre(&f.generator()), -1);
+        assert_eq!(f.legendre(&f.zero()), 0);
+        assert_eq!(f.legendre(&f.element(4)), 1);
+
+        for p in [2u64, 3, 7, 17, 97] {
+            let f = Field::new(p.into());
+            let squares: Vec<_> = (0..p)
+                .map(|v| {
+                    let x = FieldElement::new(v.into(), f);
+                    x * x
+                })
+                .collect();
+            for v in 0..p {
+                let x = FieldElement::new(v.into(), f);
+                match f.sqrt(&x) {
+                    Some(root) => assert_eq!(root * root, x),
+                    None => assert!(!squares.contains(&x)),
+                }
+                assert_eq!(x.is_square(), squares.contains(&x));
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_test() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let f = Field::new(*PRIME);
+        let samples: Vec<_> = (0..64).map(|_| f.random(&mut rng)).collect();
+        assert!(samples.iter().all(|x| x.value < f.p));
+        assert!(samples.iter().any(|x| x.value.bits() == f.p.bits()));
+        assert_ne!(samples[0], samples[1]);
+
+        let small = Field::new(7.into());
+        let mut counts = [0usize; 7];
+        for _ in 0..7000 {
+            counts[small.random(&mut rng).value.as_usize()] += 1;
+        }
+        assert!(counts.iter().all(|&count| (800..1200).contains(&count)));
+    }
+
+    #[test]
+    fn two_adicity_test() {
+        let f = Field::new(*PRIME);
+        assert_eq!(f.two_adicity(), 119);
+        assert_eq!(f.two_adic_generator(119).unwrap().value, *GENERATOR);
+        assert_eq!(
+            f.two_adic_generator(4).unwrap(),
+            f.primitive_nth_root(16.into())
+        );
+        assert!(f.two_adic_generator(120).is_none());
+
+        let small = Field::new(97.into());
+        assert_eq!(small.two_adicity(), 5);
+        for k in 0..=5 {
+            let root = small.two_adic_generator(k).unwrap();
+            assert_eq!(root ^ (ONE << k), small.one());
+            if k > 0 {
+                assert_ne!(root ^ (ONE << (k - 1)), small.one());
+            }
+        }
+        assert_eq!(
+            small.primitive_nth_root(8.into()),
+            small.two_adic_generator(3).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_inv_test() {
+        let f = Field::new(*PRIME);
+        let values = f.powers(&f.generator(), 7)[1..].to_vec();
+        let inverses = f.batch_inv(&values).unwrap();
+        for (value, inverse) in values.iter().zip(&inverses) {
+            assert_eq!(*inverse, value.inv());
+        }
+        assert_eq!(f.batch_inv(&[]), Some(vec![]));
+        assert_eq!(f.batch_inv(&[f.one(), f.zero()]), None);
+    }
+
+    #[test]
+    fn powers_test() {
+        let f = Field::new(*PRIME);
+        let g = f.generator();
+        let powers = f.powers(&g, 10);
+        assert_eq!(powers.len(), 10);
+        for (i, power) in powers.iter().enumerate() {
+            assert_eq!(*power, g ^ i.into());
+        }
+        assert!(f.powers(&g, 0).is_empty());
+
+        let bases = f.powers(&g, 6);
+        let exponent: U256 = 12345.into();
+        let batched = f.batch_pow(&bases, exponent);
+        for (base, power) in bases.iter().zip(&batched) {
+            assert_eq!(*power, base ^ exponent);
+        }
+        assert_eq!(f.batch_pow(&bases, ZERO), vec![f.one(); 6]);
+    }
+
+    #[test]
+    fn wide_modulus_test() {
+        let p = U256::MAX - (ONE << 32) - 976;
+        let f = Field::new(p);
+        let minus_one = FieldElement::new(p - ONE, f);
+        let minus_two = FieldElement::new(p - *TWO, f);
+        assert_eq!(minus_one * minus_one, f.one());
+        assert_eq!(minus_one + minus_one, minus_two);
+        assert_eq!(f.one() - minus_one, FieldElement::new(*TWO, f));
+        assert_eq!(minus_two / minus_one, FieldElement::new(*TWO, f));
+
+        let x = FieldElement::new(U256::MAX >> 3, f);
+        assert_eq!(f.inv_pow(&x), f.inv_xgcd(&x));
+        assert_eq!(f.inv_binary(&x), f.inv_xgcd(&x));
+        assert_eq!(x * x.inv(), f.one());
+        assert_eq!(x ^ (p - ONE), f.one());
+    }
+
+    #[test]
+    fn batch_arithmetic_test() {
+        for f in [Field::new(*PRIME), Field::from_preset(Preset::BabyBear)] {
+            let left = f.powers(&f.generator(), 21);
+            let right: Vec<FieldElement> = left.iter().map(|x| -&(x * x)).collect();
+            let expected = |op: fn(&FieldElement, &FieldElement) -> FieldElement| -> Vec<_> {
+                left.iter().zip(&right).map(|(l, r)| op(l, r)).collect()
+            };
+            assert_eq!(f.batch_add(&left, &right), expected(|l, r| l + r));
+            assert_eq!(f.batch_sub(&left, &right), expected(|l, r| l - r));
+            assert_eq!(f.batch_mul(&left, &right), expected(|l, r| l * r));
+        }
+    }
+
+    #[test]
+    fn windowed_pow_test() {
+        let naive = |base: u128, exponent: U256, p: u128| {
+            let mut acc = 1u128;
+            for i in (0..exponent.bits()).rev() {
+                acc = acc * acc % p;
+                if exponent.bit(i) {
+                    acc = acc * base % p;
+                }
+            }
+            acc
+        };
+        let p = 1_000_003u128;
+        let mul = |a: u128, b: u128| a * b % p;
+        for exponent in [
+            ZERO,
+            ONE,
+            U256::from(2),
+            U256::from(0b1011_0001_1111u64),
+            U256::from(u64::MAX),
+            (ONE << 200) | U256::from(0x8001u64),
+            U256::MAX,
+        ] {
+            assert_eq!(
+                windowed_pow(12345u128, 1, exponent, mul),
+                naive(12345, exponent, p)
+            );
+        }
+        let f = Field::new(*PRIME);
+        let x = f.generator();
+        assert_eq!(x.pow(*PRIME - ONE), f.one());
+        assert_eq!(
+            f.batch_pow(&[x, f.one()], 3.into()),
+            vec![(x * x) * x, f.one()]
+        );
+    }
+
+    #[test]
+    fn sum_of_products_test() {
+        for p in [*PRIME, U256::MAX - 188] {
+            let f = Field::new(p);
+            let left: Vec<FieldElement> = (0..64u64)
+                .map(|i| FieldElement::new(p - i * 7919 - 1, f))
+                .collect();
+            let right: Vec<FieldElement> = (0..64u64)
+                .map(|i| FieldElement::new(p - i * i - 3, f))
+                .collect();
+            let expected = left
+                .iter()
+                .zip(&right)
+                .fold(f.zero(), |acc, (l, r)| acc + (l * r));
+            let terms = left.iter().copied().zip(right.iter().copied());
+            assert_eq!(f.sum_of_products(terms), expected);
+            assert_eq!(f.sum_of_products([]), f.zero());
+        }
+    }
+
+    #[test]
+    fn word_field_test() {
+        let f = Field {
+            word: Word128::new(*PRIME),
+            ..Field::new(*PRIME)
+        };
+        assert!(Field::new(U256::MAX - 188).word.is_none());
+        assert!(f.with_reduction(Reduction::Barrett).word.is_none());
+
+        let wide = Field { word: None, ..f };
+        let x = FieldElement::new(*GENERATOR, f);
+        let y = FieldElement::new(*PRIME - 12345, f);
+        let (wx, wy) = (
+            FieldElement::new(x.value, wide),
+            FieldElement::new(y.value, wide),
+        );
+        assert_eq!((x + y).value, (wx + wy).value);
+        assert_eq!((x - y).value, (wx - wy).value);
+        assert_eq!((y - x).value, (wy - wx).value);
+        assert_eq!((x * y).value, (wx * wy).value);
+        assert_eq!((y ^ (*PRIME - *TWO)).value, (wy ^ (*PRIME - *TWO)).value);
+    }
+
+    #[test]
+    fn montgomery_field_test() {
+        let f = Field::new(*PRIME);
+        assert_eq!(f.reduction, Reduction::Plain);
+        assert!(f.montgomery.is_none());
+        let native = !cfg!(feature = "crypto-bigint");
+        assert_eq!(f.word.is_some(), native);
+        let f = f.with_reduction(Reduction::Montgomery);
+        assert_eq!(f.montgomery.is_some(), native);
+        assert_eq!(f.word.is_some(), native);
+        assert!(Field::new(16.into())
+            .with_reduction(Reduction::Montgomery)
+            .montgomery
+            .is_none());
+
+        let f = Field {
+            montgomery: Montgomery::new(*PRIME),
+            word: None,
+            ..f
+        };
+        let plain = Field {
+            montgomery: None,
+            ..f
+        };
+        let x = FieldElement::new(*GENERATOR, f);
+        let y = FieldElement::new(PRIME.as_u128().wrapping_sub(12345).into(), f);
+        let (px, py) = (
+            FieldElement::new(x.value, plain),
+            FieldElement::new(y.value, plain),
+        );
+        assert_eq!((x * y).value, (px * py).value);
+        assert_eq!((x ^ (*PRIME - *TWO)).value, (px ^ (*PRIME - *TWO)).value);
+        assert_eq!(f.inv_pow(&y).value, plain.inv_xgcd(&py).value);
+        assert_eq!(f.pow(&y, ZERO), f.one());
+    }
+
+    #[test]
+    fn barrett_field_test() {
+        let f = Field::new(*PRIME);
+        let barrett = f.with_reduction(Reduction::Barrett);
+        assert_eq!(barrett.reduction, Reduction::Barrett);
+        assert!(barrett.barrett.is_some() && barrett.montgomery.is_none());
+        assert_eq!(f, barrett);
+        for value in [1u128, 2, 1 << 100, PRIME.as_u128() - 1] {
+            let x = FieldElement::new(value.into(), f);
+            let y = FieldElement::new(value.into(), barrett);
+            assert_eq!((y * y).value, (x * x).value);
+            assert_eq!((y + y).value, (x + x).value);
+            assert_eq!((y ^ 12345.into()).value, (x ^ 12345.into()).value);
+            assert_eq!(y * y.inv(), barrett.one());
+        }
+
+        let wide = Field::new(U256::MAX - (ONE << 32) - 976).with_reduction(Reduction::Barrett);
+        assert!(wide.barrett.is_none());
+        let minus_one = FieldElement::new(wide.p - ONE, wide);
+        assert_eq!(minus_one * minus_one, wide.one());
     }
 
     #[test]
@@ -239,5 +1586,37 @@ mod tests {
         let deserialized: Field =
             serde_pickle::from_slice(&serialized, Default::default()).unwrap();
         assert_eq!(f, deserialized);
+        assert!(std::ptr::eq(f.interned(), deserialized.interned()));
+
+        let serialized = serde_pickle::to_vec(&Field::new(15.into()), Default::default()).unwrap();
+        assert!(serde_pickle::from_slice::<Field>(&serialized, Default::default()).is_err());
+    }
+
+    #[test]
+    fn registry_cap_test() {
+        let mut registry = Registry {
+            untrusted: MAX_FIELDS - 1,
+            ..Registry::default()
+        };
+        let key = |p: u64| (U256::from(p), Inversion::Xgcd, Reduction::Plain, [false; 3]);
+        let cell = registry.cell(key(17), true).unwrap();
+        assert!(registry.cell(key(19), true).is_err());
+        assert!(std::ptr::eq(registry.cell(key(17), true).unwrap(), cell));
+        registry.cell(key(23), false).unwrap();
+        assert!(registry.cell(key(23), true).is_ok());
+        assert_eq!(registry.untrusted, MAX_FIELDS);
+        assert_eq!(registry.fields.len(), 2);
+    }
+
+    #[test]
+    fn registry_test() {
+        let f = Field::new(*PRIME);
+        assert!(std::ptr::eq(f.interned(), f.interned().interned()));
+        assert!(std::ptr::eq(f.interned(), Field::new(*PRIME).interned()));
+        let fermat = f.with_inversion(Inversion::Fermat);
+        assert!(!std::ptr::eq(f.interned(), fermat.interned()));
+        assert_eq!(fermat.interned().inversion, Inversion::Fermat);
+        let plain = Field { word: None, ..f };
+        assert!(plain.interned().word.is_none());
     }
 }
@@ -0,0 +1,162 @@
+use crate::{
+    element::FieldElement,
+    field::{finite::element_ops, presets::Preset, Field, FiniteField, FiniteFieldElement},
+    Error,
+};
+use once_cell::sync::Lazy;
+use primitive_types::U256;
+use serde::{Deserialize, Deserializer, Serialize};
+
+pub const MODULUS: u32 = 0x7800_0001;
+
+static FIELD: Lazy<Field> = Lazy::new(|| Field::from_preset(Preset::BabyBear));
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BabyBear;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash, Serialize)]
+pub struct BabyBearElement(u32);
+
+impl BabyBearElement {
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl BabyBear {
+    pub fn field(&self) -> Field {
+        *FIELD
+    }
+
+    pub fn element(&self, value: u64) -> BabyBearElement {
+        BabyBearElement((value % MODULUS as u64) as u32)
+    }
+}
+
+impl FiniteField for BabyBear {
+    type Element = BabyBearElement;
+
+    fn modulus(&self) -> U256 {
+        MODULUS.into()
+    }
+    fn zero(&self) -> BabyBearElement {
+        BabyBearElement(0)
+    }
+    fn one(&self) -> BabyBearElement {
+        BabyBearElement(1)
+    }
+    fn add(&self, left: &BabyBearElement, right: &BabyBearElement) -> BabyBearElement {
+        let sum = left.0 + right.0;
+        BabyBearElement(if sum >= MODULUS { sum - MODULUS } else { sum })
+    }
+    fn sub(&self, left: &BabyBearElement, right: &BabyBearElement) -> BabyBearElement {
+        BabyBearElement(if left.0 >= right.0 {
+            left.0 - right.0
+        } else {
+            MODULUS - (right.0 - left.0)
+        })
+    }
+    fn mul(&self, left: &BabyBearElement, right: &BabyBearElement) -> BabyBearElement {
+        self.element(left.0 as u64 * right.0 as u64)
+    }
+    fn neg(&self, operand: &BabyBearElement) -> BabyBearElement {
+        self.sub(&self.zero(), operand)
+    }
+    fn inv(&self, operand: &BabyBearElement) -> BabyBearElement {
+        assert!(operand.0 != 0);
+        self.pow(operand, (MODULUS - 2).into())
+    }
+    fn sample(&self, byte_array: &[u8]) -> BabyBearElement {
+        BabyBearElement::try_from(self.field().sample(byte_array)).unwrap()
+    }
+    fn nth_root(&self, n: U256) -> Option<BabyBearElement> {
+        Some(BabyBearElement::try_from(self.field().nth_root(n)?).unwrap())
+    }
+    fn sample_wide(&self, byte_array: &[u8]) -> BabyBearElement {
+        BabyBearElement::try_from(self.field().sample_wide(byte_array)).unwrap()
+    }
+    fn try_generator(&self) -> Option<BabyBearElement> {
+        Some(BabyBearElement::try_from(self.field().try_generator()?).unwrap())
+    }
+}
+
+impl FiniteFieldElement for BabyBearElement {
+    type Field = BabyBear;
+
+    fn field(&self) -> BabyBear {
+        BabyBear
+    }
+    fn to_u256(&self) -> U256 {
+        self.0.into()
+    }
+}
+
+element_ops!(BabyBearElement, BabyBear);
+
+impl From<BabyBearElement> for FieldElement {
+    fn from(element: BabyBearElement) -> Self {
+        FieldElement::new(element.0.into(), BabyBear.field())
+    }
+}
+
+impl TryFrom<FieldElement> for BabyBearElement {
+    type Error = Error;
+
+    fn try_from(element: FieldElement) -> Result<Self, Error> {
+        if element.field.p != MODULUS.into() {
+            return Err(Error::FieldMismatch);
+        }
+        Ok(BabyBearElement(element.value.low_u32()))
+    }
+}
+
+impl<'de> Deserialize<'de> for BabyBearElement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u32::deserialize(deserializer)?;
+        if value >= MODULUS {
+            return Err(serde::de::Error::custom("element exceeds the modulus"));
+        }
+        Ok(BabyBearElement(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn babybear_test() {
+        let f = BabyBear;
+        let field = f.field();
+        let x = f.element(123456789);
+        let y = f.element(MODULUS as u64 - 5);
+        let (fx, fy) = (FieldElement::from(x), FieldElement::from(y));
+        assert_eq!(FieldElement::from(f.add(&x, &y)), fx + fy);
+        assert_eq!(FieldElement::from(f.sub(&x, &y)), fx - fy);
+        assert_eq!(FieldElement::from(f.sub(&y, &x)), fy - fx);
+        assert_eq!(FieldElement::from(f.mul(&x, &y)), fx * fy);
+        assert_eq!(FieldElement::from(f.neg(&x)), -&fx);
+        assert_eq!(FieldElement::from(f.inv(&y)), fy.inv());
+        assert_eq!(f.mul(&x, &f.inv(&x)), f.one());
+        assert_eq!(f.neg(&f.zero()), f.zero());
+
+        let root = f.nth_root(1024.into()).unwrap();
+        assert_eq!(f.pow(&root, 1024.into()), f.one());
+        assert_ne!(f.pow(&root, 512.into()), f.one());
+        assert_eq!(
+            FieldElement::from(f.sample(&[9, 8, 7, 6, 5])),
+            field.sample(&[9, 8, 7, 6, 5])
+        );
+        assert!(BabyBearElement::try_from(Field::new(17.into()).one()).is_err());
+
+        let serialized = serde_json::to_string(&y).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BabyBearElement>(&serialized).unwrap(),
+            y
+        );
+        assert!(serde_json::from_str::<BabyBearElement>(&MODULUS.to_string()).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use primitive_types::{U256, U512};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Barrett {
+    pub p: U256,
+    k: usize,
+    mu: U256,
+}
+
+impl Barrett {
+    pub fn new(p: U256) -> Option<Self> {
+        let k = p.bits();
+        if p <= U256::one() || k > 255 {
+            return None;
+        }
+        let mu = (U512::one() << (2 * k)) / U512::from(p);
+        Some(Barrett {
+            p,
+            k,
+            mu: U256::try_from(mu).ok()?,
+        })
+    }
+
+    pub fn reduce(&self, value: U512) -> U256 {
+        let estimate = U256::try_from(value >> (self.k - 1)).expect("value is below p^2");
+        let quotient = U256::try_from(estimate.full_mul(self.mu) >> (self.k + 1))
+            .expect("quotient fits the modulus width");
+        let mut remainder = value - quotient.full_mul(self.p);
+        let modulus = U512::from(self.p);
+        while remainder >= modulus {
+            remainder -= modulus;
+        }
+        U256::try_from(remainder).unwrap()
+    }
+
+    pub fn mul(&self, left: U256, right: U256) -> U256 {
+        self.reduce(left.full_mul(right))
+    }
+
+    pub fn add(&self, left: U256, right: U256) -> U256 {
+        let sum = left + right;
+        if sum >= self.p {
+            sum - self.p
+        } else {
+            sum
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    #[test]
+    fn barrett_test() {
+        let large = (U256::MAX >> 1) - 18;
+        for p in [
+            *PRIME,
+            U256::from(17),
+            U256::from(0xffffffff00000001u64),
+            large,
+        ] {
+            let barrett = Barrett::new(p).unwrap();
+            let values = [ZERO, ONE, p - ONE, p >> 1, (p >> 3) + 12345].map(|value| value % p);
+            for a in values {
+                for b in values {
+                    let expected = U256::try_from(a.full_mul(b) % U512::from(p)).unwrap();
+                    assert_eq!(barrett.mul(a, b), expected);
+                    let expected =
+                        U256::try_from((U512::from(a) + U512::from(b)) % U512::from(p)).unwrap();
+                    assert_eq!(barrett.add(a, b), expected);
+                }
+            }
+        }
+        assert_eq!(Barrett::new(U256::MAX - 188), None);
+        assert_eq!(Barrett::new(ONE), None);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::field::{finite::element_ops, FiniteField, FiniteFieldElement};
+use primitive_types::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Wiedemann tower: T_{k+1} = T_k[X_k] / (X_k^2 + X_{k-1} X_k + 1), with T_0 = GF(2).
+pub const LEVEL: u32 = 7;
+
+pub const GROUP_FACTORS: [u128; 9] = [3, 5, 17, 257, 641, 65537, 274177, 6700417, 67280421310721];
+
+// Values below 2^64 lie in the subfield T_6, so the generator needs a high limb.
+pub const GENERATOR: u128 = 1 << 64 | 5;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BinaryTower;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
+pub struct BinaryElement(pub u128);
+
+impl BinaryTower {
+    pub fn element(&self, value: u128) -> BinaryElement {
+        BinaryElement(value)
+    }
+
+    pub fn generator(&self) -> BinaryElement {
+        BinaryElement(GENERATOR)
+    }
+}
+
+fn mul(left: u128, right: u128, level: u32) -> u128 {
+    if level == 0 {
+        return left & right & 1;
+    }
+    let half = 1 << (level - 1);
+    let mask = u128::MAX >> (128 - half);
+    let (a0, a1) = (left & mask, left >> half);
+    let (b0, b1) = (right & mask, right >> half);
+    let low = mul(a0, b0, level - 1);
+    let high = mul(a1, b1, level - 1);
+    let cross = mul(a0 ^ a1, b0 ^ b1, level - 1) ^ low ^ high;
+    (low ^ high) | (cross ^ mul_x(high, level - 1)) << half
+}
+
+// Multiplies an element of T_level by X_{level-1}.
+fn mul_x(value: u128, level: u32) -> u128 {
+    if level == 0 {
+        return value;
+    }
+    let half = 1 << (level - 1);
+    let mask = u128::MAX >> (128 - half);
+    let (c0, c1) = (value & mask, value >> half);
+    c1 | (c0 ^ mul_x(c1, level - 1)) << half
+}
+
+impl FiniteField for BinaryTower {
+    type Element = BinaryElement;
+
+    // Characteristic of the field; the tower has no prime modulus.
+    fn modulus(&self) -> U256 {
+        2.into()
+    }
+    fn zero(&self) -> BinaryElement {
+        BinaryElement(0)
+    }
+    fn one(&self) -> BinaryElement {
+        BinaryElement(1)
+    }
+    fn add(&self, left: &BinaryElement, right: &BinaryElement) -> BinaryElement {
+        BinaryElement(left.0 ^ right.0)
+    }
+    fn sub(&self, left: &BinaryElement, right: &BinaryElement) -> BinaryElement {
+        self.add(left, right)
+    }
+    fn mul(&self, left: &BinaryElement, right: &BinaryElement) -> BinaryElement {
+        BinaryElement(mul(left.0, right.0, LEVEL))
+    }
+    fn neg(&self, operand: &BinaryElement) -> BinaryElement {
+        *operand
+    }
+    fn inv(&self, operand: &BinaryElement) -> BinaryElement {
+        assert!(operand.0 != 0);
+        self.pow(operand, (u128::MAX - 1).into())
+    }
+    fn sample(&self, byte_array: &[u8]) -> BinaryElement {
+        BinaryElement(
+            byte_array
+                .iter()
+                .fold(0u128, |acc, b| (acc << 8) ^ *b as u128),
+        )
+    }
+    // Characteristic 2, so the Frobenius map is squaring.
+    fn frobenius(&self, operand: &BinaryElement) -> BinaryElement {
+        self.mul(operand, operand)
+    }
+    fn nth_root(&self, n: U256) -> Option<BinaryElement> {
+        let order = U256::from(u128::MAX);
+        if n.is_zero() || !(order % n).is_zero() {
+            return None;
+        }
+        Some(self.pow(&self.generator(), order / n))
+    }
+}
+
+impl FiniteFieldElement for BinaryElement {
+    type Field = BinaryTower;
+
+    fn field(&self) -> BinaryTower {
+        BinaryTower
+    }
+    fn to_u256(&self) -> U256 {
+        self.0.into()
+    }
+}
+
+element_ops!(BinaryElement, BinaryTower);
+
+impl Serialize for BinaryElement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        [self.0 as i64, (self.0 >> 64) as i64].serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BinaryElement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [low, high] = <[i64; 2]>::deserialize(deserializer)?;
+        Ok(BinaryElement(
+            low as u64 as u128 | (high as u64 as u128) << 64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::Merkle;
+
+    #[test]
+    fn binary_tower_test() {
+        let f = BinaryTower;
+        // X_0 generates GF(4): X_0^2 = X_0 + 1.
+        assert_eq!(mul(2, 2, 1), 3);
+        assert_eq!(mul(3, 3, 1), 2);
+
+        let x = f.element(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210);
+        let y = f.element(u128::MAX - 12345);
+        let z = f.element(1 << 77 | 5);
+        assert_eq!(f.mul(&x, &y), f.mul(&y, &x));
+        assert_eq!(f.mul(&f.mul(&x, &y), &z), f.mul(&x, &f.mul(&y, &z)));
+        assert_eq!(
+            f.mul(&x, &f.add(&y, &z)),
+            f.add(&f.mul(&x, &y), &f.mul(&x, &z))
+        );
+        assert_eq!(f.mul(&x, &f.inv(&x)), f.one());
+        assert_eq!(f.div(&f.mul(&x, &y), &y), x);
+        assert_eq!(f.add(&x, &f.neg(&x)), f.zero());
+        assert_eq!(f.pow(&x, U256::from(u128::MAX)), f.one());
+        assert_eq!(f.frobenius(&x), f.pow(&x, 2.into()));
+        assert_ne!(f.frobenius(&x), x);
+        assert_eq!((0..128).fold(x, |acc, _| f.frobenius(&acc)), x);
+        assert_eq!(f.frobenius(&f.element(3)), f.element(2));
+
+        let g = f.generator();
+        for q in GROUP_FACTORS {
+            assert_ne!(f.pow(&g, (u128::MAX / q).into()), f.one());
+        }
+        assert_eq!(
+            f.pow(&f.element(u64::MAX as u128), U256::from(u64::MAX)),
+            f.one()
+        );
+
+        for n in [3u128, 5 * 17, 641 * 65537] {
+            let root = f.nth_root(n.into()).unwrap();
+            assert_eq!(f.pow(&root, n.into()), f.one());
+            for q in GROUP_FACTORS.iter().filter(|q| n % *q == 0) {
+                assert_ne!(f.pow(&root, (n / q).into()), f.one());
+            }
+        }
+
+        assert_eq!(f.nth_root(7.into()), None);
+
+        let leafs: Vec<BinaryElement> = (0..8u8).map(|i| f.sample(&[i, 42])).collect();
+        let root = Merkle::commit(&leafs);
+        let path = Merkle::open(3, &leafs).unwrap();
+        assert!(Merkle::verify(&root, 3, &path, &leafs[3]));
+
+        let serialized = serde_pickle::to_vec(&y, Default::default()).unwrap();
+        let deserialized: BinaryElement =
+            serde_pickle::from_slice(&serialized, Default::default()).unwrap();
+        assert_eq!(deserialized, y);
+    }
+}
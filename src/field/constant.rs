@@ -0,0 +1,363 @@
+use crate::{
+    bigint::{Backend, ModularInt},
+    consts::*,
+    element::FieldElement,
+    field::{Field, FiniteField, FiniteFieldElement},
+    Error,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ConstField<const P_HI: u128, const P_LO: u128>;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash)]
+pub struct ConstElement<const P_HI: u128, const P_LO: u128>(U256);
+
+pub type TutorialField = ConstField<0, { 1 + 407 * (1 << 119) }>;
+
+impl<const P_HI: u128, const P_LO: u128> ConstField<P_HI, P_LO> {
+    pub const MODULUS: U256 = U256([
+        P_LO as u64,
+        (P_LO >> 64) as u64,
+        P_HI as u64,
+        (P_HI >> 64) as u64,
+    ]);
+
+    // Montgomery constants for R = 2^256, derived from the modulus at compile
+    // time so `mul` never divides.
+    const INV: u64 = {
+        assert!(P_LO & 1 == 1, "ConstField needs an odd modulus");
+        montgomery_inverse(P_LO as u64)
+    };
+    const R2: [u64; 4] = r_squared(Self::MODULUS.0);
+
+    pub fn field(&self) -> Field {
+        Field::new(Self::MODULUS)
+    }
+
+    pub fn element(&self, value: U256) -> ConstElement<P_HI, P_LO> {
+        ConstElement(value % Self::MODULUS)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> ConstElement<P_HI, P_LO> {
+    fn apply<F>(self, rhs: Self, operation: F) -> Self
+    where
+        F: Fn(Backend, Backend, Backend) -> Backend,
+    {
+        let value = operation(
+            Backend::from_u256(self.0),
+            Backend::from_u256(rhs.0),
+            Backend::from_u256(ConstField::<P_HI, P_LO>::MODULUS),
+        );
+        ConstElement(value.to_u256())
+    }
+
+    pub fn value(&self) -> U256 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+// -p^-1 mod 2^64; each Newton step doubles the number of correct low bits.
+const fn montgomery_inverse(p0: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+const fn at_least(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    let mut i = 4;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+const fn sub_limbs(a: [u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut borrow = 0u64;
+    let mut i = 0;
+    while i < 4 {
+        let (difference, under) = a[i].overflowing_sub(b[i]);
+        let (difference, under_borrow) = difference.overflowing_sub(borrow);
+        result[i] = difference;
+        borrow = (under | under_borrow) as u64;
+        i += 1;
+    }
+    result
+}
+
+// R^2 mod p by doubling 1 a total of 512 times.
+const fn r_squared(p: [u64; 4]) -> [u64; 4] {
+    let mut acc = [1u64, 0, 0, 0];
+    let mut step = 0;
+    while step < 512 {
+        let carry = acc[3] >> 63;
+        acc = [
+            acc[0] << 1,
+            (acc[1] << 1) | (acc[0] >> 63),
+            (acc[2] << 1) | (acc[1] >> 63),
+            (acc[3] << 1) | (acc[2] >> 63),
+        ];
+        if carry == 1 || at_least(&acc, &p) {
+            acc = sub_limbs(acc, &p);
+        }
+        step += 1;
+    }
+    acc
+}
+
+fn mac(acc: u64, a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let wide = acc as u128 + a as u128 * b as u128 + carry as u128;
+    (wide as u64, (wide >> 64) as u64)
+}
+
+// CIOS Montgomery product a * b * R^-1 mod p.
+fn montgomery_mul(a: &[u64; 4], b: &[u64; 4], p: &[u64; 4], inv: u64) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for limb in b {
+        let mut carry = 0;
+        for j in 0..4 {
+            (t[j], carry) = mac(t[j], a[j], *limb, carry);
+        }
+        let (sum, overflow) = t[4].overflowing_add(carry);
+        (t[4], t[5]) = (sum, overflow as u64);
+        let m = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], m, p[0], 0);
+        for j in 1..4 {
+            (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+        }
+        let (sum, overflow) = t[4].overflowing_add(carry);
+        (t[3], t[4]) = (sum, t[5] + overflow as u64);
+    }
+    let result = [t[0], t[1], t[2], t[3]];
+    if t[4] != 0 || at_least(&result, p) {
+        sub_limbs(result, p)
+    } else {
+        result
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> FiniteField for ConstField<P_HI, P_LO> {
+    type Element = ConstElement<P_HI, P_LO>;
+
+    fn modulus(&self) -> U256 {
+        Self::MODULUS
+    }
+    fn zero(&self) -> Self::Element {
+        ConstElement(ZERO)
+    }
+    fn one(&self) -> Self::Element {
+        ConstElement(ONE)
+    }
+    fn add(&self, left: &Self::Element, right: &Self::Element) -> Self::Element {
+        left.apply(*right, <Backend as ModularInt>::add_mod)
+    }
+    fn sub(&self, left: &Self::Element, right: &Self::Element) -> Self::Element {
+        left.apply(*right, <Backend as ModularInt>::sub_mod)
+    }
+    fn mul(&self, left: &Self::Element, right: &Self::Element) -> Self::Element {
+        let (p, inv) = (Self::MODULUS.0, Self::INV);
+        let reduced = montgomery_mul(&left.0 .0, &right.0 .0, &p, inv);
+        ConstElement(U256(montgomery_mul(&reduced, &Self::R2, &p, inv)))
+    }
+    fn neg(&self, operand: &Self::Element) -> Self::Element {
+        self.sub(&self.zero(), operand)
+    }
+    fn inv(&self, operand: &Self::Element) -> Self::Element {
+        assert!(!operand.is_zero());
+        self.pow(operand, Self::MODULUS - *TWO)
+    }
+    fn sample(&self, byte_array: &[u8]) -> Self::Element {
+        ConstElement(self.field().sample(byte_array).value)
+    }
+    fn nth_root(&self, n: U256) -> Option<Self::Element> {
+        Some(ConstElement(self.field().nth_root(n)?.value))
+    }
+    fn sample_wide(&self, byte_array: &[u8]) -> Self::Element {
+        ConstElement(self.field().sample_wide(byte_array).value)
+    }
+    fn try_generator(&self) -> Option<Self::Element> {
+        Some(ConstElement(self.field().try_generator()?.value))
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> FiniteFieldElement for ConstElement<P_HI, P_LO> {
+    type Field = ConstField<P_HI, P_LO>;
+
+    fn field(&self) -> Self::Field {
+        ConstField
+    }
+    fn to_u256(&self) -> U256 {
+        self.0
+    }
+    fn is_zero(&self) -> bool {
+        ConstElement::is_zero(self)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::Add for ConstElement<P_HI, P_LO> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ConstField.add(&self, &rhs)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::Sub for ConstElement<P_HI, P_LO> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ConstField.sub(&self, &rhs)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::Mul for ConstElement<P_HI, P_LO> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ConstField.mul(&self, &rhs)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::Div for ConstElement<P_HI, P_LO> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        ConstField.div(&self, &rhs)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::Neg for ConstElement<P_HI, P_LO> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ConstField.neg(&self)
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::AddAssign for ConstElement<P_HI, P_LO> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::SubAssign for ConstElement<P_HI, P_LO> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> std::ops::MulAssign for ConstElement<P_HI, P_LO> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> From<ConstElement<P_HI, P_LO>> for FieldElement {
+    fn from(element: ConstElement<P_HI, P_LO>) -> Self {
+        FieldElement::new(element.0, ConstField::<P_HI, P_LO>.field())
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> TryFrom<FieldElement> for ConstElement<P_HI, P_LO> {
+    type Error = Error;
+
+    fn try_from(element: FieldElement) -> Result<Self, Error> {
+        if element.field.p != ConstField::<P_HI, P_LO>::MODULUS {
+            return Err(Error::FieldMismatch);
+        }
+        Ok(ConstElement(element.value))
+    }
+}
+
+impl<const P_HI: u128, const P_LO: u128> Serialize for ConstElement<P_HI, P_LO> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0 .0.map(|limb| limb as i64).serialize(serializer)
+    }
+}
+
+impl<'de, const P_HI: u128, const P_LO: u128> Deserialize<'de> for ConstElement<P_HI, P_LO> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let limbs = <[i64; 4]>::deserialize(deserializer)?;
+        let value = U256(limbs.map(|limb| limb as u64));
+        if value >= ConstField::<P_HI, P_LO>::MODULUS {
+            return Err(serde::de::Error::custom("element exceeds the modulus"));
+        }
+        Ok(ConstElement(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn const_field_test() {
+        let f = TutorialField::default();
+        assert_eq!(TutorialField::MODULUS, *PRIME);
+        assert_eq!(std::mem::size_of::<ConstElement<0, 17>>(), 32);
+
+        let field = f.field();
+        let x = f.element(*GENERATOR);
+        let y = f.element(*PRIME + 12345);
+        assert_eq!(y.0, 12345.into());
+        let (fx, fy) = (FieldElement::from(x), FieldElement::from(y));
+        assert_eq!(FieldElement::from(x + y), fx + fy);
+        assert_eq!(FieldElement::from(y - x), fy - fx);
+        assert_eq!(FieldElement::from(x * y), fx * fy);
+        assert_eq!(FieldElement::from(x / y), fx / fy);
+        assert_eq!(FieldElement::from(-x), -&fx);
+        assert_eq!(
+            f.nth_root(16.into()).unwrap().0,
+            field.primitive_nth_root(16.into()).value
+        );
+        assert_eq!(ConstElement::try_from(fx).unwrap(), x);
+        assert!(ConstElement::<0, 17>::try_from(fx).is_err());
+
+        let small = ConstField::<0, 17>;
+        let three = small.element(3.into());
+        assert_eq!(small.pow(&three, 16.into()), small.one());
+        assert_eq!(three * small.inv(&three), small.one());
+
+        // 2^256 - 189 exercises the top-limb carries of the Montgomery product.
+        let wide = ConstField::<{ u128::MAX }, { u128::MAX - 188 }>;
+        let mut a = wide.element(U256::MAX);
+        let b = wide.element(U256::MAX - 12345);
+        for _ in 0..16 {
+            assert_eq!(
+                FieldElement::from(a * b),
+                FieldElement::from(a) * FieldElement::from(b)
+            );
+            a = a * a + b;
+        }
+
+        let serialized = serde_pickle::to_vec(&x, Default::default()).unwrap();
+        let deserialized: ConstElement<0, { 1 + 407 * (1 << 119) }> =
+            serde_pickle::from_slice(&serialized, Default::default()).unwrap();
+        assert_eq!(deserialized, x);
+        let serialized = serde_pickle::to_vec(&x, Default::default()).unwrap();
+        assert!(
+            serde_pickle::from_slice::<ConstElement<0, 17>>(&serialized, Default::default())
+                .is_err()
+        );
+    }
+}
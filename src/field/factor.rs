@@ -0,0 +1,184 @@
+use crate::{consts::*, xgcd};
+use primitive_types::{U256, U512};
+
+const TRIAL_BOUND: u64 = 1 << 16;
+// Total Pollard rho steps one `factor` call may spend before giving up.
+pub const RHO_BUDGET: usize = 1 << 16;
+const RHO_BATCH: usize = 128;
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+fn mul_mod(a: U256, b: U256, m: U256) -> U256 {
+    U256::try_from(a.full_mul(b) % U512::from(m)).unwrap()
+}
+
+fn pow_mod(base: U256, exponent: U256, m: U256) -> U256 {
+    let mut acc = ONE;
+    for i in (0..exponent.bits()).rev() {
+        acc = mul_mod(acc, acc, m);
+        if exponent.bit(i) {
+            acc = mul_mod(acc, base, m);
+        }
+    }
+    acc
+}
+
+// Candidates are not interned as fields, so composites never reach the
+// registry.
+pub fn is_probable_prime(n: U256) -> bool {
+    if n < *TWO {
+        return false;
+    }
+    for w in WITNESSES {
+        if n == w.into() {
+            return true;
+        }
+        if (n % w).is_zero() {
+            return false;
+        }
+    }
+    let minus_one = n - ONE;
+    let s = minus_one.trailing_zeros();
+    let d = minus_one >> s;
+    WITNESSES.iter().all(|w| {
+        let mut x = pow_mod((*w).into(), d, n);
+        if x == ONE || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+pub fn factor(n: U256) -> Option<Vec<(U256, u32)>> {
+    if n.is_zero() {
+        return None;
+    }
+    let mut factors: Vec<(U256, u32)> = vec![];
+    let mut rest = n;
+    for q in 2..TRIAL_BOUND {
+        if U256::from(q) * q > rest {
+            break;
+        }
+        let mut e = 0;
+        while (rest % q).is_zero() {
+            rest /= q;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((q.into(), e));
+        }
+    }
+    let mut budget = RHO_BUDGET;
+    let mut pending = vec![rest];
+    while let Some(m) = pending.pop() {
+        if m == ONE {
+            continue;
+        }
+        if is_probable_prime(m) {
+            match factors.iter_mut().find(|(q, _)| *q == m) {
+                Some((_, e)) => *e += 1,
+                None => factors.push((m, 1)),
+            }
+            continue;
+        }
+        let d = rho(m, &mut budget)?;
+        pending.push(d);
+        pending.push(m / d);
+    }
+    factors.sort();
+    Some(factors)
+}
+
+fn rho(m: U256, budget: &mut usize) -> Option<U256> {
+    for c in 1u64.. {
+        let step =
+            |x: U256| U256::try_from((U512::from(mul_mod(x, x, m)) + c) % U512::from(m)).unwrap();
+        let (mut x, mut y) = (*TWO, *TWO);
+        // Differences are multiplied together and only gcd'd once per batch;
+        // a batch that collapses to m is replayed one step at a time.
+        loop {
+            let batch = RHO_BATCH.min(*budget);
+            if batch == 0 {
+                return None;
+            }
+            *budget -= batch;
+            let (x0, y0) = (x, y);
+            let mut product = ONE;
+            for _ in 0..batch {
+                x = step(x);
+                y = step(step(y));
+                product = mul_mod(product, if x >= y { x - y } else { y - x }, m);
+            }
+            let (_, _, d, _, _) = xgcd(product, m);
+            if d == ONE {
+                continue;
+            }
+            let (mut x, mut y) = (x0, y0);
+            let d = (0..batch)
+                .map(|_| {
+                    x = step(x);
+                    y = step(step(y));
+                    xgcd(if x >= y { x - y } else { y - x }, m).2
+                })
+                .find(|d| *d != ONE)
+                .unwrap_or(m);
+            if d != m {
+                return Some(d);
+            }
+            break;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::presets::ALL;
+
+    #[test]
+    fn factor_test() {
+        assert!(is_probable_prime(*PRIME));
+        assert!(is_probable_prime(97.into()));
+        assert!(!is_probable_prime(ONE));
+        assert!(!is_probable_prime((65537u64 * 65539).into()));
+        assert!(!is_probable_prime(561.into()));
+
+        assert_eq!(factor(*PRIME - ONE), Some(PRIME_FACTORS.clone()));
+        for preset in ALL {
+            assert_eq!(factor(preset.prime() - ONE), Some(preset.factors()));
+        }
+        assert_eq!(factor(16.into()), Some(vec![(*TWO, 4)]));
+        assert_eq!(factor(ONE), Some(vec![]));
+        assert_eq!(factor(ZERO), None);
+
+        let semiprime = U256::from(1_000_003u64) * 1_000_033u64;
+        assert_eq!(
+            factor(semiprime * 12),
+            Some(vec![
+                (*TWO, 2),
+                (3.into(), 1),
+                (1_000_003.into(), 1),
+                (1_000_033.into(), 1)
+            ])
+        );
+        assert!(!super::super::REGISTRY
+            .lock()
+            .unwrap()
+            .fields
+            .keys()
+            .any(|key| key.0 == semiprime));
+    }
+
+    #[test]
+    fn rho_budget_test() {
+        // Both factors sit far beyond what RHO_BUDGET steps can find.
+        let hard = ((ONE << 61) - ONE) * ((ONE << 89) - ONE);
+        assert_eq!(factor(hard), None);
+    }
+}
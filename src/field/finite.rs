@@ -0,0 +1,378 @@
+use crate::{element::FieldElement, field::Field};
+use primitive_types::U256;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Arithmetic shared by the prime, small-prime and binary fields.
+pub trait FiniteField: Copy + Eq + Debug + Serialize + DeserializeOwned + Send + Sync {
+    type Element: FiniteFieldElement<Field = Self>;
+
+    fn modulus(&self) -> U256;
+    fn zero(&self) -> Self::Element;
+    fn one(&self) -> Self::Element;
+    fn add(&self, left: &Self::Element, right: &Self::Element) -> Self::Element;
+    fn sub(&self, left: &Self::Element, right: &Self::Element) -> Self::Element;
+    fn mul(&self, left: &Self::Element, right: &Self::Element) -> Self::Element;
+    fn neg(&self, operand: &Self::Element) -> Self::Element;
+    fn inv(&self, operand: &Self::Element) -> Self::Element;
+    fn sample(&self, byte_array: &[u8]) -> Self::Element;
+    fn nth_root(&self, n: U256) -> Option<Self::Element>;
+
+    fn div(&self, left: &Self::Element, right: &Self::Element) -> Self::Element {
+        self.mul(left, &self.inv(right))
+    }
+
+    /// Raises to the characteristic, which `modulus` reports for every field.
+    fn frobenius(&self, operand: &Self::Element) -> Self::Element {
+        self.pow(operand, self.modulus())
+    }
+
+    fn pow(&self, base: &Self::Element, exponent: U256) -> Self::Element {
+        let mut acc = self.one();
+        for i in (0..exponent.bits()).rev() {
+            acc = self.mul(&acc, &acc);
+            if exponent.bit(i) {
+                acc = self.mul(&acc, base);
+            }
+        }
+        acc
+    }
+
+    /// The image of `n`: that many copies of one, by double-and-add.
+    fn integer(&self, n: usize) -> Self::Element {
+        let mut acc = self.zero();
+        for i in (0..usize::BITS - n.leading_zeros()).rev() {
+            acc = self.add(&acc, &acc);
+            if n >> i & 1 == 1 {
+                acc = self.add(&acc, &self.one());
+            }
+        }
+        acc
+    }
+
+    /// The coset offset FRI commits to in its parameter digest, if the field
+    /// publishes one.
+    fn try_generator(&self) -> Option<Self::Element> {
+        None
+    }
+
+    /// Samples from a long byte string; fields without a wide reduction fall
+    /// back to `sample`.
+    fn sample_wide(&self, byte_array: &[u8]) -> Self::Element {
+        self.sample(byte_array)
+    }
+
+    fn powers(&self, base: &Self::Element, n: usize) -> Vec<Self::Element> {
+        let mut power = self.one();
+        (0..n)
+            .map(|_| {
+                let current = power;
+                power = self.mul(&power, base);
+                current
+            })
+            .collect()
+    }
+
+    fn batch_pow(&self, bases: &[Self::Element], exponent: U256) -> Vec<Self::Element> {
+        bases.iter().map(|base| self.pow(base, exponent)).collect()
+    }
+
+    fn batch_add(&self, left: &[Self::Element], right: &[Self::Element]) -> Vec<Self::Element> {
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.add(l, r))
+            .collect()
+    }
+
+    fn batch_sub(&self, left: &[Self::Element], right: &[Self::Element]) -> Vec<Self::Element> {
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.sub(l, r))
+            .collect()
+    }
+
+    fn batch_mul(&self, left: &[Self::Element], right: &[Self::Element]) -> Vec<Self::Element> {
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| self.mul(l, r))
+            .collect()
+    }
+
+    /// Montgomery's trick: one inversion for the whole batch, or `None` if
+    /// any value is zero.
+    fn batch_inv(&self, values: &[Self::Element]) -> Option<Vec<Self::Element>> {
+        let mut prefix = Vec::with_capacity(values.len());
+        let mut acc = self.one();
+        for value in values {
+            prefix.push(acc);
+            acc = self.mul(&acc, value);
+        }
+        if acc.is_zero() {
+            return None;
+        }
+        let mut inverse = self.inv(&acc);
+        let mut inverses = vec![self.zero(); values.len()];
+        for i in (0..values.len()).rev() {
+            inverses[i] = self.mul(&inverse, &prefix[i]);
+            inverse = self.mul(&inverse, &values[i]);
+        }
+        Some(inverses)
+    }
+
+    fn sum_of_products<I>(&self, terms: I) -> Self::Element
+    where
+        I: IntoIterator<Item = (Self::Element, Self::Element)>,
+    {
+        terms
+            .into_iter()
+            .fold(self.zero(), |acc, (l, r)| self.add(&acc, &self.mul(&l, &r)))
+    }
+}
+
+/// An element that names its field. `Polynomial`, `MPolynomial` and `FRI` are
+/// generic over their coefficient type through this trait, so a bare
+/// `Vec<FieldElement>` still selects the prime-field instance.
+pub trait FiniteFieldElement:
+    Copy
+    + Eq
+    + Debug
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    type Field: FiniteField<Element = Self>;
+
+    fn field(&self) -> Self::Field;
+
+    /// The canonical integer behind the element, as transcripts and
+    /// explanations print it.
+    fn to_u256(&self) -> U256;
+
+    fn is_zero(&self) -> bool {
+        *self == self.field().zero()
+    }
+
+    fn pow(&self, exponent: U256) -> Self {
+        self.field().pow(self, exponent)
+    }
+
+    fn inv(&self) -> Self {
+        self.field().inv(self)
+    }
+}
+
+// Operators for elements whose arithmetic lives on a zero-sized field.
+macro_rules! element_ops {
+    ($element:ty, $field:expr) => {
+        impl std::ops::Add for $element {
+            type Output = $element;
+
+            fn add(self, rhs: $element) -> $element {
+                $crate::field::FiniteField::add(&$field, &self, &rhs)
+            }
+        }
+
+        impl std::ops::Sub for $element {
+            type Output = $element;
+
+            fn sub(self, rhs: $element) -> $element {
+                $crate::field::FiniteField::sub(&$field, &self, &rhs)
+            }
+        }
+
+        impl std::ops::Mul for $element {
+            type Output = $element;
+
+            fn mul(self, rhs: $element) -> $element {
+                $crate::field::FiniteField::mul(&$field, &self, &rhs)
+            }
+        }
+
+        impl std::ops::Div for $element {
+            type Output = $element;
+
+            fn div(self, rhs: $element) -> $element {
+                $crate::field::FiniteField::div(&$field, &self, &rhs)
+            }
+        }
+
+        impl std::ops::Neg for $element {
+            type Output = $element;
+
+            fn neg(self) -> $element {
+                $crate::field::FiniteField::neg(&$field, &self)
+            }
+        }
+
+        impl std::ops::AddAssign for $element {
+            fn add_assign(&mut self, rhs: $element) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl std::ops::SubAssign for $element {
+            fn sub_assign(&mut self, rhs: $element) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl std::ops::MulAssign for $element {
+            fn mul_assign(&mut self, rhs: $element) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+
+pub(crate) use element_ops;
+
+impl FiniteField for Field {
+    type Element = FieldElement;
+
+    fn modulus(&self) -> U256 {
+        self.p
+    }
+    fn zero(&self) -> FieldElement {
+        Field::zero(self)
+    }
+    fn one(&self) -> FieldElement {
+        Field::one(self)
+    }
+    fn add(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        Field::add(self, left, right)
+    }
+    fn sub(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        Field::sub(self, left, right)
+    }
+    fn mul(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        Field::mul(self, left, right)
+    }
+    fn neg(&self, operand: &FieldElement) -> FieldElement {
+        Field::neg(self, operand)
+    }
+    fn inv(&self, operand: &FieldElement) -> FieldElement {
+        Field::inv(self, operand)
+    }
+    fn sample(&self, byte_array: &[u8]) -> FieldElement {
+        Field::sample(self, byte_array)
+    }
+    fn nth_root(&self, n: U256) -> Option<FieldElement> {
+        Field::nth_root(self, n)
+    }
+    fn div(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        Field::div(self, left, right)
+    }
+    fn pow(&self, base: &FieldElement, exponent: U256) -> FieldElement {
+        Field::pow(self, base, exponent)
+    }
+    fn frobenius(&self, operand: &FieldElement) -> FieldElement {
+        self.pow_p(operand)
+    }
+    fn integer(&self, n: usize) -> FieldElement {
+        FieldElement::new(n.into(), *self)
+    }
+    fn try_generator(&self) -> Option<FieldElement> {
+        Field::try_generator(self)
+    }
+    fn sample_wide(&self, byte_array: &[u8]) -> FieldElement {
+        Field::sample_wide(self, byte_array)
+    }
+    fn powers(&self, base: &FieldElement, n: usize) -> Vec<FieldElement> {
+        Field::powers(self, base, n)
+    }
+    fn batch_pow(&self, bases: &[FieldElement], exponent: U256) -> Vec<FieldElement> {
+        Field::batch_pow(self, bases, exponent)
+    }
+    fn batch_add(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        Field::batch_add(self, left, right)
+    }
+    fn batch_sub(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        Field::batch_sub(self, left, right)
+    }
+    fn batch_mul(&self, left: &[FieldElement], right: &[FieldElement]) -> Vec<FieldElement> {
+        Field::batch_mul(self, left, right)
+    }
+    fn batch_inv(&self, values: &[FieldElement]) -> Option<Vec<FieldElement>> {
+        Field::batch_inv(self, values)
+    }
+    fn sum_of_products<I>(&self, terms: I) -> FieldElement
+    where
+        I: IntoIterator<Item = (FieldElement, FieldElement)>,
+    {
+        Field::sum_of_products(self, terms)
+    }
+}
+
+impl FiniteFieldElement for FieldElement {
+    type Field = Field;
+
+    fn field(&self) -> Field {
+        *self.field
+    }
+    fn to_u256(&self) -> U256 {
+        self.value
+    }
+    fn is_zero(&self) -> bool {
+        FieldElement::is_zero(self)
+    }
+    fn pow(&self, exponent: U256) -> FieldElement {
+        FieldElement::pow(self, exponent)
+    }
+    fn inv(&self) -> FieldElement {
+        FieldElement::inv(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::*, field::presets::Preset, merkle::Merkle};
+
+    fn geometric_sum<F: FiniteField>(field: &F, ratio: &F::Element, n: u64) -> F::Element {
+        let numerator = field.sub(&field.pow(ratio, n.into()), &field.one());
+        field.div(&numerator, &field.sub(ratio, &field.one()))
+    }
+
+    #[test]
+    fn finite_field_test() {
+        for f in [Field::new(*PRIME), Field::from_preset(Preset::BabyBear)] {
+            assert_eq!(FiniteField::modulus(&f), f.p);
+            let root = FiniteField::nth_root(&f, 8.into()).unwrap();
+            assert_eq!(FiniteField::nth_root(&f, 7.into()), None);
+            assert_eq!(FiniteField::pow(&f, &root, 8.into()), f.one());
+            assert_eq!(geometric_sum(&f, &root, 8), f.zero());
+
+            let x = FiniteField::sample(&f, &[7, 1]);
+            assert_eq!(
+                FiniteField::add(&f, &x, &FiniteField::neg(&f, &x)),
+                f.zero()
+            );
+            assert_eq!(FiniteField::mul(&f, &x, &FiniteField::inv(&f, &x)), f.one());
+            assert_eq!(
+                FiniteField::frobenius(&f, &x),
+                FiniteField::pow(&f, &x, f.p)
+            );
+
+            let leafs = f.powers(&root, 8);
+            let proof = Merkle::open(3, &leafs).unwrap();
+            assert!(Merkle::verify(
+                &Merkle::commit(&leafs),
+                3,
+                &proof,
+                &leafs[3]
+            ));
+        }
+    }
+}
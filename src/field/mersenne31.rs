@@ -0,0 +1,259 @@
+use crate::{
+    element::FieldElement,
+    field::{finite::element_ops, Field, FiniteField, FiniteFieldElement},
+    Error,
+};
+use once_cell::sync::Lazy;
+use primitive_types::U256;
+use serde::{Deserialize, Deserializer, Serialize};
+
+pub const MODULUS: u32 = (1 << 31) - 1;
+
+pub const CIRCLE_LOG_ORDER: u32 = 31;
+
+static FIELD: Lazy<Field> = Lazy::new(|| Field::new(MODULUS.into()));
+
+pub static FACTORS: Lazy<Vec<(U256, u32)>> = Lazy::new(|| {
+    vec![
+        (2.into(), 1),
+        (3.into(), 2),
+        (7.into(), 1),
+        (11.into(), 1),
+        (31.into(), 1),
+        (151.into(), 1),
+        (331.into(), 1),
+    ]
+});
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Mersenne31;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Hash, Serialize)]
+pub struct M31(u32);
+
+impl M31 {
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct CirclePoint {
+    pub x: M31,
+    pub y: M31,
+}
+
+impl Mersenne31 {
+    pub fn field(&self) -> Field {
+        *FIELD
+    }
+
+    pub fn element(&self, value: u64) -> M31 {
+        M31(reduce(value))
+    }
+
+    pub fn circle_identity(&self) -> CirclePoint {
+        CirclePoint {
+            x: self.one(),
+            y: self.zero(),
+        }
+    }
+
+    pub fn circle_add(&self, left: &CirclePoint, right: &CirclePoint) -> CirclePoint {
+        CirclePoint {
+            x: self.sub(&self.mul(&left.x, &right.x), &self.mul(&left.y, &right.y)),
+            y: self.add(&self.mul(&left.x, &right.y), &self.mul(&left.y, &right.x)),
+        }
+    }
+
+    pub fn circle_double(&self, point: &CirclePoint) -> CirclePoint {
+        self.circle_add(point, point)
+    }
+
+    pub fn circle_conjugate(&self, point: &CirclePoint) -> CirclePoint {
+        CirclePoint {
+            x: point.x,
+            y: self.neg(&point.y),
+        }
+    }
+
+    pub fn circle_generator(&self, log_order: u32) -> Option<CirclePoint> {
+        if log_order > CIRCLE_LOG_ORDER {
+            return None;
+        }
+        let mut point = CirclePoint {
+            x: M31(2),
+            y: M31(1268011823),
+        };
+        for _ in log_order..CIRCLE_LOG_ORDER {
+            point = self.circle_double(&point);
+        }
+        Some(point)
+    }
+
+    pub fn circle_subgroup(&self, log_order: u32) -> Option<Vec<CirclePoint>> {
+        let generator = self.circle_generator(log_order)?;
+        let mut point = self.circle_identity();
+        Some(
+            (0..1usize << log_order)
+                .map(|_| {
+                    let current = point;
+                    point = self.circle_add(&point, &generator);
+                    current
+                })
+                .collect(),
+        )
+    }
+}
+
+fn reduce(value: u64) -> u32 {
+    let folded = (value & MODULUS as u64) + (value >> 31);
+    let folded = (folded & MODULUS as u64) + (folded >> 31);
+    if folded >= MODULUS as u64 {
+        (folded - MODULUS as u64) as u32
+    } else {
+        folded as u32
+    }
+}
+
+impl FiniteField for Mersenne31 {
+    type Element = M31;
+
+    fn modulus(&self) -> U256 {
+        MODULUS.into()
+    }
+    fn zero(&self) -> M31 {
+        M31(0)
+    }
+    fn one(&self) -> M31 {
+        M31(1)
+    }
+    fn add(&self, left: &M31, right: &M31) -> M31 {
+        M31(reduce(left.0 as u64 + right.0 as u64))
+    }
+    fn sub(&self, left: &M31, right: &M31) -> M31 {
+        M31(reduce(left.0 as u64 + (MODULUS - right.0) as u64))
+    }
+    fn mul(&self, left: &M31, right: &M31) -> M31 {
+        M31(reduce(left.0 as u64 * right.0 as u64))
+    }
+    fn neg(&self, operand: &M31) -> M31 {
+        self.sub(&self.zero(), operand)
+    }
+    fn inv(&self, operand: &M31) -> M31 {
+        assert!(operand.0 != 0);
+        self.pow(operand, (MODULUS - 2).into())
+    }
+    fn sample(&self, byte_array: &[u8]) -> M31 {
+        M31::try_from(self.field().sample(byte_array)).unwrap()
+    }
+    fn nth_root(&self, n: U256) -> Option<M31> {
+        let root = self.field().primitive_nth_root_with(n, &FACTORS)?;
+        Some(M31::try_from(root).unwrap())
+    }
+    fn sample_wide(&self, byte_array: &[u8]) -> M31 {
+        M31::try_from(self.field().sample_wide(byte_array)).unwrap()
+    }
+    fn try_generator(&self) -> Option<M31> {
+        Some(M31::try_from(self.field().try_generator()?).unwrap())
+    }
+}
+
+impl FiniteFieldElement for M31 {
+    type Field = Mersenne31;
+
+    fn field(&self) -> Mersenne31 {
+        Mersenne31
+    }
+    fn to_u256(&self) -> U256 {
+        self.0.into()
+    }
+}
+
+element_ops!(M31, Mersenne31);
+
+impl From<M31> for FieldElement {
+    fn from(element: M31) -> Self {
+        FieldElement::new(element.0.into(), Mersenne31.field())
+    }
+}
+
+impl TryFrom<FieldElement> for M31 {
+    type Error = Error;
+
+    fn try_from(element: FieldElement) -> Result<Self, Error> {
+        if element.field.p != MODULUS.into() {
+            return Err(Error::FieldMismatch);
+        }
+        Ok(M31(element.value.low_u32()))
+    }
+}
+
+impl<'de> Deserialize<'de> for M31 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u32::deserialize(deserializer)?;
+        if value >= MODULUS {
+            return Err(serde::de::Error::custom("element exceeds the modulus"));
+        }
+        Ok(M31(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mersenne31_test() {
+        let f = Mersenne31;
+        let x = f.element(u64::MAX);
+        let y = f.element(MODULUS as u64 - 3);
+        let (fx, fy) = (FieldElement::from(x), FieldElement::from(y));
+        assert_eq!(FieldElement::from(f.add(&x, &y)), fx + fy);
+        assert_eq!(FieldElement::from(f.sub(&x, &y)), fx - fy);
+        assert_eq!(FieldElement::from(f.mul(&x, &y)), fx * fy);
+        assert_eq!(FieldElement::from(f.mul(&y, &y)), fy * fy);
+        assert_eq!(FieldElement::from(f.neg(&x)), -&fx);
+        assert_eq!(f.mul(&y, &f.inv(&y)), f.one());
+        assert_eq!(f.sub(&x, &x), f.zero());
+        assert_eq!(f.element(MODULUS as u64), f.zero());
+
+        for n in [2u64, 9, 7 * 331, MODULUS as u64 - 1] {
+            let root = f.nth_root(n.into()).unwrap();
+            assert_eq!(f.pow(&root, n.into()), f.one());
+            for (q, _) in FACTORS.iter().filter(|(q, _)| (n % q.low_u64()) == 0) {
+                assert_ne!(f.pow(&root, (n / q.low_u64()).into()), f.one());
+            }
+        }
+        assert!(M31::try_from(Field::new(17.into()).one()).is_err());
+
+        let serialized = serde_json::to_string(&y).unwrap();
+        assert_eq!(serde_json::from_str::<M31>(&serialized).unwrap(), y);
+        assert!(serde_json::from_str::<M31>(&MODULUS.to_string()).is_err());
+    }
+
+    #[test]
+    fn circle_test() {
+        let f = Mersenne31;
+        let generator = f.circle_generator(CIRCLE_LOG_ORDER).unwrap();
+        let mut point = generator;
+        for _ in 0..CIRCLE_LOG_ORDER - 1 {
+            point = f.circle_double(&point);
+        }
+        assert_ne!(point, f.circle_identity());
+        assert_eq!(f.circle_double(&point), f.circle_identity());
+        assert!(f.circle_generator(CIRCLE_LOG_ORDER + 1).is_none());
+
+        let subgroup = f.circle_subgroup(4).unwrap();
+        assert_eq!(subgroup.len(), 16);
+        for p in &subgroup {
+            assert_eq!(f.add(&f.mul(&p.x, &p.x), &f.mul(&p.y, &p.y)), f.one());
+            assert_eq!(f.circle_add(p, &f.circle_conjugate(p)), f.circle_identity());
+        }
+        let generator = f.circle_generator(4).unwrap();
+        assert_eq!(f.circle_add(&subgroup[15], &generator), f.circle_identity());
+    }
+}
@@ -0,0 +1,215 @@
+use primitive_types::{U256, U512};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Montgomery {
+    pub p: U256,
+    n0: u64,
+    r: U256,
+    r2: U256,
+}
+
+impl Montgomery {
+    pub fn new(p: U256) -> Option<Self> {
+        if !p.bit(0) || p <= U256::one() {
+            return None;
+        }
+        let mut inverse: u64 = 1;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(p.0[0].wrapping_mul(inverse)));
+        }
+        let modulus = U512::from(p);
+        let r = U256::try_from((U512::one() << 256) % modulus).unwrap();
+        let r2 = U256::try_from(r.full_mul(r) % modulus).unwrap();
+        Some(Montgomery {
+            p,
+            n0: inverse.wrapping_neg(),
+            r,
+            r2,
+        })
+    }
+
+    pub fn one(&self) -> U256 {
+        self.r
+    }
+
+    pub fn to_montgomery(&self, value: U256) -> U256 {
+        self.mul(value, self.r2)
+    }
+
+    pub fn from_montgomery(&self, value: U256) -> U256 {
+        self.mul(value, U256::one())
+    }
+
+    pub fn mul_canonical(&self, left: U256, right: U256) -> U256 {
+        self.mul(self.mul(left, right), self.r2)
+    }
+
+    pub fn pow(&self, base: U256, exponent: U256) -> U256 {
+        super::windowed_pow(base, self.r, exponent, |a, b| self.mul(a, b))
+    }
+
+    pub fn mul(&self, left: U256, right: U256) -> U256 {
+        let (a, b, p) = (&left.0, &right.0, &self.p.0);
+        let mut t = [0u64; 6];
+        for &word in b {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let sum = t[j] as u128 + a[j] as u128 * word as u128 + carry;
+                t[j] = sum as u64;
+                carry = sum >> 64;
+            }
+            let sum = t[4] as u128 + carry;
+            t[4] = sum as u64;
+            t[5] = (sum >> 64) as u64;
+
+            let m = t[0].wrapping_mul(self.n0);
+            let mut carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+            for j in 1..4 {
+                let sum = t[j] as u128 + m as u128 * p[j] as u128 + carry;
+                t[j - 1] = sum as u64;
+                carry = sum >> 64;
+            }
+            let sum = t[4] as u128 + carry;
+            t[3] = sum as u64;
+            t[4] = t[5] + (sum >> 64) as u64;
+        }
+        // t < 2p, so subtract p once unless that borrows out of the top word.
+        let (difference, borrow) = sub_limbs([t[0], t[1], t[2], t[3]], p);
+        let keep = (t[4] < borrow as u64) as u64;
+        select(keep, [t[0], t[1], t[2], t[3]], difference)
+    }
+
+    /// `left + right` on reduced values, without branching on them.
+    pub fn add(&self, left: U256, right: U256) -> U256 {
+        let mut sum = [0u64; 4];
+        let mut carry = false;
+        for (i, word) in sum.iter_mut().enumerate() {
+            let (partial, first) = left.0[i].overflowing_add(right.0[i]);
+            let (partial, second) = partial.overflowing_add(carry as u64);
+            *word = partial;
+            carry = first | second;
+        }
+        let (difference, borrow) = sub_limbs(sum, &self.p.0);
+        let keep = ((carry as u64) < borrow as u64) as u64;
+        select(keep, sum, difference)
+    }
+
+    /// `left - right` on reduced values, without branching on them.
+    pub fn sub(&self, left: U256, right: U256) -> U256 {
+        let (difference, borrow) = sub_limbs(left.0, &right.0);
+        let mask = 0u64.wrapping_sub(borrow as u64);
+        let mut result = [0u64; 4];
+        let mut carry = false;
+        for (i, word) in result.iter_mut().enumerate() {
+            let (partial, first) = difference[i].overflowing_add(self.p.0[i] & mask);
+            let (partial, second) = partial.overflowing_add(carry as u64);
+            *word = partial;
+            carry = first | second;
+        }
+        U256(result)
+    }
+
+    /// Fixed-window exponentiation: every call performs the same squarings,
+    /// multiplications and table scans for a given exponent length, whatever
+    /// the base or the exponent's bits.
+    pub fn pow_fixed(&self, base: U256, exponent: U256) -> U256 {
+        let mut table = [self.r; 1 << FIXED_WINDOW];
+        for k in 1..table.len() {
+            table[k] = self.mul(table[k - 1], base);
+        }
+        let windows = exponent.bits().div_ceil(FIXED_WINDOW);
+        let mut acc = self.r;
+        for window in (0..windows).rev() {
+            for _ in 0..FIXED_WINDOW {
+                acc = self.mul(acc, acc);
+            }
+            let digit = ((exponent >> (window * FIXED_WINDOW)).low_u64() as usize)
+                & ((1 << FIXED_WINDOW) - 1);
+            let mut entry = [0u64; 4];
+            for (k, power) in table.iter().enumerate() {
+                let mask = ((k == digit) as u64).wrapping_neg();
+                for (word, limb) in entry.iter_mut().zip(power.0) {
+                    *word |= limb & mask;
+                }
+            }
+            acc = self.mul(acc, U256(entry));
+        }
+        acc
+    }
+}
+
+const FIXED_WINDOW: usize = 4;
+
+fn sub_limbs(a: [u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut result = [0u64; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (partial, first) = a[i].overflowing_sub(b[i]);
+        let (partial, second) = partial.overflowing_sub(borrow as u64);
+        result[i] = partial;
+        borrow = first | second;
+    }
+    (result, borrow)
+}
+
+// `a` when `keep` is 1 and `b` when it is 0, by masking instead of branching.
+fn select(keep: u64, a: [u64; 4], b: [u64; 4]) -> U256 {
+    let mask = keep.wrapping_neg();
+    let mut result = [0u64; 4];
+    for i in 0..4 {
+        result[i] = (a[i] & mask) | (b[i] & !mask);
+    }
+    U256(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    #[test]
+    fn montgomery_test() {
+        let large = U256::MAX - (ONE << 32) - 976;
+        for p in [
+            *PRIME,
+            U256::from(17),
+            U256::from(0xffffffff00000001u64),
+            large,
+        ] {
+            let montgomery = Montgomery::new(p).unwrap();
+            let values = [ZERO, ONE, p - ONE, p >> 1, (p >> 3) + 12345].map(|value| value % p);
+            for a in values {
+                assert_eq!(montgomery.from_montgomery(montgomery.to_montgomery(a)), a);
+                for b in values {
+                    let expected = U256::try_from(a.full_mul(b) % U512::from(p)).unwrap();
+                    assert_eq!(montgomery.mul_canonical(a, b), expected);
+                }
+            }
+            let base = (p >> 2) + 7;
+            let power = montgomery
+                .from_montgomery(montgomery.pow(montgomery.to_montgomery(base), U256::from(5)));
+            let mut expected = ONE;
+            for _ in 0..5 {
+                expected = montgomery.mul_canonical(expected, base);
+            }
+            assert_eq!(power, expected);
+            let half = montgomery.to_montgomery(p >> 1);
+            let top = montgomery.to_montgomery(p - ONE);
+            for (a, b) in [(half, half), (top, top), (ZERO, top), (top, ZERO)] {
+                let (x, y) = (montgomery.from_montgomery(a), montgomery.from_montgomery(b));
+                let sum = U256::try_from((U512::from(x) + U512::from(y)) % U512::from(p)).unwrap();
+                assert_eq!(montgomery.from_montgomery(montgomery.add(a, b)), sum);
+                assert_eq!(montgomery.add(montgomery.sub(a, b), b), a);
+            }
+            let exponent = (p >> 3) + 12345;
+            assert_eq!(
+                montgomery.pow_fixed(montgomery.to_montgomery(base), exponent),
+                montgomery.pow(montgomery.to_montgomery(base), exponent)
+            );
+            assert_eq!(montgomery.pow_fixed(base, ZERO), montgomery.one());
+            assert_eq!(montgomery.from_montgomery(montgomery.one()), ONE);
+        }
+        assert_eq!(Montgomery::new(U256::from(16)), None);
+        assert_eq!(Montgomery::new(ONE), None);
+    }
+}
@@ -0,0 +1,119 @@
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Preset {
+    Tutorial,
+    Goldilocks,
+    BabyBear,
+    Toy64,
+}
+
+pub const ALL: [Preset; 4] = [
+    Preset::Tutorial,
+    Preset::Goldilocks,
+    Preset::BabyBear,
+    Preset::Toy64,
+];
+
+impl Preset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Tutorial => "tutorial",
+            Preset::Goldilocks => "goldilocks",
+            Preset::BabyBear => "babybear",
+            Preset::Toy64 => "toy64",
+        }
+    }
+
+    pub fn prime(&self) -> U256 {
+        match self {
+            Preset::Tutorial => (1u128 + 407 * (1 << 119)).into(),
+            Preset::Goldilocks => 0xffff_ffff_0000_0001u64.into(),
+            Preset::BabyBear => 0x7800_0001u64.into(),
+            Preset::Toy64 => (27 * (1u64 << 59) + 1).into(),
+        }
+    }
+
+    /// The published generator of the two-adic subgroup, of order
+    /// 2^`two_adicity`; not a generator of the whole multiplicative group.
+    pub fn generator(&self) -> U256 {
+        match self {
+            Preset::Tutorial => 85408008396924667383611388730472331217u128.into(),
+            Preset::Goldilocks => 1753635133440165772u64.into(),
+            Preset::BabyBear => 440564289u64.into(),
+            Preset::Toy64 => 7450580596923828125u64.into(),
+        }
+    }
+
+    pub fn two_adicity(&self) -> u32 {
+        match self {
+            Preset::Tutorial => 119,
+            Preset::Goldilocks => 32,
+            Preset::BabyBear => 27,
+            Preset::Toy64 => 59,
+        }
+    }
+
+    pub fn factors(&self) -> Vec<(U256, u32)> {
+        match self {
+            Preset::Tutorial => vec![(2.into(), 119), (11.into(), 1), (37.into(), 1)],
+            Preset::Goldilocks => vec![
+                (2.into(), 32),
+                (3.into(), 1),
+                (5.into(), 1),
+                (17.into(), 1),
+                (257.into(), 1),
+                (65537.into(), 1),
+            ],
+            Preset::BabyBear => vec![(2.into(), 27), (3.into(), 1), (5.into(), 1)],
+            Preset::Toy64 => vec![(2.into(), 59), (3.into(), 3)],
+        }
+    }
+
+    pub fn from_prime(p: U256) -> Option<Preset> {
+        ALL.into_iter().find(|preset| preset.prime() == p)
+    }
+
+    pub fn from_name(name: &str) -> Option<Preset> {
+        ALL.into_iter().find(|preset| preset.name() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::*, field::Field};
+
+    #[test]
+    fn presets_test() {
+        for preset in ALL {
+            let f = Field::from_preset(preset);
+            assert_eq!(f.preset(), Some(preset));
+            assert_eq!(Preset::from_name(preset.name()), Some(preset));
+
+            let mut order = ONE;
+            for (q, e) in preset.factors() {
+                for _ in 0..e {
+                    order *= q;
+                }
+            }
+            assert_eq!(order, f.p - ONE);
+            assert_eq!(f.two_adicity(), preset.two_adicity());
+
+            let g = f.generator();
+            let one = f.one();
+            assert_eq!(g ^ (ONE << preset.two_adicity()), one);
+            assert_ne!(g ^ (ONE << (preset.two_adicity() - 1)), one);
+
+            let root = f.primitive_nth_root(16.into());
+            assert_eq!(root ^ 16.into(), one);
+            assert_ne!(root ^ 8.into(), one);
+            let q = preset.factors()[1].0;
+            let root = f.primitive_nth_root(q);
+            assert_eq!(root ^ q, one);
+            assert_ne!(root, one);
+        }
+        assert_eq!(Preset::from_prime(7.into()), None);
+    }
+}
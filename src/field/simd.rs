@@ -0,0 +1,208 @@
+use primitive_types::U256;
+
+// Lane-wise arithmetic for moduli below 2^31, packed eight to an AVX2 register.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Packed31 {
+    pub p: u32,
+    p_inv: u32,
+    r2: u32,
+}
+
+impl Packed31 {
+    pub fn new(p: U256) -> Option<Self> {
+        if p.bits() > 31 || !p.bit(0) || p <= U256::one() {
+            return None;
+        }
+        let p = p.low_u32();
+        let mut p_inv: u32 = 1;
+        for _ in 0..5 {
+            p_inv = p_inv.wrapping_mul(2u32.wrapping_sub(p.wrapping_mul(p_inv)));
+        }
+        let r2 = ((1u128 << 64) % p as u128) as u32;
+        Some(Packed31 { p, p_inv, r2 })
+    }
+
+    pub fn add(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { self.add_avx2(left, right) };
+        }
+        self.add_scalar(left, right)
+    }
+
+    pub fn sub(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { self.sub_avx2(left, right) };
+        }
+        self.sub_scalar(left, right)
+    }
+
+    pub fn mul(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { self.mul_avx2(left, right) };
+        }
+        self.mul_scalar(left, right)
+    }
+
+    pub fn add_scalar(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        left.iter()
+            .zip(right)
+            .map(|(a, b)| self.add_lane(*a, *b))
+            .collect()
+    }
+
+    pub fn sub_scalar(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        left.iter()
+            .zip(right)
+            .map(|(a, b)| self.sub_lane(*a, *b))
+            .collect()
+    }
+
+    pub fn mul_scalar(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        left.iter()
+            .zip(right)
+            .map(|(a, b)| self.mul_lane(*a, *b))
+            .collect()
+    }
+
+    fn add_lane(&self, left: u32, right: u32) -> u32 {
+        let sum = left + right;
+        sum.min(sum.wrapping_sub(self.p))
+    }
+
+    fn sub_lane(&self, left: u32, right: u32) -> u32 {
+        let difference = left.wrapping_sub(right);
+        difference.min(difference.wrapping_add(self.p))
+    }
+
+    fn mul_lane(&self, left: u32, right: u32) -> u32 {
+        self.montgomery(self.montgomery(left, right), self.r2)
+    }
+
+    // Returns left * right / 2^32 mod p.
+    fn montgomery(&self, left: u32, right: u32) -> u32 {
+        let product = left as u64 * right as u64;
+        let q = (product as u32).wrapping_mul(self.p_inv);
+        let difference =
+            ((product >> 32) as u32).wrapping_sub(((q as u64 * self.p as u64) >> 32) as u32);
+        difference.min(difference.wrapping_add(self.p))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Packed31 {
+    #[target_feature(enable = "avx2")]
+    unsafe fn add_avx2(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        use std::arch::x86_64::*;
+        let p = _mm256_set1_epi32(self.p as i32);
+        self.map_avx2(left, right, |a, b| {
+            let sum = _mm256_add_epi32(a, b);
+            _mm256_min_epu32(sum, _mm256_sub_epi32(sum, p))
+        })
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn sub_avx2(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        use std::arch::x86_64::*;
+        let p = _mm256_set1_epi32(self.p as i32);
+        self.map_avx2(left, right, |a, b| {
+            let difference = _mm256_sub_epi32(a, b);
+            _mm256_min_epu32(difference, _mm256_add_epi32(difference, p))
+        })
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul_avx2(&self, left: &[u32], right: &[u32]) -> Vec<u32> {
+        use std::arch::x86_64::*;
+        let r2 = _mm256_set1_epi32(self.r2 as i32);
+        self.map_avx2(left, right, |a, b| {
+            let product = self.montgomery_avx2(a, b);
+            self.montgomery_avx2(product, r2)
+        })
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn montgomery_avx2(
+        &self,
+        a: std::arch::x86_64::__m256i,
+        b: std::arch::x86_64::__m256i,
+    ) -> std::arch::x86_64::__m256i {
+        use std::arch::x86_64::*;
+        let p = _mm256_set1_epi32(self.p as i32);
+        let p_inv = _mm256_set1_epi32(self.p_inv as i32);
+        // _mm256_mul_epu32 only multiplies the even lanes, so the odd lanes go through a shifted copy.
+        let high = |even: __m256i, odd: __m256i| {
+            _mm256_blend_epi32::<0b1010_1010>(_mm256_srli_epi64::<32>(even), odd)
+        };
+        let product_even = _mm256_mul_epu32(a, b);
+        let product_odd = _mm256_mul_epu32(_mm256_srli_epi64::<32>(a), _mm256_srli_epi64::<32>(b));
+        let q_even = _mm256_mul_epu32(product_even, p_inv);
+        let q_odd = _mm256_mul_epu32(product_odd, p_inv);
+        let qp_even = _mm256_mul_epu32(q_even, p);
+        let qp_odd = _mm256_mul_epu32(q_odd, p);
+        let difference = _mm256_sub_epi32(high(product_even, product_odd), high(qp_even, qp_odd));
+        _mm256_min_epu32(difference, _mm256_add_epi32(difference, p))
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn map_avx2<F>(&self, left: &[u32], right: &[u32], kernel: F) -> Vec<u32>
+    where
+        F: Fn(std::arch::x86_64::__m256i, std::arch::x86_64::__m256i) -> std::arch::x86_64::__m256i,
+    {
+        use std::arch::x86_64::*;
+        let len = left.len().min(right.len());
+        let mut out = vec![0u32; len];
+        let chunks = len / 8;
+        for i in 0..chunks {
+            let a = _mm256_loadu_si256(left[8 * i..].as_ptr() as *const __m256i);
+            let b = _mm256_loadu_si256(right[8 * i..].as_ptr() as *const __m256i);
+            _mm256_storeu_si256(out[8 * i..].as_mut_ptr() as *mut __m256i, kernel(a, b));
+        }
+        for i in 8 * chunks..len {
+            let (a, b) = (
+                _mm256_set1_epi32(left[i] as i32),
+                _mm256_set1_epi32(right[i] as i32),
+            );
+            out[i] = _mm256_extract_epi32::<0>(kernel(a, b)) as u32;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{babybear, mersenne31};
+
+    #[test]
+    fn packed31_test() {
+        for p in [mersenne31::MODULUS, babybear::MODULUS, 97] {
+            let packed = Packed31::new(p.into()).unwrap();
+            let values: Vec<u32> = (0..37u64)
+                .map(|i| ((i * 0x9e37_79b9 + i * i * 7) % p as u64) as u32)
+                .chain([0, 1, p - 1, p / 2])
+                .collect();
+            let shifted: Vec<u32> = values.iter().rev().copied().collect();
+            let expected = |op: fn(u64, u64, u64) -> u64| -> Vec<u32> {
+                values
+                    .iter()
+                    .zip(&shifted)
+                    .map(|(a, b)| op(*a as u64, *b as u64, p as u64) as u32)
+                    .collect()
+            };
+            let add = expected(|a, b, p| (a + b) % p);
+            let sub = expected(|a, b, p| (a + p - b) % p);
+            let mul = expected(|a, b, p| a * b % p);
+            assert_eq!(packed.add(&values, &shifted), add);
+            assert_eq!(packed.add_scalar(&values, &shifted), add);
+            assert_eq!(packed.sub(&values, &shifted), sub);
+            assert_eq!(packed.sub_scalar(&values, &shifted), sub);
+            assert_eq!(packed.mul(&values, &shifted), mul);
+            assert_eq!(packed.mul_scalar(&values, &shifted), mul);
+        }
+        assert_eq!(Packed31::new((1u64 << 31).into()), None);
+        assert_eq!(Packed31::new(0xffff_fffbu32.into()), None);
+    }
+}
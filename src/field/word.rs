@@ -0,0 +1,130 @@
+use primitive_types::U256;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Word128 {
+    pub p: u128,
+    n0: u64,
+    r: u128,
+    r2: u128,
+}
+
+impl Word128 {
+    pub fn new(p: U256) -> Option<Self> {
+        if p.bits() > 128 || !p.bit(0) || p <= U256::one() {
+            return None;
+        }
+        let modulus = p.low_u128();
+        let mut inverse: u64 = 1;
+        for _ in 0..6 {
+            inverse =
+                inverse.wrapping_mul(2u64.wrapping_sub((modulus as u64).wrapping_mul(inverse)));
+        }
+        let r = (U256::one() << 128) % p;
+        let r2 = (r * r) % p;
+        Some(Word128 {
+            p: modulus,
+            n0: inverse.wrapping_neg(),
+            r: r.low_u128(),
+            r2: r2.low_u128(),
+        })
+    }
+
+    pub fn add(&self, left: U256, right: U256) -> U256 {
+        let (sum, overflow) = left.low_u128().overflowing_add(right.low_u128());
+        if overflow || sum >= self.p {
+            sum.wrapping_sub(self.p).into()
+        } else {
+            sum.into()
+        }
+    }
+
+    pub fn sub(&self, left: U256, right: U256) -> U256 {
+        let (left, right) = (left.low_u128(), right.low_u128());
+        if left >= right {
+            (left - right).into()
+        } else {
+            left.wrapping_sub(right).wrapping_add(self.p).into()
+        }
+    }
+
+    pub fn mul_canonical(&self, left: U256, right: U256) -> U256 {
+        let product = self.mul(left.low_u128(), right.low_u128());
+        self.mul(product, self.r2).into()
+    }
+
+    pub fn pow(&self, base: U256, exponent: U256) -> U256 {
+        let base = self.mul(base.low_u128(), self.r2);
+        let acc = super::windowed_pow(base, self.r, exponent, |a, b| self.mul(a, b));
+        self.mul(acc, 1).into()
+    }
+
+    pub fn mul(&self, left: u128, right: u128) -> u128 {
+        let a = [left as u64, (left >> 64) as u64];
+        let p = [self.p as u64, (self.p >> 64) as u64];
+        let mut t = [0u64; 4];
+        for word in [right as u64, (right >> 64) as u64] {
+            let mut carry = 0u128;
+            for j in 0..2 {
+                let sum = t[j] as u128 + a[j] as u128 * word as u128 + carry;
+                t[j] = sum as u64;
+                carry = sum >> 64;
+            }
+            let sum = t[2] as u128 + carry;
+            t[2] = sum as u64;
+            t[3] = (sum >> 64) as u64;
+
+            let m = t[0].wrapping_mul(self.n0);
+            let carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+            let sum = t[1] as u128 + m as u128 * p[1] as u128 + carry;
+            t[0] = sum as u64;
+            let sum = t[2] as u128 + (sum >> 64);
+            t[1] = sum as u64;
+            t[2] = t[3] + (sum >> 64) as u64;
+        }
+        let result = t[0] as u128 | (t[1] as u128) << 64;
+        if t[2] != 0 || result >= self.p {
+            result.wrapping_sub(self.p)
+        } else {
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+    use primitive_types::U512;
+
+    #[test]
+    fn word128_test() {
+        let large = (ONE << 128) - 159;
+        for p in [
+            *PRIME,
+            U256::from(17),
+            U256::from(0xffffffff00000001u64),
+            large,
+        ] {
+            let word = Word128::new(p).unwrap();
+            let values = [ZERO, ONE, p - ONE, p >> 1, (p >> 3) + 12345].map(|value| value % p);
+            for a in values {
+                for b in values {
+                    let expected = U256::try_from(a.full_mul(b) % U512::from(p)).unwrap();
+                    assert_eq!(word.mul_canonical(a, b), expected);
+                    let reduce = |value: U512| U256::try_from(value % U512::from(p)).unwrap();
+                    assert_eq!(word.add(a, b), reduce(U512::from(a) + b));
+                    assert_eq!(word.sub(a, b), reduce(U512::from(a) + p - b));
+                }
+            }
+            let base = (p >> 2) + 7;
+            let mut expected = ONE;
+            for _ in 0..5 {
+                expected = word.mul_canonical(expected, base);
+            }
+            assert_eq!(word.pow(base, U256::from(5)), expected);
+            assert_eq!(word.pow(base, ZERO), ONE);
+        }
+        assert_eq!(Word128::new(U256::from(16)), None);
+        assert_eq!(Word128::new(ONE << 128 | ONE), None);
+    }
+}
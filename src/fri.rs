@@ -1,26 +1,78 @@
+#[cfg(feature = "metrics")]
+use crate::metrics::ProverMetrics;
 use crate::{
+    channel::{ProverChannel, VerifierChannel},
     consts::*,
+    domain::{Coset, DomainInverses},
     element::FieldElement,
-    field::Field,
-    merkle::{self, Merkle},
+    explain::{Explainer, Explanation, Step},
+    field::{FiniteField, FiniteFieldElement},
+    hash::Hasher,
+    merkle::Merkle,
+    ntt,
     polynomial::Polynomial,
-    proofstream::{Object, ProofStream},
+    proofstream::Object,
+    recorder::Recorder,
+    sampling, Error, Result,
 };
-use core::panic;
+use serde::{Deserialize, Serialize};
 
-pub struct FRI {
-    pub offset: FieldElement,
-    pub omega: FieldElement,
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum QueryStrategy {
+    #[default]
+    Folded,
+    PerRound,
+    Derived,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum ChallengeSampling {
+    #[default]
+    Wide,
+    Legacy,
+}
+
+pub const WIDE_CHALLENGE_BYTES: usize = 64;
+
+impl ChallengeSampling {
+    pub fn num_bytes(&self, digest_length: usize) -> usize {
+        match self {
+            ChallengeSampling::Wide => WIDE_CHALLENGE_BYTES,
+            ChallengeSampling::Legacy => digest_length,
+        }
+    }
+
+    pub fn sample<F: FiniteField>(&self, field: &F, byte_array: &[u8]) -> F::Element {
+        match self {
+            ChallengeSampling::Wide => field.sample_wide(byte_array),
+            ChallengeSampling::Legacy => field.sample(byte_array),
+        }
+    }
+}
+
+pub(crate) struct Commitments<E: FiniteFieldElement> {
+    pub correction: Option<(Vec<u8>, E, usize)>,
+    pub roots: Vec<Vec<u8>>,
+    pub alphas: Vec<E>,
+}
+
+pub struct FRI<E: FiniteFieldElement = FieldElement> {
+    pub offset: E,
+    pub omega: E,
     pub domain_length: usize,
-    pub field: Field,
+    pub field: E::Field,
     pub expansion_factor: usize,
     pub num_colinearity_tests: usize,
+    pub query_strategy: QueryStrategy,
+    pub challenge_sampling: ChallengeSampling,
+    pub max_degree: Option<usize>,
+    pub hasher: Hasher,
 }
 
-impl FRI {
+impl<E: FiniteFieldElement> FRI<E> {
     pub fn new(
-        offset: FieldElement,
-        omega: FieldElement,
+        offset: E,
+        omega: E,
         initial_domain_length: usize,
         expansion_factor: usize,
         num_colinearity_tests: usize,
@@ -29,10 +81,173 @@ impl FRI {
             offset,
             omega,
             domain_length: initial_domain_length,
-            field: omega.field,
+            field: omega.field(),
+            expansion_factor,
+            num_colinearity_tests,
+            query_strategy: QueryStrategy::default(),
+            challenge_sampling: ChallengeSampling::default(),
+            max_degree: None,
+            hasher: Hasher::default(),
+        }
+    }
+
+    pub fn try_new(
+        offset: E,
+        omega: E,
+        initial_domain_length: usize,
+        expansion_factor: usize,
+        num_colinearity_tests: usize,
+    ) -> Result<Self> {
+        if offset.field() != omega.field() {
+            return Err(Error::FieldMismatch);
+        }
+        if initial_domain_length < 2 || !initial_domain_length.is_power_of_two() {
+            return Err(Error::InvalidParameters(format!(
+                "domain length {} is not a power of two",
+                initial_domain_length
+            )));
+        }
+        if !expansion_factor.is_power_of_two() || expansion_factor >= initial_domain_length {
+            return Err(Error::InvalidParameters(format!(
+                "expansion factor {} is not a power of two below the domain length",
+                expansion_factor
+            )));
+        }
+        if num_colinearity_tests == 0 {
+            return Err(Error::InvalidParameters(
+                "at least one colinearity test is required".to_string(),
+            ));
+        }
+        if offset.is_zero() {
+            return Err(Error::InvalidParameters("offset is zero".to_string()));
+        }
+        let one = omega.field().one();
+        if omega.pow(initial_domain_length.into()) != one
+            || omega.pow((initial_domain_length / 2).into()) == one
+        {
+            return Err(Error::InvalidParameters(format!(
+                "omega does not have order {}",
+                initial_domain_length
+            )));
+        }
+        let fri = Self::new(
+            offset,
+            omega,
+            initial_domain_length,
+            expansion_factor,
+            num_colinearity_tests,
+        );
+        fri.check_rounds()?;
+        Ok(fri)
+    }
+
+    // `FRI::new` does not validate, so proving and verifying check this
+    // themselves before indexing into the rounds.
+    fn check_rounds(&self) -> Result<()> {
+        if self.num_rounds() < 2 {
+            return Err(Error::InvalidParameters(format!(
+                "{} colinearity tests leave fewer than two folding rounds",
+                self.num_colinearity_tests
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn for_domain<F: FiniteField<Element = E>>(
+        field: F,
+        domain_length: usize,
+        expansion_factor: usize,
+        num_colinearity_tests: usize,
+    ) -> Result<Self> {
+        let offset = field.try_generator().ok_or_else(|| {
+            Error::InvalidParameters("cannot find a two-adic generator".to_string())
+        })?;
+        let omega = field
+            .nth_root((domain_length as u64).into())
+            .ok_or(Error::BadDomainSize(domain_length))?;
+        Self::try_new(
+            offset,
+            omega,
+            domain_length,
+            expansion_factor,
+            num_colinearity_tests,
+        )
+    }
+
+    pub fn for_degree<F: FiniteField<Element = E>>(
+        field: F,
+        max_degree: usize,
+        expansion_factor: usize,
+        num_colinearity_tests: usize,
+    ) -> Result<Self> {
+        let domain_length = FRI::domain_length_for_degree(max_degree, expansion_factor)
+            .ok_or_else(|| Error::InvalidParameters("domain length overflows usize".to_string()))?;
+        Self::for_domain(
+            field,
+            domain_length,
             expansion_factor,
             num_colinearity_tests,
+        )?
+        .with_max_degree(max_degree)
+    }
+
+    pub fn with_query_strategy(mut self, query_strategy: QueryStrategy) -> Self {
+        self.query_strategy = query_strategy;
+        self
+    }
+
+    pub fn with_challenge_sampling(mut self, challenge_sampling: ChallengeSampling) -> Self {
+        self.challenge_sampling = challenge_sampling;
+        self
+    }
+
+    pub fn with_hasher(mut self, hasher: impl Into<Hasher>) -> Self {
+        self.hasher = hasher.into();
+        self
+    }
+
+    pub fn with_max_degree(mut self, max_degree: usize) -> Result<Self> {
+        let bound = self.domain_length / self.expansion_factor;
+        if max_degree >= bound {
+            return Err(Error::InvalidParameters(format!(
+                "max degree {} does not fit below {}",
+                max_degree, bound
+            )));
         }
+        self.max_degree = Some(max_degree);
+        Ok(self)
+    }
+
+    fn prover_challenge(&self, channel: &mut ProverChannel<Vec<E>>) -> E {
+        let num_bytes = self
+            .challenge_sampling
+            .num_bytes(channel.hasher().digest_length);
+        self.challenge_sampling
+            .sample(&self.field, &channel.fiat_shamir(num_bytes))
+    }
+
+    fn verifier_challenge(&self, channel: &mut VerifierChannel<Vec<E>>) -> E {
+        let num_bytes = self
+            .challenge_sampling
+            .num_bytes(channel.hasher().digest_length);
+        self.challenge_sampling
+            .sample(&self.field, &channel.fiat_shamir(num_bytes))
+    }
+
+    fn degree_shift(&self) -> Option<usize> {
+        let bound = self.domain_length / self.expansion_factor;
+        self.max_degree
+            .map(|max_degree| bound - 1 - max_degree)
+            .filter(|shift| *shift > 0)
+    }
+
+    fn degree_correction(&self, beta: &E, shift: usize, points: &[E]) -> Vec<E> {
+        let one = self.field.one();
+        self.field
+            .batch_pow(points, shift.into())
+            .iter()
+            .map(|power| one + (*beta * *power))
+            .collect()
     }
 
     pub fn num_rounds(&self) -> usize {
@@ -50,295 +265,685 @@ impl FRI {
         num_rounds
     }
 
-    pub fn eval_domain(&self) -> Vec<FieldElement> {
-        (0..self.domain_length)
-            .map(|i| &self.offset * &(&self.omega ^ i.into()))
-            .collect()
+    /// Every parameter is written as a one-byte tag followed by a fixed-width
+    /// value, so no two configurations share an encoding. Optional values
+    /// carry a presence byte and are zero-filled when absent.
+    pub fn parameters_digest(&self, hash: impl Into<Hasher>) -> Vec<u8> {
+        let hash: Hasher = hash.into();
+        let mut bytes = PROTOCOL_VERSION.to_be_bytes().to_vec();
+        let generator = self.field.try_generator().map(|g| g.to_u256());
+        for (tag, value) in [
+            (0x01, Some(self.field.modulus())),
+            (0x02, generator),
+            (0x03, Some(self.offset.to_u256())),
+            (0x04, Some(self.omega.to_u256())),
+        ] {
+            let mut word = [0u8; 32];
+            if let Some(value) = value {
+                value.to_big_endian(&mut word);
+            }
+            bytes.push(tag);
+            bytes.push(value.is_some() as u8);
+            bytes.extend(word);
+        }
+        for (tag, value) in [
+            (0x05, Some(self.domain_length)),
+            (0x06, Some(self.expansion_factor)),
+            (0x07, Some(self.num_colinearity_tests)),
+            (0x08, self.max_degree),
+            (0x09, Some(hash.digest_length)),
+        ] {
+            bytes.push(tag);
+            bytes.push(value.is_some() as u8);
+            bytes.extend((value.unwrap_or(0) as u64).to_be_bytes());
+        }
+        for (tag, value) in [
+            (0x0a, self.query_strategy as u8),
+            (0x0b, self.challenge_sampling as u8),
+            (0x0c, hash.kind as u8),
+        ] {
+            bytes.push(tag);
+            bytes.push(value);
+        }
+        hash.digest(&bytes)
+    }
+
+    pub fn eval_coset(&self) -> Coset<E> {
+        Coset::new(self.offset, self.omega, self.domain_length)
+    }
+
+    pub fn eval_domain(&self) -> Vec<E> {
+        self.eval_coset().elements()
     }
 
     pub fn commit(
         &self,
-        mut codeword: Vec<FieldElement>,
-        proof_stream: &mut ProofStream<Vec<FieldElement>>,
-    ) -> Vec<Vec<FieldElement>> {
-        let one = self.field.one();
-        let two = FieldElement::new(*TWO, self.field);
-        let mut omega = self.omega;
-        let mut offset = self.offset;
+        codeword: Vec<E>,
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<Vec<Vec<E>>> {
+        self.commit_(codeword, channel, &mut (), &mut ())
+    }
+
+    fn commit_(
+        &self,
+        mut codeword: Vec<E>,
+        channel: &mut ProverChannel<Vec<E>>,
+        metrics: &mut impl Recorder,
+        explanation: &mut impl Explainer,
+    ) -> Result<Vec<Vec<E>>> {
+        self.check_rounds()?;
+        let _span = span!("fri_commit", rounds = self.num_rounds());
+        let mut inverses = DomainInverses::new(self.eval_coset())?;
         let mut codewords = vec![];
+        metrics.allocate("codewords", codeword.len() * size_of::<E>());
 
         for r in 0..self.num_rounds() {
-            let root = Merkle::commit(&codeword);
-            proof_stream.push_hash(root);
+            let _round = span!("fri_round", round = r, length = codeword.len());
+            let tree_bytes =
+                Merkle::tree_bytes_sized(codeword.len(), channel.hasher().digest_length);
+            metrics.allocate("merkle_layers", tree_bytes);
+            let root = metrics.time("merkle_commit", || {
+                Merkle::commit_with(channel.hasher(), &codeword)
+            });
+            metrics.release(tree_bytes);
+            explanation.explain(|| Step::Commit {
+                round: r,
+                length: codeword.len(),
+                root: hex::encode(&root),
+            });
+            channel.push_hash(root);
 
             if r == self.num_rounds() - 1 {
                 break;
             }
 
-            let alpha = self.field.sample(&proof_stream.prover_fiat_shamir(32));
+            let alpha = self.prover_challenge(channel);
+            explanation.explain(|| Step::Challenge {
+                round: r,
+                alpha: alpha.to_u256().to_string(),
+            });
             codewords.push(codeword.clone());
-            codeword = (0..codeword.len() / 2)
-                .map(|i| {
-                    &(&(&(&one + &(&alpha / &(&offset * &(&omega ^ i.into())))) * &codeword[i])
-                        + &(&(&one - &(&alpha / &(&offset * &(&omega ^ i.into()))))
-                            * &codeword[codeword.len() / 2 + i]))
-                        * &two.inv()
-                })
-                .collect();
+            codeword = metrics.time("fri_fold", || inverses.fold_codeword(&codeword, &alpha))?;
+            metrics.allocate("codewords", codeword.len() * size_of::<E>());
+            explanation.explain(|| Step::Fold {
+                round: r,
+                length: codeword.len(),
+            });
 
-            omega = &omega ^ two.value;
-            offset = &offset ^ two.value;
+            inverses = inverses.squared();
         }
 
-        proof_stream.push_obj(codeword.clone());
+        explanation.explain(|| Step::LastCodeword {
+            length: codeword.len(),
+            max_degree: codeword.len() / self.expansion_factor - 1,
+        });
+        channel.push_obj(codeword.clone());
         codewords.push(codeword);
-        codewords
+        Ok(codewords)
     }
 
     pub fn query(
         &self,
-        current_codeword: &Vec<FieldElement>,
-        next_codeword: &Vec<FieldElement>,
-        c_indices: &Vec<usize>,
-        proof_stream: &mut ProofStream<Vec<FieldElement>>,
-    ) -> Vec<usize> {
-        let mut a_indices = c_indices.clone();
+        current_codeword: &[E],
+        next_codeword: &[E],
+        c_indices: &[usize],
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<Vec<usize>> {
+        let mut a_indices = c_indices.to_vec();
         let b_indices: Vec<usize> = c_indices
             .iter()
             .map(|i| i + current_codeword.len() / 2)
             .collect();
 
+        let derived = self.query_strategy == QueryStrategy::Derived;
         for s in 0..self.num_colinearity_tests {
-            let leafs = vec![
+            let mut leafs = vec![
                 current_codeword[a_indices[s]],
                 current_codeword[b_indices[s]],
-                next_codeword[c_indices[s]],
             ];
-            proof_stream.push_leafs(leafs);
+            if !derived {
+                leafs.push(next_codeword[c_indices[s]]);
+            }
+            channel.push_leafs(leafs);
         }
 
         for s in 0..self.num_colinearity_tests {
-            proof_stream.push_path(Merkle::open(a_indices[s], current_codeword));
-            proof_stream.push_path(Merkle::open(b_indices[s], current_codeword));
-            proof_stream.push_path(Merkle::open(c_indices[s], next_codeword));
+            channel.push_path(Merkle::open_with(
+                channel.hasher(),
+                a_indices[s],
+                current_codeword,
+            )?);
+            channel.push_path(Merkle::open_with(
+                channel.hasher(),
+                b_indices[s],
+                current_codeword,
+            )?);
+            if !derived {
+                channel.push_path(Merkle::open_with(
+                    channel.hasher(),
+                    c_indices[s],
+                    next_codeword,
+                )?);
+            }
         }
 
         a_indices.extend(b_indices);
-        a_indices
+        Ok(a_indices)
     }
 
-    pub fn sample_index(byte_array: &Vec<u8>, size: usize) -> usize {
-        let mut acc = 0;
-        byte_array.iter().for_each(|b| {
-            acc = acc << 8 ^ (*b as usize);
-        });
-        acc % size
-    }
-
-    pub fn sample_indices(
-        seed: &Vec<u8>,
-        size: usize,
-        reduced_size: usize,
-        number: usize,
-    ) -> Vec<usize> {
-        assert!(number <= reduced_size);
-        let mut indices = vec![];
-        let mut reduced_indices = vec![];
-        let mut counter = 0usize;
-
-        let mut bytes = seed.clone();
-        counter.to_be_bytes().iter().for_each(|b| {
-            bytes.push(*b);
-        });
+    // Opens the uncorrected codeword at the first round's query pairs.
+    fn open_original(
+        &self,
+        original: &[E],
+        opened: &[usize],
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<()> {
+        let (a_indices, b_indices) = opened.split_at(self.num_colinearity_tests);
+        for (a, b) in a_indices.iter().zip(b_indices) {
+            channel.push_leafs(vec![original[*a], original[*b]]);
+        }
+        for (a, b) in a_indices.iter().zip(b_indices) {
+            channel.push_path(Merkle::open_with(channel.hasher(), *a, original)?);
+            channel.push_path(Merkle::open_with(channel.hasher(), *b, original)?);
+        }
+        Ok(())
+    }
+
+    pub fn prove(&self, codeword: &[E], channel: &mut ProverChannel<Vec<E>>) -> Result<Vec<usize>> {
+        self.prove_(codeword, channel, &mut (), &mut ())
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn prove_with_metrics(
+        &self,
+        codeword: &[E],
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<(Vec<usize>, ProverMetrics)> {
+        let mut metrics = ProverMetrics::new();
+        let indices = self.prove_(codeword, channel, &mut metrics, &mut ())?;
+        Ok((indices, metrics))
+    }
+
+    pub fn prove_explained(
+        &self,
+        codeword: &[E],
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<(Vec<usize>, Explanation)> {
+        let mut explanation = Explanation::new();
+        let indices = self.prove_(codeword, channel, &mut (), &mut explanation)?;
+        Ok((indices, explanation))
+    }
 
-        while indices.len() < number {
-            let index = FRI::sample_index(&merkle::hash(&bytes), size);
-            let reduced_index = index % reduced_size;
+    /// Commits a trace whose length need not be a power of two. The trace is
+    /// interpolated over the first points of the subgroup of order
+    /// `domain_length / expansion_factor`, and the interpolant's coefficients
+    /// are zero-padded and evaluated over the FRI domain.
+    pub fn prove_trace(
+        &self,
+        trace: &[E],
+        channel: &mut ProverChannel<Vec<E>>,
+    ) -> Result<Vec<usize>> {
+        self.prove(&self.extend_trace(trace)?, channel)
+    }
 
-            counter += 1;
-            let mut l = bytes.len();
-            counter.to_le_bytes().iter().for_each(|b| {
-                bytes[l - 1] = *b;
-                l -= 1;
+    pub fn extend_trace(&self, trace: &[E]) -> Result<Vec<E>> {
+        let bound = self
+            .max_degree
+            .map_or(self.domain_length / self.expansion_factor, |degree| {
+                degree + 1
+            });
+        if trace.is_empty() || trace.len() > bound {
+            return Err(Error::LengthMismatch {
+                expected: bound,
+                actual: trace.len(),
             });
+        }
+        let root = self.omega.pow((self.expansion_factor as u64).into());
+        let points = self.field.powers(&root, trace.len());
+        let polynomial = Polynomial::interpolate_domain(&points, trace)?;
+        ntt::fast_coset_evaluate(&polynomial, &self.offset, &self.omega, self.domain_length)
+    }
 
-            if !reduced_indices.contains(&reduced_index) {
-                indices.push(index);
-                reduced_indices.push(reduced_index);
+    fn prove_(
+        &self,
+        codeword: &[E],
+        channel: &mut ProverChannel<Vec<E>>,
+        metrics: &mut impl Recorder,
+        explanation: &mut impl Explainer,
+    ) -> Result<Vec<usize>> {
+        if self.domain_length != codeword.len() {
+            return Err(Error::LengthMismatch {
+                expected: self.domain_length,
+                actual: codeword.len(),
+            });
+        }
+        self.check_rounds()?;
+        let _span = span!("fri_prove", domain_length = self.domain_length);
+        explanation.explain(|| Step::Domain {
+            length: self.domain_length,
+            offset: self.offset.to_u256().to_string(),
+            omega: self.omega.to_u256().to_string(),
+            rounds: self.num_rounds(),
+        });
+        if channel.hasher() != self.hasher {
+            return Err(Error::InvalidParameters(format!(
+                "channel hashes with {:?}, expected {:?}",
+                channel.hasher(),
+                self.hasher
+            )));
+        }
+        channel.push_hash(self.parameters_digest(self.hasher));
+        let mut codeword = codeword.to_vec();
+        let mut original = None;
+        if let Some(shift) = self.degree_shift() {
+            // beta must depend on the committed codeword, or the prover could
+            // cancel the correction factor before committing.
+            channel.push_hash(Merkle::commit_with(channel.hasher(), &codeword));
+            let beta = self.prover_challenge(channel);
+            original = Some(codeword.clone());
+            let factors = self.degree_correction(&beta, shift, &self.eval_domain());
+            for (value, factor) in codeword.iter_mut().zip(&factors) {
+                *value *= *factor;
             }
+            explanation.explain(|| Step::DegreeCorrection {
+                max_degree: self.max_degree.unwrap(),
+                shift,
+                beta: beta.to_u256().to_string(),
+            });
         }
-        indices
+        let codewords = self.commit_(codeword, channel, metrics, explanation)?;
+        let mut indices = match self.query_strategy {
+            QueryStrategy::Folded | QueryStrategy::Derived => {
+                let sampled = sampling::sample_indices(
+                    channel.hash(),
+                    &channel.challenge(),
+                    codewords[1].len(),
+                    codewords.last().unwrap().len(),
+                    self.num_colinearity_tests,
+                )?;
+                explanation.explain(|| Step::SampleIndices {
+                    indices: sampled.clone(),
+                });
+                sampled
+            }
+            QueryStrategy::PerRound => vec![],
+        };
+        let mut top_level_indices = indices.clone();
+
+        for (i, codeword) in codewords.iter().enumerate().take(codewords.len() - 1) {
+            let half = codeword.len() / 2;
+            indices = match self.query_strategy {
+                QueryStrategy::Folded | QueryStrategy::Derived => {
+                    indices.iter().map(|index| index % half).collect()
+                }
+                QueryStrategy::PerRound => {
+                    let fresh = sampling::sample_indices(
+                        channel.hash(),
+                        &channel.challenge(),
+                        half,
+                        half,
+                        self.num_colinearity_tests,
+                    )?;
+                    explanation.explain(|| Step::SampleIndices {
+                        indices: fresh.clone(),
+                    });
+                    fresh
+                }
+            };
+            if i == 0 {
+                top_level_indices = indices.clone();
+            }
+            let opened = metrics.time("fri_query", || {
+                self.query(codeword, &codewords[i + 1], &indices, channel)
+            })?;
+            if let (0, Some(original)) = (i, &original) {
+                self.open_original(original, &opened, channel)?;
+            }
+            explanation.explain(|| Step::Query {
+                round: i,
+                indices: indices.clone(),
+            });
+        }
+
+        let codeword_bytes: usize = codewords.iter().map(Vec::len).sum();
+        drop(codewords);
+        metrics.release(codeword_bytes * size_of::<E>());
+        Ok(top_level_indices)
     }
 
-    pub fn prove(
+    // Checks the openings of the uncorrected codeword against its root and
+    // against the corrected values, returning the uncorrected values.
+    #[allow(clippy::type_complexity)]
+    fn verify_original(
         &self,
-        codeword: &Vec<FieldElement>,
-        proof_stream: &mut ProofStream<Vec<FieldElement>>,
-    ) -> Vec<usize> {
-        assert!(self.domain_length == codeword.len());
-        let codewords = self.commit(codeword.clone(), proof_stream);
-        let top_level_indices = FRI::sample_indices(
-            &proof_stream.prover_fiat_shamir(32),
-            codewords[1].len(),
-            codewords.last().unwrap().len(),
-            self.num_colinearity_tests,
-        );
-        let mut indices = top_level_indices.clone();
+        channel: &mut VerifierChannel<Vec<E>>,
+        (root, beta, shift): (&[u8], &E, usize),
+        (a_indices, b_indices): (&[usize], &[usize]),
+        points: &[[E; 2]],
+        (aa, bb): (&[E], &[E]),
+    ) -> Result<Option<(Vec<E>, Vec<E>)>> {
+        let mut original_a = vec![];
+        let mut original_b = vec![];
+        for s in 0..self.num_colinearity_tests {
+            let (fa, fb) = match channel.pull()? {
+                Object::LEAF(leafs) if leafs.len() == 2 => (leafs[0], leafs[1]),
+                _ => return Err(Error::MalformedProof("expected leafs")),
+            };
+            let factors = self.degree_correction(beta, shift, &points[s]);
+            if fa * factors[0] != aa[s] || fb * factors[1] != bb[s] {
+                debug!(
+                    test = s,
+                    "degree correction does not match the committed codeword"
+                );
+                return Ok(None);
+            }
+            original_a.push(fa);
+            original_b.push(fb);
+        }
+        for s in 0..self.num_colinearity_tests {
+            for (index, value) in [(a_indices[s], original_a[s]), (b_indices[s], original_b[s])] {
+                let path = match channel.pull()? {
+                    Object::PATH(p) => p,
+                    _ => return Err(Error::MalformedProof("expected path")),
+                };
+                if !Merkle::verify_with(channel.hasher(), root, index, &path, &value) {
+                    debug!(index, "auth path fail for the uncorrected codeword");
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(Some((original_a, original_b)))
+    }
 
-        codewords.iter().enumerate().for_each(|(i, codeword)| {
-            if i < codewords.len() - 1 {
-                indices = indices
-                    .iter()
-                    .map(|index| index % (codeword.len() / 2))
-                    .collect();
-                self.query(codeword, &codewords[i + 1], &indices, proof_stream);
+    // Pulls everything committed after the parameter digest and before the
+    // last codeword, drawing the same challenges the prover drew.
+    pub(crate) fn pull_commitments(
+        &self,
+        channel: &mut VerifierChannel<Vec<E>>,
+    ) -> Result<Commitments<E>> {
+        let correction = match self.degree_shift() {
+            Some(shift) => {
+                let root = match channel.pull()? {
+                    Object::HASH(root) => root,
+                    _ => return Err(Error::MalformedProof("expected hash")),
+                };
+                Some((root, self.verifier_challenge(channel), shift))
             }
-        });
+            None => None,
+        };
 
-        top_level_indices
+        let mut roots = vec![];
+        let mut alphas = vec![];
+        for _ in 0..self.num_rounds() {
+            match channel.pull()? {
+                Object::HASH(root) => roots.push(root),
+                _ => return Err(Error::MalformedProof("expected hash")),
+            }
+            alphas.push(self.verifier_challenge(channel));
+        }
+        Ok(Commitments {
+            correction,
+            roots,
+            alphas,
+        })
     }
 
     pub fn verify(
         &self,
-        proof_stream: &mut ProofStream<Vec<FieldElement>>,
-        mut polynomial_values: Vec<(usize, FieldElement)>,
-    ) -> bool {
-        let two = FieldElement::new(*TWO, self.field);
+        channel: &mut VerifierChannel<Vec<E>>,
+        mut polynomial_values: Vec<(usize, E)>,
+    ) -> Result<bool> {
+        self.check_rounds()?;
+        let _span = span!("fri_verify", domain_length = self.domain_length);
         let mut omega = self.omega;
         let mut offset = self.offset;
 
-        let mut roots = vec![];
-        let mut alphas = vec![];
-        for _ in 0..self.num_rounds() {
-            if let Object::HASH(root) = proof_stream.pull() {
-                roots.push(root);
-            } else {
-                panic!("Expected hash");
+        if channel.hasher() != self.hasher {
+            debug!("proof was hashed with a different hash function");
+            return Ok(false);
+        }
+        match channel.pull()? {
+            Object::HASH(digest) if digest == self.parameters_digest(self.hasher) => {}
+            Object::HASH(_) => {
+                debug!("proof was generated for different parameters");
+                return Ok(false);
             }
-            alphas.push(self.field.sample(&proof_stream.verifier_fiat_shamir(32)));
+            _ => return Err(Error::MalformedProof("expected parameter digest")),
         }
+        let Commitments {
+            correction,
+            roots,
+            alphas,
+        } = self.pull_commitments(channel)?;
 
-        let last_codeword = match proof_stream.pull() {
+        let last_codeword = match channel.pull()? {
             Object::OBJ(codeword) => codeword,
-            _ => panic!("Expected object"),
+            _ => return Err(Error::MalformedProof("expected object")),
         };
+        if last_codeword.len() != self.domain_length >> (self.num_rounds() - 1) {
+            return Err(Error::MalformedProof("last codeword has the wrong length"));
+        }
 
-        if *roots.last().unwrap() != Merkle::commit(&last_codeword) {
-            println!("Malformed last_codeword");
-            return false;
+        let last_root = roots.last().ok_or(Error::MalformedProof("expected hash"))?;
+        if *last_root != Merkle::try_commit_with(channel.hasher(), &last_codeword)? {
+            debug!("malformed last codeword");
+            return Ok(false);
         }
 
-        let degree: i32 = (last_codeword.len() / self.expansion_factor - 1)
-            .try_into()
-            .unwrap();
-        let mut last_omega = omega;
-        let mut last_offset = offset;
+        let degree: i32 = (last_codeword.len() / self.expansion_factor)
+            .checked_sub(1)
+            .and_then(|degree| degree.try_into().ok())
+            .ok_or(Error::MalformedProof("last codeword too short"))?;
+        let mut last_coset = self.eval_coset();
         for _ in 0..self.num_rounds() - 1 {
-            last_omega = &last_omega ^ two.value;
-            last_offset = &last_offset ^ two.value;
+            last_coset = last_coset.squared();
+        }
+        last_coset.size = last_codeword.len();
+        if !last_coset.is_closed() {
+            return Err(Error::BadDomainSize(last_codeword.len()));
         }
-        assert!(last_omega.inv() == &last_omega ^ (last_codeword.len() - 1).into());
 
-        let last_domain: Vec<FieldElement> = (0..last_codeword.len())
-            .map(|i| &last_offset * &(&last_omega ^ i.into()))
-            .collect();
-        let poly = Polynomial::interpolate_domain(&last_domain, &last_codeword);
-        assert!(poly.evaluate_domain(&last_domain) == last_codeword);
+        // The last coset is closed, so an inverse NTT recovers the coefficients
+        // directly; odd toy domains fall back to Lagrange interpolation.
+        let poly = if last_codeword.len().is_power_of_two() {
+            Polynomial::interpolate_coset(
+                &last_coset.offset,
+                &last_coset.generator,
+                &last_codeword,
+            )?
+        } else {
+            Polynomial::interpolate_domain(&last_coset.elements(), &last_codeword)?
+        };
 
         if poly.degree() > degree {
-            println!("last codeword does not correspond to polynomial of low enough degree");
-            println!("observed degree: {}", poly.degree());
-            println!("but should be: {}", degree);
-            return false;
+            debug!(
+                observed = poly.degree(),
+                expected = degree,
+                "last codeword does not correspond to polynomial of low enough degree"
+            );
+            return Ok(false);
         }
 
-        let top_level_indices = FRI::sample_indices(
-            &proof_stream.verifier_fiat_shamir(32),
-            self.domain_length >> 1,
-            self.domain_length >> (self.num_rounds() - 1),
-            self.num_colinearity_tests,
-        );
+        let top_level_indices = match self.query_strategy {
+            QueryStrategy::Folded | QueryStrategy::Derived => sampling::sample_indices(
+                channel.hash(),
+                &channel.challenge(),
+                self.domain_length >> 1,
+                self.domain_length >> (self.num_rounds() - 1),
+                self.num_colinearity_tests,
+            )?,
+            QueryStrategy::PerRound => vec![],
+        };
 
+        let derived = self.query_strategy == QueryStrategy::Derived;
+        let mut pending: Vec<(usize, E)> = vec![];
         for r in 0..self.num_rounds() - 1 {
-            let c_indices: Vec<usize> = top_level_indices
-                .iter()
-                .map(|index| *index % (self.domain_length >> (r + 1)))
-                .collect();
+            let _round = span!("fri_round", round = r);
+            let half = self.domain_length >> (r + 1);
+            let c_indices: Vec<usize> = match self.query_strategy {
+                QueryStrategy::Folded | QueryStrategy::Derived => top_level_indices
+                    .iter()
+                    .map(|index| *index % half)
+                    .collect(),
+                QueryStrategy::PerRound => sampling::sample_indices(
+                    channel.hash(),
+                    &channel.challenge(),
+                    half,
+                    half,
+                    self.num_colinearity_tests,
+                )?,
+            };
             let a_indices = c_indices.clone();
-            let b_indices: Vec<usize> = a_indices
-                .iter()
-                .map(|index| *index + (self.domain_length >> (r + 1)))
-                .collect();
+            let b_indices: Vec<usize> = a_indices.iter().map(|index| *index + half).collect();
+
+            let coset = Coset::new(offset, omega, 2 * half);
+            let inverses = DomainInverses::sparse(coset, &a_indices)?;
 
             let mut aa = vec![];
             let mut bb = vec![];
             let mut cc = vec![];
-            for s in 0..self.num_colinearity_tests {
-                let (ay, by, cy) = match proof_stream.pull() {
-                    Object::LEAF(leafs) => (leafs[0], leafs[1], leafs[2]),
-                    _ => panic!("Expected a leaf"),
+            let mut points = vec![];
+            let mut triples = vec![];
+            for (s, &index) in a_indices.iter().enumerate() {
+                let ax = inverses.point(index).unwrap();
+                let bx = -ax;
+                let cx = alphas[r];
+
+                let (ay, by, cy) = match channel.pull()? {
+                    Object::LEAF(leafs) if !derived && leafs.len() == 3 => {
+                        (leafs[0], leafs[1], leafs[2])
+                    }
+                    Object::LEAF(leafs) if derived && leafs.len() == 2 => {
+                        let cy = inverses.fold(index, &leafs[0], &leafs[1], &cx).unwrap();
+                        (leafs[0], leafs[1], cy)
+                    }
+                    _ => return Err(Error::MalformedProof("expected leafs")),
                 };
 
+                if let Some((next, value)) = pending.get(s) {
+                    let opened = if *next < half { ay } else { by };
+                    if opened != *value {
+                        debug!(test = s, "derived value does not match the next round");
+                        return Ok(false);
+                    }
+                }
+
                 aa.push(ay);
                 bb.push(by);
                 cc.push(cy);
 
                 if r == 0 {
-                    polynomial_values.push((a_indices[s], ay));
-                    polynomial_values.push((b_indices[s], by));
+                    points.push([ax, bx]);
                 }
 
-                let ax = &offset * &(&omega ^ a_indices[s].into());
-                let bx = &offset * &(&omega ^ b_indices[s].into());
-                let cx = alphas[r];
-
-                if !Polynomial::test_colinearity(&vec![(ax, ay), (bx, by), (cx, cy)]) {
-                    println!("Faild colinearity check");
-                    return false;
+                if !derived {
+                    triples.push([(ax, ay), (bx, by), (cx, cy)]);
                 }
             }
+            if !Polynomial::test_colinearity_batch(&triples) {
+                debug!(round = r, "failed colinearity check");
+                return Ok(false);
+            }
 
             for i in 0..self.num_colinearity_tests {
-                let path = match proof_stream.pull() {
+                let path = match channel.pull()? {
                     Object::PATH(p) => p,
-                    _ => panic!("Expected path"),
+                    _ => return Err(Error::MalformedProof("expected path")),
                 };
-                if !Merkle::verify(&roots[r], a_indices[i], &path, &aa[i]) {
-                    println!("Auth path fail for aa");
-                    return false;
+                if !Merkle::verify_with(channel.hasher(), &roots[r], a_indices[i], &path, &aa[i]) {
+                    debug!(index = a_indices[i], "auth path fail for aa");
+                    return Ok(false);
                 }
 
-                let path = match proof_stream.pull() {
+                let path = match channel.pull()? {
                     Object::PATH(p) => p,
-                    _ => panic!("Expected path"),
+                    _ => return Err(Error::MalformedProof("expected path")),
                 };
-                if !Merkle::verify(&roots[r], b_indices[i], &path, &bb[i]) {
-                    println!("Auth path fail for bb");
-                    return false;
+                if !Merkle::verify_with(channel.hasher(), &roots[r], b_indices[i], &path, &bb[i]) {
+                    debug!(index = b_indices[i], "auth path fail for bb");
+                    return Ok(false);
                 }
 
-                let path = match proof_stream.pull() {
+                if derived {
+                    continue;
+                }
+                let path = match channel.pull()? {
                     Object::PATH(p) => p,
-                    _ => panic!("Expected path"),
+                    _ => return Err(Error::MalformedProof("expected path")),
+                };
+                if !Merkle::verify_with(
+                    channel.hasher(),
+                    &roots[r + 1],
+                    c_indices[i],
+                    &path,
+                    &cc[i],
+                ) {
+                    debug!(index = c_indices[i], "auth path fail for cc");
+                    return Ok(false);
+                }
+            }
+
+            if r == 0 {
+                let opened = match &correction {
+                    Some((root, beta, shift)) => {
+                        let values = self.verify_original(
+                            channel,
+                            (root, beta, *shift),
+                            (&a_indices, &b_indices),
+                            &points,
+                            (&aa, &bb),
+                        )?;
+                        match values {
+                            Some(values) => values,
+                            None => return Ok(false),
+                        }
+                    }
+                    None => (aa.clone(), bb.clone()),
                 };
-                if !Merkle::verify(&roots[r + 1], c_indices[i], &path, &cc[i]) {
-                    println!("Auth path fail for cc");
-                    return false;
+                for s in 0..self.num_colinearity_tests {
+                    polynomial_values.push((a_indices[s], opened.0[s]));
+                    polynomial_values.push((b_indices[s], opened.1[s]));
                 }
             }
 
-            omega = &omega ^ two.value;
-            offset = &offset ^ two.value;
+            if derived {
+                pending = c_indices.into_iter().zip(cc).collect();
+            }
+            debug!("round passed");
+            omega = omega * omega;
+            offset = offset * offset;
         }
 
-        true
+        for (index, value) in pending {
+            if last_codeword.get(index) != Some(&value) {
+                debug!(index, "derived value does not match the last codeword");
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
     }
 }
 
-#[cfg(test)]
+// Outside the generic block so that `FRI::domain_length_for_degree` resolves
+// without naming an element type.
+impl FRI {
+    pub fn domain_length_for_degree(max_degree: usize, expansion_factor: usize) -> Option<usize> {
+        max_degree
+            .checked_add(1)?
+            .checked_next_power_of_two()?
+            .checked_mul(expansion_factor)
+    }
+}
 
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        field::Field,
+        hash::{HashKind, Hasher},
+        proofstream::ProofStream,
+    };
 
     #[test]
     fn fri_test() {
@@ -350,24 +955,23 @@ mod tests {
         let fri = FRI::new(f.one(), f.generator(), 16, 2, 1);
         assert_eq!(fri.num_rounds(), 2);
 
+        assert_eq!(FRI::domain_length_for_degree(0, 4), Some(4));
+        assert_eq!(FRI::domain_length_for_degree(7, 4), Some(32));
+        assert_eq!(FRI::domain_length_for_degree(8, 4), Some(64));
+        assert_eq!(FRI::domain_length_for_degree(usize::MAX, 4), None);
+
         let fri = FRI::new(FieldElement::new(*TWO, f), f.generator(), 3, 2, 1);
         let two = FieldElement::new(*TWO, f);
         assert_eq!(
             fri.eval_domain(),
-            vec![two, &two * &f.generator(), &two * &(&f.generator() ^ *TWO)]
+            vec![two, two * f.generator(), two * (f.generator() ^ *TWO)]
         );
     }
 
     #[test]
     fn verification_test() {
         let f = Field::new(17.into());
-        let fri = FRI::new(
-            FieldElement::new(13.into(), f),
-            FieldElement::new(7.into(), f),
-            16,
-            7,
-            1,
-        );
+        let fri = FRI::new(f.element(13), f.element(7), 16, 7, 1);
         let codeword = vec![
             f.one(),
             f.zero(),
@@ -386,39 +990,47 @@ mod tests {
             f.one(),
             f.zero(),
         ];
-        let mut ps = ProofStream::new();
-        fri.prove(&codeword, &mut ps);
-        assert!(!fri.verify(&mut ps, vec![]));
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(!fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
 
         let f = Field::new(7.into());
-        let fri = FRI::new(
-            FieldElement::new(1.into(), f),
-            FieldElement::new(5.into(), f),
-            6,
-            1,
-            1,
-        );
+        let fri = FRI::new(f.element(1), f.element(5), 6, 1, 1);
 
         let p = Polynomial::new(vec![
-            FieldElement::new(3.into(), f),
-            FieldElement::new(4.into(), f),
+            f.element(3),
+            f.element(4),
             FieldElement::new(*TWO, f),
             f.one(),
         ]);
         let codeword = p.evaluate_domain(&fri.eval_domain());
-        let mut ps = ProofStream::new();
-        fri.prove(&codeword, &mut ps);
-        assert!(fri.verify(&mut ps, vec![]));
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
 
-        let f = Field::new(17.into());
-        let fri = FRI::new(
-            FieldElement::new(1.into(), f),
-            FieldElement::new(6.into(), f),
-            16,
-            2,
-            2,
-        );
+        let (fri, codeword, mut ps) = toy_proof();
+        assert!(fri
+            .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+            .unwrap());
+
+        assert!(matches!(
+            fri.prove(&codeword[1..], &mut ProverChannel::new()),
+            Err(Error::LengthMismatch { .. })
+        ));
+        ps.objects.truncate(ps.objects.len() - 1);
+        assert!(matches!(
+            fri.verify(&mut VerifierChannel::new(ps), vec![]),
+            Err(Error::MalformedProof(_))
+        ));
+    }
 
+    fn toy_proof() -> (FRI, Vec<FieldElement>, ProofStream<Vec<FieldElement>>) {
+        let f = Field::new(17.into());
+        let fri = FRI::new(f.element(1), f.element(6), 16, 2, 2);
         let p = Polynomial::new(vec![
             f.one(),
             f.zero(),
@@ -428,8 +1040,532 @@ mod tests {
             FieldElement::new(*TWO, f),
         ]);
         let codeword = p.evaluate_domain(&fri.eval_domain());
-        let mut ps = ProofStream::new();
-        fri.prove(&codeword, &mut ps);
-        assert!(fri.verify(&mut ps, vec![]));
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        (fri, codeword, prover.into_proof())
+    }
+
+    #[test]
+    fn explanation_test() {
+        let (fri, codeword, ps) = toy_proof();
+        let mut prover = ProverChannel::new();
+        let (indices, explanation) = fri.prove_explained(&codeword, &mut prover).unwrap();
+        assert_eq!(prover.proof().objects, ps.objects);
+        assert_eq!(indices.len(), fri.num_colinearity_tests);
+        assert!(matches!(
+            explanation.steps[0],
+            Step::Domain { length: 16, .. }
+        ));
+        assert_eq!(
+            explanation
+                .steps
+                .iter()
+                .filter(|s| matches!(s, Step::Challenge { .. }))
+                .count(),
+            fri.num_rounds() - 1
+        );
+        assert!(explanation.steps.contains(&Step::SampleIndices { indices }));
+    }
+
+    #[test]
+    fn digest_length_test() {
+        let (fri, codeword, ps) = toy_proof();
+        let f = fri.field;
+        for kind in [HashKind::Blake3, HashKind::Sha3] {
+            let hashed = FRI::new(f.element(1), f.element(6), 16, 2, 2).with_hasher(kind);
+            let mut prover = ProverChannel::with_hash(kind);
+            hashed.prove(&codeword, &mut prover).unwrap();
+            let mut verifier = VerifierChannel::deserialize(&prover.serialize()).unwrap();
+            assert!(!fri.verify(&mut verifier, vec![]).unwrap());
+            assert!(matches!(
+                fri.prove(&codeword, &mut ProverChannel::with_hash(kind)),
+                Err(Error::InvalidParameters(_))
+            ));
+        }
+
+        let short = Hasher::new(HashKind::Blake2b, 16).unwrap();
+        let truncated = FRI::new(f.element(1), f.element(6), 16, 2, 2).with_hasher(short);
+        let mut prover = ProverChannel::with_hasher(short);
+        truncated.prove(&codeword, &mut prover).unwrap();
+        assert!(prover.serialize().len() < ps.serialize().len());
+        assert!(prover.proof().iter_hashes().all(|root| root.len() == 16));
+        let mut verifier = VerifierChannel::deserialize(&prover.serialize()).unwrap();
+        assert!(truncated.verify(&mut verifier, vec![]).unwrap());
+        let mut verifier = VerifierChannel::deserialize(&prover.serialize()).unwrap();
+        assert!(!fri.verify(&mut verifier, vec![]).unwrap());
+        let mut mismatched = VerifierChannel::new(ProofStream {
+            digest_length: 32,
+            ..prover.proof().clone()
+        });
+        assert!(!truncated.verify(&mut mismatched, vec![]).unwrap());
+    }
+
+    #[test]
+    fn hash_kind_test() {
+        let (fri, codeword, ps) = toy_proof();
+        let f = fri.field;
+        for kind in [HashKind::Blake3, HashKind::Sha3, HashKind::Rescue] {
+            let hashed = FRI::new(f.element(1), f.element(6), 16, 2, 2).with_hasher(kind);
+            let mut prover = ProverChannel::with_hash(kind);
+            hashed.prove(&codeword, &mut prover).unwrap();
+            assert_ne!(prover.proof().objects[0], ps.objects[0]);
+            let serialized = prover.serialize();
+            let mut verifier = VerifierChannel::deserialize(&serialized).unwrap();
+            assert!(hashed.verify(&mut verifier, vec![]).unwrap());
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn phase_metrics_test() {
+        let (fri, codeword, ps) = toy_proof();
+        let mut prover = ProverChannel::new();
+        let (indices, metrics) = fri.prove_with_metrics(&codeword, &mut prover).unwrap();
+        assert_eq!(prover.proof().objects, ps.objects);
+        assert_eq!(indices.len(), fri.num_colinearity_tests);
+        let phases: Vec<&str> = metrics.phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            phases.iter().filter(|p| **p == "merkle_commit").count(),
+            fri.num_rounds()
+        );
+        assert_eq!(
+            phases.iter().filter(|p| **p == "fri_fold").count(),
+            fri.num_rounds() - 1
+        );
+        assert_eq!(
+            phases.iter().filter(|p| **p == "fri_query").count(),
+            fri.num_rounds() - 1
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn memory_accounting_test() {
+        let (fri, codeword, _) = toy_proof();
+        let (_, metrics) = fri
+            .prove_with_metrics(&codeword, &mut ProverChannel::new())
+            .unwrap();
+        let element_bytes = size_of::<FieldElement>();
+        assert_eq!(metrics.allocated("codewords"), (16 + 8) * element_bytes);
+        assert_eq!(metrics.live_bytes, 0);
+        assert_eq!(
+            metrics.peak_bytes,
+            (16 * element_bytes + Merkle::tree_bytes(16))
+                .max((16 + 8) * element_bytes + Merkle::tree_bytes(8))
+        );
+    }
+
+    #[test]
+    fn last_codeword_length_test() {
+        let f = Field::new(*PRIME);
+        let fri = FRI::for_domain(f, 1024, 2, 8).unwrap();
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let codeword = p.evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        let ps = prover.into_proof();
+
+        let position = ps
+            .objects
+            .iter()
+            .position(|object| matches!(object, Object::OBJ(_)))
+            .unwrap();
+        let root = ps.objects[..position]
+            .iter()
+            .rposition(|object| matches!(object, Object::HASH(_)))
+            .unwrap();
+        let resize = |f: &dyn Fn(&mut Vec<FieldElement>)| {
+            let mut tampered = ps.clone();
+            if let Object::OBJ(last) = &mut tampered.objects[position] {
+                f(last);
+                tampered.objects[root] = Object::HASH(Merkle::commit(last));
+            }
+            fri.verify(&mut VerifierChannel::new(tampered), vec![])
+        };
+        assert!(matches!(
+            resize(&|last| last.extend(last.clone())),
+            Err(Error::MalformedProof(_))
+        ));
+        assert!(matches!(
+            resize(&|last| last.truncate(48)),
+            Err(Error::MalformedProof(_))
+        ));
+        assert!(resize(&|_| {}).unwrap());
+    }
+
+    #[test]
+    fn try_new_test() {
+        let f = Field::new(17.into());
+        let one = f.one();
+        let omega = f.element(6);
+        let fri = FRI::try_new(one, omega, 16, 2, 2).unwrap();
+        assert_eq!(fri.num_rounds(), 2);
+
+        assert!(matches!(
+            FRI::try_new(one, f.element(2), 16, 2, 2),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            FRI::try_new(one, omega, 12, 2, 2),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            FRI::try_new(one, omega, 16, 3, 2),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            FRI::try_new(one, omega, 16, 2, 0),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            FRI::try_new(one, omega, 16, 2, 4),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            FRI::try_new(f.zero(), omega, 16, 2, 2),
+            Err(Error::InvalidParameters(_))
+        ));
+        let other = Field::new(*PRIME);
+        assert!(matches!(
+            FRI::try_new(other.one(), omega, 16, 2, 2),
+            Err(Error::FieldMismatch)
+        ));
+    }
+
+    #[test]
+    fn too_few_rounds_test() {
+        let f = Field::new(17.into());
+        let fri = FRI::new(f.one(), f.element(6), 16, 2, 4);
+        assert_eq!(fri.num_rounds(), 0);
+        let codeword = vec![f.one(); 16];
+        assert!(matches!(
+            fri.prove(&codeword, &mut ProverChannel::new()),
+            Err(Error::InvalidParameters(_))
+        ));
+        let valid = FRI::try_new(f.one(), f.element(6), 16, 2, 2).unwrap();
+        let mut prover = ProverChannel::new();
+        valid.prove(&codeword, &mut prover).unwrap();
+        assert!(matches!(
+            fri.verify(&mut VerifierChannel::from(prover), vec![]),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn query_strategy_test() {
+        let f = Field::new(*PRIME);
+        let folded = FRI::new(f.generator(), f.primitive_nth_root(256.into()), 256, 4, 4);
+        let per_round = FRI::new(f.generator(), f.primitive_nth_root(256.into()), 256, 4, 4)
+            .with_query_strategy(QueryStrategy::PerRound);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one(), f.generator()]);
+        let codeword = p.evaluate_domain(&per_round.eval_domain());
+
+        let mut prover = ProverChannel::new();
+        let (indices, explanation) = per_round.prove_explained(&codeword, &mut prover).unwrap();
+        assert_eq!(indices.len(), per_round.num_colinearity_tests);
+        assert_eq!(
+            explanation
+                .steps
+                .iter()
+                .filter(|s| matches!(s, Step::SampleIndices { .. }))
+                .count(),
+            per_round.num_rounds() - 1
+        );
+        let ps = prover.into_proof();
+        assert!(per_round
+            .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+            .unwrap());
+        assert!(!folded
+            .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+            .unwrap());
+        let shifted = FRI::new(f.one(), f.primitive_nth_root(256.into()), 256, 4, 4)
+            .with_query_strategy(QueryStrategy::PerRound);
+        assert_ne!(
+            shifted.parameters_digest(HashKind::Blake2b),
+            per_round.parameters_digest(HashKind::Blake2b)
+        );
+        assert!(!shifted
+            .verify(&mut VerifierChannel::new(ps), vec![])
+            .unwrap());
+    }
+
+    #[test]
+    fn parameters_digest_test() {
+        let f = Field::new(*PRIME);
+        let new = || FRI::new(f.generator(), f.primitive_nth_root(64.into()), 64, 4, 2);
+        let fri = new();
+        let bounded = new().with_max_degree(0).unwrap();
+        assert_ne!(
+            fri.parameters_digest(HashKind::Blake2b),
+            bounded.parameters_digest(HashKind::Blake2b)
+        );
+        assert_ne!(
+            bounded.parameters_digest(HashKind::Blake2b),
+            new()
+                .with_max_degree(1)
+                .unwrap()
+                .parameters_digest(HashKind::Blake2b)
+        );
+        assert_ne!(
+            fri.parameters_digest(HashKind::Blake2b),
+            new()
+                .with_challenge_sampling(ChallengeSampling::Legacy)
+                .parameters_digest(HashKind::Blake2b)
+        );
+        assert_ne!(
+            fri.parameters_digest(HashKind::Blake2b),
+            fri.parameters_digest(HashKind::Legacy)
+        );
+    }
+
+    #[test]
+    fn challenge_sampling_test() {
+        let f = Field::new(*PRIME);
+        let wide = FRI::new(f.generator(), f.primitive_nth_root(64.into()), 64, 4, 2);
+        let legacy = FRI::new(f.generator(), f.primitive_nth_root(64.into()), 64, 4, 2)
+            .with_challenge_sampling(ChallengeSampling::Legacy);
+        assert_eq!(wide.challenge_sampling, ChallengeSampling::Wide);
+        assert_ne!(
+            wide.parameters_digest(HashKind::Blake2b),
+            legacy.parameters_digest(HashKind::Blake2b)
+        );
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let codeword = p.evaluate_domain(&wide.eval_domain());
+        for (fri, other) in [(&wide, &legacy), (&legacy, &wide)] {
+            let mut prover = ProverChannel::new();
+            fri.prove(&codeword, &mut prover).unwrap();
+            let ps = prover.into_proof();
+            assert!(fri
+                .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+                .unwrap());
+            assert!(!other.verify(&mut VerifierChannel::new(ps), vec![]).unwrap());
+        }
+    }
+
+    #[test]
+    fn derived_query_test() {
+        let f = Field::new(*PRIME);
+        let omega = f.primitive_nth_root(256.into());
+        let folded = FRI::new(f.generator(), omega, 256, 4, 4);
+        let derived =
+            FRI::new(f.generator(), omega, 256, 4, 4).with_query_strategy(QueryStrategy::Derived);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one(), f.generator()]);
+        let codeword = p.evaluate_domain(&derived.eval_domain());
+
+        let mut full = ProverChannel::new();
+        let full_indices = folded.prove(&codeword, &mut full).unwrap();
+        let mut prover = ProverChannel::new();
+        let indices = derived.prove(&codeword, &mut prover).unwrap();
+        assert_eq!(indices.len(), full_indices.len());
+        assert_eq!(
+            prover.proof().iter_paths().count() * 3,
+            full.proof().iter_paths().count() * 2
+        );
+        assert!(prover.proof().iter_leafs().all(|leafs| leafs.len() == 2));
+        assert!(prover.serialize().len() < full.serialize().len());
+
+        let ps = prover.into_proof();
+        assert!(derived
+            .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+            .unwrap());
+        assert!(!folded
+            .verify(&mut VerifierChannel::new(ps.clone()), vec![])
+            .unwrap());
+
+        let mut tampered = ps.clone();
+        let position = tampered
+            .objects
+            .iter()
+            .rposition(|object| matches!(object, Object::LEAF(_)))
+            .unwrap();
+        if let Object::LEAF(leafs) = &mut tampered.objects[position] {
+            leafs[0] += f.one();
+        }
+        assert!(!derived
+            .verify(&mut VerifierChannel::new(tampered), vec![])
+            .unwrap());
+
+        let mut tampered = ps;
+        let position = tampered
+            .objects
+            .iter()
+            .position(|object| matches!(object, Object::OBJ(_)))
+            .unwrap();
+        if let Object::OBJ(last) = &mut tampered.objects[position] {
+            last.iter_mut().for_each(|value| *value += f.one());
+        }
+        assert!(!derived
+            .verify(&mut VerifierChannel::new(tampered), vec![])
+            .unwrap());
+    }
+
+    #[test]
+    fn degree_correction_test() {
+        let f = Field::new(*PRIME);
+        let fri = FRI::for_degree(f, 11, 4, 2).unwrap();
+        assert_eq!(fri.domain_length, 64);
+        assert_eq!(fri.max_degree, Some(11));
+        let plain = FRI::new(fri.offset, fri.omega, 64, 4, 2);
+        assert!(FRI::new(fri.offset, fri.omega, 64, 4, 2)
+            .with_max_degree(16)
+            .is_err());
+
+        let honest = Polynomial::new(vec![f.generator(); 12]);
+        let codeword = honest.evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        let (_, explanation) = fri.prove_explained(&codeword, &mut prover).unwrap();
+        assert!(matches!(
+            explanation.steps[1],
+            Step::DegreeCorrection {
+                max_degree: 11,
+                shift: 4,
+                ..
+            }
+        ));
+        let compact = prover.serialize_compact().unwrap();
+        assert!(compact.len() < prover.serialize().len() / 2);
+        assert!(fri
+            .verify(
+                &mut VerifierChannel::deserialize_compact(&compact).unwrap(),
+                vec![]
+            )
+            .unwrap());
+
+        let too_high = Polynomial::new(vec![f.generator(); 14]);
+        let codeword = too_high.evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(!fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+
+        let mut prover = ProverChannel::new();
+        plain.prove(&codeword, &mut prover).unwrap();
+        assert!(plain
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+
+        // A prover who could predict beta would divide it out of a
+        // high-degree codeword; beta now follows the commitment to it.
+        let mut predicted = ProverChannel::new();
+        predicted.push_hash(fri.parameters_digest(predicted.hasher()));
+        let beta = fri.prover_challenge(&mut predicted);
+        let high = Polynomial::new(vec![f.generator(); 16]);
+        let domain = fri.eval_domain();
+        let factors = fri.degree_correction(&beta, 4, &domain);
+        let codeword: Vec<FieldElement> = high
+            .evaluate_domain(&domain)
+            .iter()
+            .zip(&factors)
+            .map(|(value, factor)| value / factor)
+            .collect();
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(!fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+    }
+
+    #[test]
+    fn prove_trace_test() {
+        let f = Field::new(*PRIME);
+        let trace = f.powers(&f.element(3u64), 11);
+        let fri = FRI::for_degree(f, trace.len() - 1, 4, 2).unwrap();
+        assert_eq!(fri.domain_length, 64);
+        let mut prover = ProverChannel::new();
+        fri.prove_trace(&trace, &mut prover).unwrap();
+        assert!(fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+
+        let codeword = fri.extend_trace(&trace).unwrap();
+        assert_eq!(codeword.len(), 64);
+        let polynomial = Polynomial::interpolate_domain(&fri.eval_domain(), &codeword).unwrap();
+        assert_eq!(polynomial.degree(), 10);
+        let root = fri.omega ^ 4u64.into();
+        assert_eq!(polynomial.evaluate(&(root ^ 5u64.into())), trace[5]);
+
+        assert!(matches!(
+            fri.prove_trace(&f.powers(&f.one(), 12), &mut ProverChannel::new()),
+            Err(Error::LengthMismatch {
+                expected: 11,
+                actual: 12
+            })
+        ));
+    }
+
+    #[test]
+    fn babybear_test() {
+        use crate::field::{babybear::BabyBear, FiniteField};
+
+        let bb = BabyBear;
+        let fri = FRI::for_degree(bb.field(), 15, 4, 4).unwrap();
+        let coefficients = (1..=16u64)
+            .map(|i| bb.pow(&bb.element(i * 1_000_003), 7.into()).into())
+            .collect();
+        let codeword = Polynomial::new(coefficients).evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+    }
+
+    #[test]
+    fn generic_element_test() {
+        use crate::field::babybear::{BabyBear, BabyBearElement};
+
+        let bb = BabyBear;
+        let fri = FRI::for_degree(bb, 15, 4, 4).unwrap();
+        let wide = FRI::for_degree(bb.field(), 15, 4, 4).unwrap();
+        assert_eq!(
+            fri.parameters_digest(HashKind::default()),
+            wide.parameters_digest(HashKind::default())
+        );
+
+        let coefficients = (1..=16u64).map(|i| bb.element(i * 1_000_003)).collect();
+        let polynomial: Polynomial<BabyBearElement> = Polynomial::new(coefficients);
+        let codeword = polynomial.evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+
+        let mut prover = ProverChannel::new();
+        let too_high = &polynomial * &Polynomial::new(vec![bb.zero(), bb.one()]);
+        fri.prove(&too_high.evaluate_domain(&fri.eval_domain()), &mut prover)
+            .unwrap();
+        assert!(!fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
+    }
+
+    #[test]
+    fn custom_prime_test() {
+        let f = Field::new(97.into());
+        assert!(matches!(
+            FRI::for_degree(f, 15, 4, 2),
+            Err(Error::BadDomainSize(64))
+        ));
+        let fri = FRI::for_degree(f, 3, 4, 2).unwrap();
+        assert_eq!(fri.domain_length, 16);
+        assert_eq!(fri.omega ^ 16.into(), f.one());
+        assert_ne!(fri.omega ^ 8.into(), f.one());
+        assert!(!fri.eval_domain().contains(&f.one()));
+        let by_domain = FRI::for_domain(f, 16, 4, 2).unwrap();
+        assert_eq!((by_domain.offset, by_domain.omega), (fri.offset, fri.omega));
+        assert!(matches!(
+            FRI::for_domain(f, 64, 4, 2),
+            Err(Error::BadDomainSize(64))
+        ));
+
+        let polynomial = Polynomial::new(f.powers(&f.element(5), 4));
+        let codeword = polynomial.evaluate_domain(&fri.eval_domain());
+        let mut prover = ProverChannel::new();
+        fri.prove(&codeword, &mut prover).unwrap();
+        assert!(fri
+            .verify(&mut VerifierChannel::from(prover), vec![])
+            .unwrap());
     }
 }
@@ -0,0 +1,258 @@
+use crate::{rescue, Error, Result};
+use blake2::Blake2bVar;
+use serde::{Deserialize, Serialize};
+use sha3::{
+    digest::{ExtendableOutput, Update, VariableOutput},
+    Digest,
+};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum HashKind {
+    Blake2b,
+    Blake3,
+    Sha3,
+    /// The tutorial's Rescue-Prime sponge. Its capacity is one 128-bit field
+    /// element and it squeezes 15 bytes at a time, so digests offer at most
+    /// about 64-bit collision resistance, below `SECURITY_BITS`.
+    Rescue,
+    /// Blake2b commitments with SHAKE256 Fiat–Shamir, as in the Python tutorial.
+    #[default]
+    Legacy,
+}
+
+pub const ALL: [HashKind; 5] = [
+    HashKind::Blake2b,
+    HashKind::Blake3,
+    HashKind::Sha3,
+    HashKind::Rescue,
+    HashKind::Legacy,
+];
+
+pub const DIGEST_LENGTH: usize = 32;
+pub const MIN_DIGEST_LENGTH: usize = 16;
+pub const MAX_DIGEST_LENGTH: usize = 64;
+
+impl HashKind {
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        self.digest_sized(data, DIGEST_LENGTH)
+    }
+
+    pub fn digest_sized(&self, data: &[u8], digest_length: usize) -> Vec<u8> {
+        match self {
+            HashKind::Blake2b | HashKind::Legacy => {
+                let mut hasher = Blake2bVar::new(digest_length).unwrap();
+                hasher.update(data);
+                let mut out = vec![0; digest_length];
+                hasher.finalize_variable(&mut out).unwrap();
+                out
+            }
+            HashKind::Blake3 => self.expand(data, digest_length),
+            HashKind::Sha3 => match digest_length {
+                32 => sha3::Sha3_256::digest(data).to_vec(),
+                64 => sha3::Sha3_512::digest(data).to_vec(),
+                _ => self.expand(data, digest_length),
+            },
+            HashKind::Rescue => rescue::hash_bytes(data, digest_length),
+        }
+    }
+
+    pub fn expand(&self, data: &[u8], num_bytes: usize) -> Vec<u8> {
+        let mut output = vec![0u8; num_bytes];
+        match self {
+            HashKind::Blake2b => {
+                for (counter, chunk) in output.chunks_mut(32).enumerate() {
+                    let mut block = data.to_vec();
+                    block.extend((counter as u64).to_be_bytes());
+                    chunk.copy_from_slice(&self.digest(&block)[..chunk.len()]);
+                }
+            }
+            HashKind::Blake3 => {
+                blake3::Hasher::new()
+                    .update(data)
+                    .finalize_xof()
+                    .fill(&mut output);
+            }
+            HashKind::Sha3 | HashKind::Legacy => sha3::Shake256::digest_xof(data, &mut output),
+            HashKind::Rescue => output = rescue::hash_bytes(data, num_bytes),
+        }
+        output
+    }
+}
+
+pub const SECURITY_BITS: usize = 128;
+
+// RFC 9380 expand_message_xof instantiated with SHAKE256.
+pub fn expand_message_xof(dst: &[u8], msg: &[u8], num_bytes: usize) -> Result<Vec<u8>> {
+    if num_bytes > u16::MAX as usize {
+        return Err(Error::InvalidParameters(format!(
+            "cannot expand to {} bytes",
+            num_bytes
+        )));
+    }
+    let oversized;
+    let dst = if dst.len() > u8::MAX as usize {
+        oversized = HashKind::Sha3.expand(
+            &[b"H2C-OVERSIZE-DST-".as_slice(), dst].concat(),
+            2 * SECURITY_BITS / 8,
+        );
+        &oversized[..]
+    } else {
+        dst
+    };
+    let mut output = vec![0u8; num_bytes];
+    let mut hasher = sha3::Shake256::default();
+    hasher.update(msg);
+    hasher.update(&(num_bytes as u16).to_be_bytes());
+    hasher.update(dst);
+    hasher.update(&[dst.len() as u8]);
+    hasher.finalize_xof_into(&mut output);
+    Ok(output)
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Hasher {
+    pub kind: HashKind,
+    pub digest_length: usize,
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        HashKind::default().into()
+    }
+}
+
+impl From<HashKind> for Hasher {
+    fn from(kind: HashKind) -> Self {
+        Hasher {
+            kind,
+            digest_length: DIGEST_LENGTH,
+        }
+    }
+}
+
+impl Hasher {
+    pub fn new(kind: HashKind, digest_length: usize) -> Result<Self> {
+        if !(MIN_DIGEST_LENGTH..=MAX_DIGEST_LENGTH).contains(&digest_length) {
+            return Err(Error::InvalidParameters(format!(
+                "digest length {} outside {}..={}",
+                digest_length, MIN_DIGEST_LENGTH, MAX_DIGEST_LENGTH
+            )));
+        }
+        Ok(Hasher {
+            kind,
+            digest_length,
+        })
+    }
+
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        self.kind.digest_sized(data, self.digest_length)
+    }
+
+    pub fn expand(&self, data: &[u8], num_bytes: usize) -> Vec<u8> {
+        self.kind.expand(data, num_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_message_xof, HashKind, Hasher};
+    use crate::Error;
+
+    #[test]
+    fn hash_kind_test() {
+        let kinds = [
+            HashKind::Blake2b,
+            HashKind::Blake3,
+            HashKind::Sha3,
+            HashKind::Rescue,
+            HashKind::Legacy,
+        ];
+        for kind in kinds {
+            assert_eq!(kind.digest(b"abc").len(), 32);
+            assert_eq!(kind.expand(b"abc", 70).len(), 70);
+            assert_eq!(kind.expand(b"abc", 70)[..32], kind.expand(b"abc", 32));
+            assert_ne!(kind.digest(b"abc"), kind.digest(b"abd"));
+        }
+        assert_ne!(
+            HashKind::Blake2b.digest(b"abc"),
+            HashKind::Blake3.digest(b"abc")
+        );
+        assert_ne!(
+            HashKind::Blake3.digest(b"abc"),
+            HashKind::Sha3.digest(b"abc")
+        );
+        assert_eq!(
+            hex::encode(HashKind::Sha3.digest(b"abc")),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_ne!(
+            HashKind::Rescue.digest(b"abc"),
+            HashKind::Blake2b.digest(b"abc")
+        );
+
+        // The default keeps the tutorial's Blake2b commitments and SHAKE256
+        // Fiat–Shamir.
+        assert_eq!(HashKind::default(), HashKind::Legacy);
+        assert_eq!(
+            HashKind::Legacy.digest(b"abc"),
+            HashKind::Blake2b.digest(b"abc")
+        );
+        assert_eq!(
+            HashKind::Legacy.expand(b"abc", 70),
+            HashKind::Sha3.expand(b"abc", 70)
+        );
+    }
+
+    #[test]
+    fn expand_message_xof_test() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
+        assert_eq!(
+            hex::encode(expand_message_xof(dst, b"", 0x20).unwrap()),
+            "2ffc05c48ed32b95d72e807f6eab9f7530dd1c2f013914c8fed38c5ccc15ad76"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xof(dst, b"abc", 0x20).unwrap()),
+            "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07"
+        );
+        assert_ne!(
+            expand_message_xof(b"tag-a", b"abc", 48).unwrap(),
+            expand_message_xof(b"tag-b", b"abc", 48).unwrap()
+        );
+        assert_eq!(expand_message_xof(&[7; 300], b"abc", 48).unwrap().len(), 48);
+        assert!(matches!(
+            expand_message_xof(dst, b"", 1 << 16),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn hasher_test() {
+        let kinds = [
+            HashKind::Blake2b,
+            HashKind::Blake3,
+            HashKind::Sha3,
+            HashKind::Rescue,
+            HashKind::Legacy,
+        ];
+        for kind in kinds {
+            assert_eq!(Hasher::from(kind).digest(b"abc"), kind.digest(b"abc"));
+            for digest_length in [16, 20, 48, 64] {
+                let hasher = Hasher::new(kind, digest_length).unwrap();
+                assert_eq!(hasher.digest(b"abc").len(), digest_length);
+                assert_ne!(hasher.digest(b"abc"), hasher.digest(b"abd"));
+            }
+        }
+        assert_eq!(
+            Hasher::new(HashKind::Blake3, 16).unwrap().digest(b"abc"),
+            HashKind::Blake3.digest(b"abc")[..16]
+        );
+        assert!(matches!(
+            Hasher::new(HashKind::Blake2b, 8),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            Hasher::new(HashKind::Sha3, 65),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    element::FieldElement,
+    fri::FRI,
+    hash::HashKind,
+    json,
+    proofstream::{Object, ProofStream},
+    Result,
+};
+use std::fmt;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Format {
+    Pickle,
+    Compact,
+    Json,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Counts {
+    pub hashes: usize,
+    pub paths: usize,
+    pub leafs: usize,
+    pub objs: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Round {
+    pub leafs: usize,
+    pub leaf_width: usize,
+    pub paths: usize,
+    pub path_depth: usize,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Inspection {
+    pub format: Format,
+    pub hash: HashKind,
+    pub parameters: Option<String>,
+    pub parameter_digest: Option<String>,
+    pub roots: Vec<String>,
+    pub last_codeword_length: Option<usize>,
+    pub counts: Counts,
+    pub commit_bytes: usize,
+    pub query_bytes: usize,
+    pub rounds: Vec<Round>,
+}
+
+impl Inspection {
+    pub fn new(format: Format, stream: &ProofStream<Vec<FieldElement>>) -> Self {
+        let mut counts = Counts::default();
+        let mut hashes = vec![];
+        let mut last_codeword_length = None;
+        let mut commit_bytes = 0;
+        let mut query_bytes = 0;
+        let mut rounds: Vec<Round> = vec![];
+
+        for object in &stream.objects {
+            let size = serde_pickle::to_vec(object, Default::default())
+                .unwrap()
+                .len();
+            if last_codeword_length.is_none() {
+                commit_bytes += size;
+            } else {
+                query_bytes += size;
+            }
+            match object {
+                Object::HASH(hash) => {
+                    counts.hashes += 1;
+                    hashes.push(hex::encode(hash));
+                }
+                Object::OBJ(codeword) => {
+                    counts.objs += 1;
+                    last_codeword_length.get_or_insert(codeword.len());
+                }
+                Object::LEAF(leafs) => {
+                    counts.leafs += 1;
+                    if rounds.last().is_none_or(|round| round.paths > 0) {
+                        rounds.push(Round {
+                            leafs: 0,
+                            leaf_width: leafs.len(),
+                            paths: 0,
+                            path_depth: 0,
+                        });
+                    }
+                    rounds.last_mut().unwrap().leafs += 1;
+                }
+                Object::PATH(path) => {
+                    counts.paths += 1;
+                    if let Some(round) = rounds.last_mut() {
+                        round.paths += 1;
+                        round.path_depth = round.path_depth.max(path.len());
+                    }
+                }
+            }
+        }
+
+        let parameter_digest = (!hashes.is_empty()).then(|| hashes.remove(0));
+        Inspection {
+            format,
+            hash: stream.hash,
+            parameters: None,
+            parameter_digest,
+            roots: hashes,
+            last_codeword_length,
+            counts,
+            commit_bytes,
+            query_bytes,
+            rounds,
+        }
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        if data.first() == Some(&b'{') {
+            let (fri, stream) = json::from_json(std::str::from_utf8(data).unwrap_or_default())?;
+            let mut inspection = Inspection::new(Format::Json, &stream);
+            inspection.parameters = Some(describe_parameters(&fri));
+            return Ok(inspection);
+        }
+        match ProofStream::deserialize(data) {
+            Ok(stream) => Ok(Inspection::new(Format::Pickle, &stream)),
+            Err(e) => match ProofStream::deserialize_compact(data) {
+                Ok(stream) => Ok(Inspection::new(Format::Compact, &stream)),
+                Err(_) => Err(e),
+            },
+        }
+    }
+}
+
+fn describe_parameters(fri: &FRI) -> String {
+    format!(
+        "p = {:#x}, offset = {:#x}, omega = {:#x}, domain = {}, expansion = {}, tests = {}, strategy = {:?}",
+        fri.field.p(),
+        fri.offset.value,
+        fri.omega.value,
+        fri.domain_length,
+        fri.expansion_factor,
+        fri.num_colinearity_tests,
+        fri.query_strategy
+    )
+}
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "format: {:?}", self.format)?;
+        writeln!(f, "hash: {:?}", self.hash)?;
+        if let Some(parameters) = &self.parameters {
+            writeln!(f, "parameters: {}", parameters)?;
+        }
+        if let Some(digest) = &self.parameter_digest {
+            writeln!(f, "parameter digest: {}", digest)?;
+        }
+        writeln!(
+            f,
+            "objects: {} hashes, {} paths, {} leafs, {} objs",
+            self.counts.hashes, self.counts.paths, self.counts.leafs, self.counts.objs
+        )?;
+        for (i, root) in self.roots.iter().enumerate() {
+            writeln!(f, "root {}: {}", i, root)?;
+        }
+        if let Some(length) = self.last_codeword_length {
+            writeln!(f, "last codeword: {} elements", length)?;
+        }
+        writeln!(f, "commit phase: {} bytes", self.commit_bytes)?;
+        writeln!(f, "query phase: {} bytes", self.query_bytes)?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            writeln!(
+                f,
+                "query round {}: {} leafs of {} values, {} paths of depth {}",
+                i, round.leafs, round.leaf_width, round.paths, round.path_depth
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{channel::ProverChannel, consts::*, field::Field, polynomial::Polynomial};
+
+    #[test]
+    fn inspection_test() {
+        let f = Field::new(*PRIME);
+        let fri = FRI::new(f.generator(), f.primitive_nth_root(256.into()), 256, 4, 4);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let mut channel = ProverChannel::new();
+        fri.prove(&p.evaluate_domain(&fri.eval_domain()), &mut channel)
+            .unwrap();
+        let stream = channel.proof();
+
+        let inspection = Inspection::from_bytes(&channel.serialize()).unwrap();
+        assert_eq!(inspection.format, Format::Pickle);
+        assert_eq!(inspection.roots.len(), fri.num_rounds());
+        assert_eq!(
+            inspection.last_codeword_length,
+            Some(256 >> (fri.num_rounds() - 1))
+        );
+        assert_eq!(inspection.rounds.len(), fri.num_rounds() - 1);
+        assert_eq!(inspection.rounds[0].leaf_width, 3);
+        assert_eq!(inspection.rounds[0].paths, 12);
+        assert_eq!(inspection.rounds[0].path_depth, 8);
+        assert_eq!(inspection.counts.objs, 1);
+        assert!(inspection.parameters.is_none());
+        assert_eq!(
+            inspection.parameter_digest,
+            Some(hex::encode(fri.parameters_digest(HashKind::Legacy)))
+        );
+
+        let compact = Inspection::from_bytes(&stream.serialize_compact().unwrap()).unwrap();
+        assert_eq!(compact.format, Format::Compact);
+        assert_eq!(compact.rounds, inspection.rounds);
+
+        let json = Inspection::from_bytes(json::to_json(&fri, stream).as_bytes()).unwrap();
+        assert_eq!(json.format, Format::Json);
+        assert!(json.parameters.unwrap().contains("domain = 256"));
+        assert!(inspection.to_string().contains("query round 1"));
+
+        assert!(Inspection::from_bytes(&[0xff, 0x00]).is_err());
+    }
+}
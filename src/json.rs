@@ -0,0 +1,318 @@
+use crate::{
+    element::FieldElement,
+    field::Field,
+    fri::{ChallengeSampling, QueryStrategy, FRI},
+    hash::{self, HashKind, Hasher, MAX_DIGEST_LENGTH, MIN_DIGEST_LENGTH},
+    merkle::{Direction, MerkleProof},
+    proofstream::{Object, ProofStream},
+    Error, Result,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+pub const VERSION: u32 = 3;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonParameters {
+    pub prime: String,
+    pub offset: String,
+    pub omega: String,
+    pub domain_length: usize,
+    pub expansion_factor: usize,
+    pub num_colinearity_tests: usize,
+    #[serde(default)]
+    pub query_strategy: QueryStrategy,
+    #[serde(default)]
+    pub challenge_sampling: ChallengeSampling,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_degree: Option<usize>,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonNode {
+    pub direction: Direction,
+    pub value: String,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonObject {
+    Hash { value: String },
+    Path { nodes: Vec<JsonNode> },
+    Leaf { values: Vec<String> },
+    Obj { values: Vec<String> },
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct JsonProof {
+    pub version: u32,
+    pub hash: HashKind,
+    pub digest_length: usize,
+    pub parameters: JsonParameters,
+    pub objects: Vec<JsonObject>,
+}
+
+fn encode_u256(value: U256) -> String {
+    format!("{:x}", value)
+}
+
+fn decode_u256(value: &str) -> Result<U256> {
+    U256::from_str_radix(value, 16).map_err(|_| Error::MalformedProof("invalid hex integer"))
+}
+
+fn decode_bytes(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|_| Error::MalformedProof("invalid hex string"))
+}
+
+fn encode_elements(elements: &[FieldElement]) -> Vec<String> {
+    elements.iter().map(|e| encode_u256(e.value)).collect()
+}
+
+fn decode_element(value: &str, field: Field) -> Result<FieldElement> {
+    let value = decode_u256(value)?;
+    if value >= field.p() {
+        return Err(Error::MalformedProof("field element out of range"));
+    }
+    Ok(FieldElement::new(value, field))
+}
+
+fn decode_elements(values: &[String], field: Field) -> Result<Vec<FieldElement>> {
+    values.iter().map(|v| decode_element(v, field)).collect()
+}
+
+impl JsonProof {
+    pub fn new(fri: &FRI, proof_stream: &ProofStream<Vec<FieldElement>>) -> Self {
+        JsonProof {
+            version: VERSION,
+            hash: proof_stream.hash,
+            digest_length: proof_stream.digest_length,
+            parameters: JsonParameters {
+                prime: encode_u256(fri.field.p()),
+                offset: encode_u256(fri.offset.value),
+                omega: encode_u256(fri.omega.value),
+                domain_length: fri.domain_length,
+                expansion_factor: fri.expansion_factor,
+                num_colinearity_tests: fri.num_colinearity_tests,
+                query_strategy: fri.query_strategy,
+                challenge_sampling: fri.challenge_sampling,
+                max_degree: fri.max_degree,
+            },
+            objects: proof_stream
+                .objects
+                .iter()
+                .map(|object| match object {
+                    Object::HASH(h) => JsonObject::Hash {
+                        value: hex::encode(h),
+                    },
+                    Object::PATH(p) => JsonObject::Path {
+                        nodes: p
+                            .nodes
+                            .iter()
+                            .map(|(direction, node)| JsonNode {
+                                direction: *direction,
+                                value: hex::encode(node),
+                            })
+                            .collect(),
+                    },
+                    Object::LEAF(l) => JsonObject::Leaf {
+                        values: encode_elements(l),
+                    },
+                    Object::OBJ(o) => JsonObject::Obj {
+                        values: encode_elements(o),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    pub fn fri(&self) -> Result<FRI> {
+        let field = Field::try_new(decode_u256(&self.parameters.prime)?)?;
+        let offset = decode_element(&self.parameters.offset, field)?;
+        let omega = decode_element(&self.parameters.omega, field)?;
+        let fri = FRI::try_new(
+            offset,
+            omega,
+            self.parameters.domain_length,
+            self.parameters.expansion_factor,
+            self.parameters.num_colinearity_tests,
+        )?
+        .with_query_strategy(self.parameters.query_strategy)
+        .with_challenge_sampling(self.parameters.challenge_sampling)
+        .with_hasher(Hasher::new(self.hash, self.digest_length)?);
+        match self.parameters.max_degree {
+            Some(max_degree) => fri.with_max_degree(max_degree),
+            None => Ok(fri),
+        }
+    }
+
+    pub fn proof_stream(&self) -> Result<ProofStream<Vec<FieldElement>>> {
+        let field = Field::try_new(decode_u256(&self.parameters.prime)?)?;
+        let mut proof_stream =
+            ProofStream::with_hasher(Hasher::new(self.hash, self.digest_length)?);
+        for object in &self.objects {
+            proof_stream.push(match object {
+                JsonObject::Hash { value } => Object::HASH(decode_bytes(value)?),
+                JsonObject::Path { nodes } => Object::PATH(MerkleProof::new(
+                    nodes
+                        .iter()
+                        .map(|n| Ok((n.direction, decode_bytes(&n.value)?)))
+                        .collect::<Result<Vec<(Direction, Vec<u8>)>>>()?,
+                )),
+                JsonObject::Leaf { values } => Object::LEAF(decode_elements(values, field)?),
+                JsonObject::Obj { values } => Object::OBJ(decode_elements(values, field)?),
+            });
+        }
+        Ok(proof_stream)
+    }
+}
+
+pub fn to_json(fri: &FRI, proof_stream: &ProofStream<Vec<FieldElement>>) -> String {
+    serde_json::to_string_pretty(&JsonProof::new(fri, proof_stream)).unwrap()
+}
+
+pub fn from_json(json: &str) -> Result<(FRI, ProofStream<Vec<FieldElement>>)> {
+    let proof: JsonProof = serde_json::from_str(json)?;
+    if proof.version != VERSION {
+        return Err(Error::MalformedProof("unsupported json proof version"));
+    }
+    Ok((proof.fri()?, proof.proof_stream()?))
+}
+
+pub fn to_json_schema() -> String {
+    let hex = json!({ "type": "string", "pattern": "^[0-9a-f]*$" });
+    let hex_array = json!({ "type": "array", "items": hex });
+    let node_array = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "direction": { "enum": ["Left", "Right"] },
+                "value": hex,
+            },
+            "required": ["direction", "value"],
+            "additionalProperties": false,
+        },
+    });
+    let object = |kind: &str, key: &str, value: &serde_json::Value| {
+        json!({
+            "type": "object",
+            "properties": { "type": { "const": kind }, key: value },
+            "required": ["type", key],
+            "additionalProperties": false,
+        })
+    };
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "FRI proof",
+        "description": "Field elements and integers are big-endian hex without a prefix; hashes and authentication path nodes are hex-encoded digests, and each path node records the side its sibling sits on.",
+        "type": "object",
+        "properties": {
+            "version": { "const": VERSION },
+            "hash": { "enum": hash::ALL },
+            "digest_length": {
+                "type": "integer",
+                "minimum": MIN_DIGEST_LENGTH,
+                "maximum": MAX_DIGEST_LENGTH
+            },
+            "parameters": {
+                "type": "object",
+                "properties": {
+                    "prime": hex,
+                    "offset": hex,
+                    "omega": hex,
+                    "domain_length": { "type": "integer", "minimum": 1 },
+                    "expansion_factor": { "type": "integer", "minimum": 1 },
+                    "num_colinearity_tests": { "type": "integer", "minimum": 1 },
+                    "query_strategy": { "enum": ["Folded", "PerRound", "Derived"] },
+                    "challenge_sampling": { "enum": ["Wide", "Legacy"] },
+                    "max_degree": { "type": "integer", "minimum": 0 },
+                },
+                "required": [
+                    "prime",
+                    "offset",
+                    "omega",
+                    "domain_length",
+                    "expansion_factor",
+                    "num_colinearity_tests",
+                ],
+                "additionalProperties": false,
+            },
+            "objects": {
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        object("hash", "value", &hex),
+                        object("path", "nodes", &node_array),
+                        object("leaf", "values", &hex_array),
+                        object("obj", "values", &hex_array),
+                    ],
+                },
+            },
+        },
+        "required": ["version", "hash", "digest_length", "parameters", "objects"],
+        "additionalProperties": false,
+    });
+    serde_json::to_string_pretty(&schema).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        channel::{ProverChannel, VerifierChannel},
+        consts::*,
+        polynomial::Polynomial,
+    };
+
+    #[test]
+    fn json_round_trip_test() {
+        let f = Field::new(*PRIME);
+        let fri = FRI::new(f.generator(), f.primitive_nth_root(32.into()), 32, 4, 2)
+            .with_hasher(HashKind::Sha3);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let mut prover = ProverChannel::with_hash(HashKind::Sha3);
+        fri.prove(&p.evaluate_domain(&fri.eval_domain()), &mut prover)
+            .unwrap();
+
+        let json = to_json(&fri, prover.proof());
+        let (imported_fri, imported) = from_json(&json).unwrap();
+        assert_eq!(&imported, prover.proof());
+        assert_eq!(imported_fri.omega, fri.omega);
+        assert_eq!(imported_fri.query_strategy, QueryStrategy::Folded);
+        assert_eq!(imported_fri.hasher, fri.hasher);
+        assert!(imported_fri
+            .verify(&mut VerifierChannel::new(imported), vec![])
+            .unwrap());
+
+        let tampered = json.replacen("\"type\": \"obj\"", "\"type\": \"blob\"", 1);
+        assert!(matches!(from_json(&tampered), Err(Error::Json(_))));
+        let tampered = json.replacen(&encode_u256(f.p()), "zz", 1);
+        assert!(matches!(
+            from_json(&tampered),
+            Err(Error::MalformedProof(_))
+        ));
+    }
+
+    #[test]
+    fn json_schema_test() {
+        let schema: serde_json::Value = serde_json::from_str(&to_json_schema()).unwrap();
+        assert_eq!(schema["properties"]["version"]["const"], VERSION);
+        assert_eq!(
+            schema["properties"]["objects"]["items"]["oneOf"][3]["properties"]["type"]["const"],
+            "obj"
+        );
+
+        let f = Field::new(*PRIME);
+        let fri = FRI::new(f.generator(), f.primitive_nth_root(32.into()), 32, 4, 2);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let mut prover = ProverChannel::new();
+        fri.prove(&p.evaluate_domain(&fri.eval_domain()), &mut prover)
+            .unwrap();
+        let proof: serde_json::Value =
+            serde_json::from_str(&to_json(&fri, prover.proof())).unwrap();
+        let kinds = schema["properties"]["hash"]["enum"].as_array().unwrap();
+        assert_eq!(kinds.len(), hash::ALL.len());
+        assert!(kinds.contains(&proof["hash"]));
+    }
+}
@@ -1,14 +1,50 @@
 use consts::*;
 use primitive_types::U256;
 
-mod consts;
+#[macro_use]
+mod log;
+
+#[cfg(feature = "ark")]
+pub mod ark;
+pub mod bigint;
+pub mod channel;
+pub mod config;
+pub mod consts;
+pub mod domain;
 pub mod element;
+mod error;
+pub mod explain;
 pub mod field;
 pub mod fri;
+pub mod hash;
+pub mod inspect;
+pub mod json;
+pub mod matrix;
 pub mod merkle;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod mpolynomial;
+pub mod ntt;
 pub mod polynomial;
 pub mod proofstream;
+#[cfg(feature = "python")]
+pub mod python;
+mod recorder;
+pub mod rescue;
+pub mod sampling;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
+pub mod trace;
+pub mod transcript;
+pub mod vectors;
+#[cfg(feature = "viz")]
+pub mod viz;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "winterfell")]
+pub mod winterfell;
+
+pub use error::{Error, Result};
 
 pub fn xgcd(x: U256, y: U256) -> (U256, U256, U256, bool, bool) {
     let (mut old_r, mut r) = (x, y);
@@ -78,7 +114,46 @@ pub fn xgcd(x: U256, y: U256) -> (U256, U256, U256, bool, bool) {
             }
         }
     }
-    return (old_s, old_t, old_r, old_s_neg, old_t_neg);
+    (old_s, old_t, old_r, old_s_neg, old_t_neg)
+}
+
+pub fn binary_inverse(x: U256, p: U256) -> U256 {
+    assert!(p.bit(0) && !(x % p).is_zero());
+    let halve = |value: U256| {
+        if !value.bit(0) {
+            return value >> 1;
+        }
+        let (sum, overflow) = value.overflowing_add(p);
+        if overflow {
+            (sum >> 1) | (ONE << 255)
+        } else {
+            sum >> 1
+        }
+    };
+    let (mut u, mut v) = (x % p, p);
+    let (mut x1, mut x2) = (ONE, ZERO);
+    while u != ONE && v != ONE {
+        while !u.bit(0) {
+            u >>= 1;
+            x1 = halve(x1);
+        }
+        while !v.bit(0) {
+            v >>= 1;
+            x2 = halve(x2);
+        }
+        if u >= v {
+            u -= v;
+            x1 = if x1 >= x2 { x1 - x2 } else { p - (x2 - x1) };
+        } else {
+            v -= u;
+            x2 = if x2 >= x1 { x2 - x1 } else { p - (x1 - x2) };
+        }
+    }
+    if u == ONE {
+        x1
+    } else {
+        x2
+    }
 }
 
 #[cfg(test)]
@@ -91,14 +166,33 @@ mod tests {
         assert_eq!(result.0, ONE);
         assert_eq!(result.1, ONE);
         assert_eq!(result.2, 12.into());
-        assert_eq!(true, result.3);
-        assert_eq!(false, result.4);
+        assert!(result.3);
+        assert!(!result.4);
 
         let result = xgcd(36.into(), 24.into());
         assert_eq!(result.0, ONE);
         assert_eq!(result.1, ONE);
         assert_eq!(result.2, 12.into());
-        assert_eq!(false, result.3);
-        assert_eq!(true, result.4);
+        assert!(!result.3);
+        assert!(result.4);
+    }
+
+    #[test]
+    fn binary_inverse_test() {
+        for (x, p) in [(3u64, 7u64), (1, 7), (6, 7), (5, 17), (24, 37)] {
+            let inverse = binary_inverse(x.into(), p.into());
+            assert_eq!(inverse * U256::from(x) % U256::from(p), ONE);
+        }
+        let inverse = binary_inverse(*GENERATOR, *PRIME);
+        assert_eq!(inverse * *GENERATOR % *PRIME, ONE);
+
+        let p = U256::MAX - (ONE << 32) - 976;
+        let x = U256::MAX >> 1;
+        let inverse = binary_inverse(x, p);
+        assert!(inverse < p);
+        assert_eq!(
+            inverse.full_mul(x) % primitive_types::U512::from(p),
+            1.into()
+        );
     }
 }
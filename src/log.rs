@@ -0,0 +1,19 @@
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! span {
+    ($name:literal $(, $($fields:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+        #[cfg(not(feature = "tracing"))]
+        let span = $crate::log::NoSpan;
+        span
+    }};
+}
@@ -0,0 +1,186 @@
+use crate::{
+    hash::{HashKind, Hasher},
+    merkle::{Merkle, MerkleProof},
+    Error, Result,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct RowOpening<T> {
+    pub row: usize,
+    pub values: Vec<T>,
+    pub proof: MerkleProof,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CellOpening<T> {
+    pub row: usize,
+    pub column: usize,
+    pub value: T,
+    pub cell_proof: MerkleProof,
+    pub row_root: Vec<u8>,
+    pub row_proof: MerkleProof,
+}
+
+pub struct Matrix<T> {
+    pub hash: Hasher,
+    rows: Vec<Vec<T>>,
+    row_roots: Vec<Vec<u8>>,
+    root: Vec<u8>,
+}
+
+impl<T: Serialize + Clone> Matrix<T> {
+    pub fn commit(rows: Vec<Vec<T>>) -> Result<Self> {
+        Matrix::commit_with(HashKind::default(), rows)
+    }
+
+    pub fn commit_with(hash: impl Into<Hasher>, rows: Vec<Vec<T>>) -> Result<Self> {
+        let hash = hash.into();
+        if rows.len() < 2 {
+            return Err(Error::BadDomainSize(rows.len()));
+        }
+        let num_columns = rows[0].len();
+        if num_columns < 2 {
+            return Err(Error::BadDomainSize(num_columns));
+        }
+        if let Some(row) = rows.iter().find(|row| row.len() != num_columns) {
+            return Err(Error::LengthMismatch {
+                expected: num_columns,
+                actual: row.len(),
+            });
+        }
+        let row_roots: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|row| Merkle::commit_with(hash, row))
+            .collect();
+        let root = Merkle::commit_with(hash, &row_roots);
+        Ok(Matrix {
+            hash,
+            rows,
+            row_roots,
+            root,
+        })
+    }
+
+    pub fn root(&self) -> &[u8] {
+        &self.root
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn open_row(&self, row: usize) -> Result<RowOpening<T>> {
+        Ok(RowOpening {
+            row,
+            proof: Merkle::open_with(self.hash, row, &self.row_roots)?,
+            values: self.rows[row].clone(),
+        })
+    }
+
+    pub fn open_cell(&self, row: usize, column: usize) -> Result<CellOpening<T>> {
+        let row_proof = Merkle::open_with(self.hash, row, &self.row_roots)?;
+        let cell_proof = Merkle::open_with(self.hash, column, &self.rows[row])?;
+        Ok(CellOpening {
+            row,
+            column,
+            value: self.rows[row][column].clone(),
+            cell_proof,
+            row_root: self.row_roots[row].clone(),
+            row_proof,
+        })
+    }
+
+    pub fn verify_row(root: &[u8], opening: &RowOpening<T>) -> bool {
+        Matrix::verify_row_with(HashKind::default(), root, opening)
+    }
+
+    pub fn verify_row_with(hash: impl Into<Hasher>, root: &[u8], opening: &RowOpening<T>) -> bool {
+        let hash = hash.into();
+        let row_root = Merkle::commit_with(hash, &opening.values);
+        Merkle::verify_with(hash, root, opening.row, &opening.proof, &row_root)
+    }
+
+    pub fn verify_cell(root: &[u8], opening: &CellOpening<T>) -> bool {
+        Matrix::verify_cell_with(HashKind::default(), root, opening)
+    }
+
+    pub fn verify_cell_with(
+        hash: impl Into<Hasher>,
+        root: &[u8],
+        opening: &CellOpening<T>,
+    ) -> bool {
+        let hash = hash.into();
+        Merkle::verify_with(
+            hash,
+            &opening.row_root,
+            opening.column,
+            &opening.cell_proof,
+            &opening.value,
+        ) && Merkle::verify_with(
+            hash,
+            root,
+            opening.row,
+            &opening.row_proof,
+            &opening.row_root,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_test() {
+        let rows: Vec<Vec<u64>> = (0..5)
+            .map(|r| (0..3).map(|c| r * 10 + c).collect())
+            .collect();
+        let matrix = Matrix::commit(rows.clone()).unwrap();
+        assert_eq!(matrix.num_rows(), 5);
+        assert_eq!(matrix.num_columns(), 3);
+
+        let opening = matrix.open_row(3).unwrap();
+        assert_eq!(opening.values, rows[3]);
+        assert!(Matrix::verify_row(matrix.root(), &opening));
+        let mut tampered = opening.clone();
+        tampered.values[1] = 0;
+        assert!(!Matrix::verify_row(matrix.root(), &tampered));
+        let mut moved = opening.clone();
+        moved.row = 2;
+        assert!(!Matrix::verify_row(matrix.root(), &moved));
+
+        let opening = matrix.open_cell(4, 2).unwrap();
+        assert_eq!(opening.value, 42);
+        assert!(Matrix::verify_cell(matrix.root(), &opening));
+        let mut tampered = opening.clone();
+        tampered.value = 41;
+        assert!(!Matrix::verify_cell(matrix.root(), &tampered));
+        let mut moved = opening.clone();
+        moved.column = 1;
+        assert!(!Matrix::verify_cell(matrix.root(), &moved));
+        assert!(!Matrix::verify_cell_with(
+            HashKind::Sha3,
+            matrix.root(),
+            &opening
+        ));
+
+        assert!(matrix.open_row(5).is_err());
+        assert!(matrix.open_cell(0, 3).is_err());
+        assert!(matches!(
+            Matrix::commit(vec![vec![1u64, 2], vec![3]]),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            Matrix::commit(vec![vec![1u64, 2]]),
+            Err(Error::BadDomainSize(1))
+        ));
+    }
+}
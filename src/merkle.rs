@@ -1,104 +1,231 @@
-use blake2::Blake2bVar;
-use serde::Serialize;
-use sha3::digest::{Update, VariableOutput};
+use crate::{
+    hash::{HashKind, Hasher, DIGEST_LENGTH},
+    Error, Result,
+};
+use serde::{Deserialize, Serialize};
 
 pub fn hash(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Blake2bVar::new(32).unwrap();
-    hasher.update(data);
-    let mut out = vec![0; 32];
-    hasher.finalize_variable(&mut out).unwrap();
-    out
+    HashKind::Blake2b.digest(data)
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub nodes: Vec<(Direction, Vec<u8>)>,
+}
+
+impl MerkleProof {
+    pub fn new(nodes: Vec<(Direction, Vec<u8>)>) -> Self {
+        MerkleProof { nodes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        if self.nodes.len() >= usize::BITS as usize {
+            return None;
+        }
+        Some(
+            self.nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, (direction, _))| *direction == Direction::Left)
+                .map(|(level, _)| 1 << level)
+                .sum(),
+        )
+    }
 }
 
 pub struct Merkle {}
 
 impl Merkle {
-    fn commit_(leafs: &[Vec<u8>]) -> Vec<u8> {
+    fn commit_(hash: Hasher, leafs: &[Vec<u8>]) -> Result<Vec<u8>> {
         let len = leafs.len();
-        assert!(len & (len - 1) == 0);
+        if !len.is_power_of_two() {
+            return Err(Error::BadDomainSize(len));
+        }
         if len == 1 {
-            return leafs[0].clone();
+            return Ok(leafs[0].clone());
         }
 
-        let mut combined = Vec::from(Merkle::commit_(&leafs[0..len / 2]));
-        combined.extend(Merkle::commit_(&leafs[len / 2..len]));
-        hash(&combined)
+        let mut combined = Merkle::commit_(hash, &leafs[0..len / 2])?;
+        combined.extend(Merkle::commit_(hash, &leafs[len / 2..len])?);
+        Ok(hash.digest(&combined))
     }
 
-    fn open_(index: usize, leafs: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    fn open_(hash: Hasher, index: usize, leafs: &[Vec<u8>]) -> Result<Vec<(Direction, Vec<u8>)>> {
         let len = leafs.len();
-        assert!(len & (len - 1) == 0);
-        assert!(index < len);
+        if !len.is_power_of_two() || len < 2 {
+            return Err(Error::BadDomainSize(len));
+        }
+        if index >= len {
+            return Err(Error::IndexOutOfRange { index, len });
+        }
         if len == 2 {
-            return vec![leafs[1 - index].clone()];
+            let direction = if index == 0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            };
+            Ok(vec![(direction, leafs[1 - index].clone())])
         } else if index < len / 2 {
-            let mut combined = Merkle::open_(index, &leafs[0..len / 2]);
-            combined.push(Merkle::commit_(&leafs[len / 2..len]));
-            return combined;
+            let mut combined = Merkle::open_(hash, index, &leafs[0..len / 2])?;
+            combined.push((
+                Direction::Right,
+                Merkle::commit_(hash, &leafs[len / 2..len])?,
+            ));
+            Ok(combined)
         } else {
-            let mut combined = Merkle::open_(index - len / 2, &leafs[len / 2..len]);
-            combined.push(Merkle::commit_(&leafs[0..len / 2]));
-            return combined;
+            let mut combined = Merkle::open_(hash, index - len / 2, &leafs[len / 2..len])?;
+            combined.push((Direction::Left, Merkle::commit_(hash, &leafs[0..len / 2])?));
+            Ok(combined)
         }
     }
 
-    fn verify_(root: &[u8], index: usize, path: &[Vec<u8>], leaf: &[u8]) -> bool {
-        let len = path.len();
-        assert!(index < (1 << path.len()));
-        let mut data;
-        if index % 2 == 0 {
-            data = Vec::from(leaf);
-            data.extend(&path[0]);
-        } else {
-            data = path[0].clone();
-            data.extend(leaf);
-        }
-        let hash = hash(&data);
-        if len == 1 {
-            return root == hash;
-        } else {
-            return Merkle::verify_(root, index >> 1, &path[1..], &hash);
+    fn verify_(hash: Hasher, root: &[u8], proof: &MerkleProof, leaf: &[u8]) -> bool {
+        if proof.is_empty() {
+            return false;
         }
+        let digest = proof
+            .nodes
+            .iter()
+            .fold(Vec::from(leaf), |digest, (direction, sibling)| {
+                let mut data;
+                match direction {
+                    Direction::Right => {
+                        data = digest;
+                        data.extend(sibling);
+                    }
+                    Direction::Left => {
+                        data = sibling.clone();
+                        data.extend(digest);
+                    }
+                }
+                hash.digest(&data)
+            });
+        root == digest
     }
 
-    fn hash_data_array<T: Serialize>(data_array: &Vec<T>) -> Vec<Vec<u8>> {
+    pub(crate) fn hash_data_array<T: Serialize>(hash: Hasher, data_array: &[T]) -> Vec<Vec<u8>> {
         let mut hash_data: Vec<Vec<u8>> = data_array
             .iter()
             .map(|data| {
                 let bytes = serde_pickle::to_vec(data, Default::default()).unwrap();
-                hash(&bytes)
+                hash.digest(&bytes)
             })
             .collect();
         let len = hash_data.len();
-        if len & (len - 1) != 0 {
-            hash_data.resize_with(len.next_power_of_two(), || Vec::new());
+        if len > 0 && !len.is_power_of_two() {
+            hash_data.resize_with(len.next_power_of_two(), Vec::new);
         }
         hash_data
     }
 
-    pub fn commit<T: Serialize>(data_array: &Vec<T>) -> Vec<u8> {
-        Merkle::commit_(&Merkle::hash_data_array(data_array))
+    pub fn tree_bytes(num_leafs: usize) -> usize {
+        Merkle::tree_bytes_sized(num_leafs, DIGEST_LENGTH)
+    }
+
+    pub fn tree_bytes_sized(num_leafs: usize, digest_length: usize) -> usize {
+        (2 * num_leafs.next_power_of_two() - 1) * digest_length
+    }
+
+    pub fn commit<T: Serialize>(data_array: &[T]) -> Vec<u8> {
+        Merkle::commit_with(HashKind::default(), data_array)
+    }
+
+    pub fn commit_with<T: Serialize>(hash: impl Into<Hasher>, data_array: &[T]) -> Vec<u8> {
+        Merkle::try_commit_with(hash, data_array).expect("cannot commit to an empty array")
     }
 
-    pub fn open<T: Serialize>(index: usize, data_array: &Vec<T>) -> Vec<Vec<u8>> {
-        Merkle::open_(index, &Merkle::hash_data_array(data_array))
+    pub fn try_commit_with<T: Serialize>(
+        hash: impl Into<Hasher>,
+        data_array: &[T],
+    ) -> Result<Vec<u8>> {
+        let hash = hash.into();
+        Merkle::commit_(hash, &Merkle::hash_data_array(hash, data_array))
+    }
+
+    pub fn open<T: Serialize>(index: usize, data_array: &[T]) -> Result<MerkleProof> {
+        Merkle::open_with(HashKind::default(), index, data_array)
+    }
+
+    pub fn open_with<T: Serialize>(
+        hash: impl Into<Hasher>,
+        index: usize,
+        data_array: &[T],
+    ) -> Result<MerkleProof> {
+        let hash = hash.into();
+        if data_array.len() < 2 {
+            return Err(Error::BadDomainSize(data_array.len()));
+        }
+        if index >= data_array.len() {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: data_array.len(),
+            });
+        }
+        Ok(MerkleProof::new(Merkle::open_(
+            hash,
+            index,
+            &Merkle::hash_data_array(hash, data_array),
+        )?))
     }
 
     pub fn verify<T: Serialize>(
         root: &[u8],
         index: usize,
-        path: &[Vec<u8>],
+        proof: &MerkleProof,
         data_element: &T,
     ) -> bool {
+        Merkle::verify_with(HashKind::default(), root, index, proof, data_element)
+    }
+
+    pub fn verify_with<T: Serialize>(
+        hash: impl Into<Hasher>,
+        root: &[u8],
+        index: usize,
+        proof: &MerkleProof,
+        data_element: &T,
+    ) -> bool {
+        proof.index() == Some(index)
+            && Merkle::verify_membership_with(hash, root, proof, data_element)
+    }
+
+    pub fn verify_membership<T: Serialize>(
+        root: &[u8],
+        proof: &MerkleProof,
+        data_element: &T,
+    ) -> bool {
+        Merkle::verify_membership_with(HashKind::default(), root, proof, data_element)
+    }
+
+    pub fn verify_membership_with<T: Serialize>(
+        hash: impl Into<Hasher>,
+        root: &[u8],
+        proof: &MerkleProof,
+        data_element: &T,
+    ) -> bool {
+        let hash = hash.into();
         let bytes = serde_pickle::to_vec(data_element, Default::default()).unwrap();
-        let leaf = hash(&bytes);
-        Merkle::verify_(root, index, path, &leaf)
+        let leaf = hash.digest(&bytes);
+        Merkle::verify_(hash, root, proof, &leaf)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{hash, Merkle};
+    use super::{hash, Direction, Error, HashKind, Hasher, Merkle, MerkleProof};
 
     fn combine(a: &[u8], b: &[u8]) -> Vec<u8> {
         let mut combined = Vec::from(a);
@@ -111,25 +238,35 @@ mod tests {
         let leafs = vec![vec![1], vec![2], vec![3], vec![4]];
         let root = Merkle::commit(&leafs);
 
-        let hashed_leafs = Merkle::hash_data_array(&leafs);
+        let hashed_leafs = Merkle::hash_data_array(HashKind::Blake2b.into(), &leafs);
 
         let mut expected_root = hash(&combine(&hashed_leafs[0], &hashed_leafs[1]));
         expected_root.extend(hash(&combine(&hashed_leafs[2], &hashed_leafs[3])));
 
         assert_eq!(root, hash(&expected_root));
+        assert!(matches!(
+            Merkle::try_commit_with(HashKind::Blake2b, &Vec::<Vec<u8>>::new()),
+            Err(Error::BadDomainSize(0))
+        ));
     }
 
     #[test]
     fn open_test() {
         let leafs = vec![vec![1], vec![2], vec![3], vec![4]];
-        let path = Merkle::open(1, &leafs);
+        let path = Merkle::open(1, &leafs).unwrap();
 
-        let hashed_leafs = Merkle::hash_data_array(&leafs);
+        let hashed_leafs = Merkle::hash_data_array(HashKind::Blake2b.into(), &leafs);
 
-        let mut expected_path = vec![hashed_leafs[0].clone()];
-        expected_path.push(hash(&combine(&hashed_leafs[2], &hashed_leafs[3])));
+        let expected_path = MerkleProof::new(vec![
+            (Direction::Left, hashed_leafs[0].clone()),
+            (
+                Direction::Right,
+                hash(&combine(&hashed_leafs[2], &hashed_leafs[3])),
+            ),
+        ]);
 
         assert_eq!(path, expected_path);
+        assert_eq!(path.index(), Some(1));
     }
 
     #[test]
@@ -138,11 +275,44 @@ mod tests {
 
         let root = Merkle::commit(&leafs);
 
-        let path = Merkle::open(0, &leafs);
+        let path = Merkle::open(0, &leafs).unwrap();
         assert!(Merkle::verify(&root, 0, &path, &vec![1]));
 
-        let path = Merkle::open(1, &leafs);
+        let path = Merkle::open(1, &leafs).unwrap();
         assert!(Merkle::verify(&root, 1, &path, &vec![2]));
         assert!(!Merkle::verify(&root, 2, &path, &vec![2]));
+        assert!(!Merkle::verify(&root, 4, &path, &vec![2]));
+        assert!(!Merkle::verify(&root, 0, &MerkleProof::default(), &vec![1]));
+        assert!(Merkle::open(4, &leafs).is_err());
+
+        assert!(Merkle::verify_membership(&root, &path, &vec![2]));
+        assert!(!Merkle::verify_membership(&root, &path, &vec![3]));
+        let mut flipped = path.clone();
+        flipped.nodes[0].0 = Direction::Right;
+        assert!(!Merkle::verify_membership(&root, &flipped, &vec![2]));
+        assert!(!Merkle::verify(&root, 0, &flipped, &vec![2]));
+
+        for kind in [HashKind::Blake3, HashKind::Sha3] {
+            let root = Merkle::commit_with(kind, &leafs);
+            assert_ne!(root, Merkle::commit(&leafs));
+            let path = Merkle::open_with(kind, 3, &leafs).unwrap();
+            assert!(Merkle::verify_with(kind, &root, 3, &path, &vec![4]));
+            assert!(!Merkle::verify(&root, 3, &path, &vec![4]));
+        }
+
+        let short = Hasher::new(HashKind::Blake2b, 16).unwrap();
+        let root = Merkle::commit_with(short, &leafs);
+        assert_eq!(root.len(), 16);
+        let path = Merkle::open_with(short, 2, &leafs).unwrap();
+        assert!(path.nodes.iter().all(|(_, node)| node.len() == 16));
+        assert!(Merkle::verify_with(short, &root, 2, &path, &vec![3]));
+        assert!(!Merkle::verify_with(
+            HashKind::Blake2b,
+            &root,
+            2,
+            &path,
+            &vec![3]
+        ));
+        assert_eq!(Merkle::tree_bytes_sized(4, 16), Merkle::tree_bytes(4) / 2);
     }
 }
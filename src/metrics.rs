@@ -0,0 +1,108 @@
+use crate::recorder::Recorder;
+use std::time::{Duration, Instant};
+
+/// Byte counts are estimated from buffer lengths, not measured at the allocator.
+#[derive(Debug, Clone, Default)]
+pub struct ProverMetrics {
+    pub phases: Vec<(&'static str, Duration)>,
+    pub allocations: Vec<(&'static str, usize)>,
+    pub live_bytes: usize,
+    pub peak_bytes: usize,
+}
+
+impl ProverMetrics {
+    pub fn new() -> Self {
+        ProverMetrics {
+            phases: vec![],
+            allocations: vec![],
+            live_bytes: 0,
+            peak_bytes: 0,
+        }
+    }
+
+    pub fn record(&mut self, phase: &'static str, duration: Duration) {
+        self.phases.push((phase, duration));
+    }
+
+    pub fn time<R>(&mut self, phase: &'static str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    pub fn duration(&self, phase: &str) -> Duration {
+        self.phases
+            .iter()
+            .filter(|(name, _)| *name == phase)
+            .map(|(_, duration)| *duration)
+            .sum()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    pub fn allocate(&mut self, buffer: &'static str, bytes: usize) {
+        self.allocations.push((buffer, bytes));
+        self.live_bytes += bytes;
+        self.peak_bytes = self.peak_bytes.max(self.live_bytes);
+    }
+
+    pub fn release(&mut self, bytes: usize) {
+        self.live_bytes = self.live_bytes.saturating_sub(bytes);
+    }
+
+    pub fn allocated(&self, buffer: &str) -> usize {
+        self.allocations
+            .iter()
+            .filter(|(name, _)| *name == buffer)
+            .map(|(_, bytes)| *bytes)
+            .sum()
+    }
+}
+
+impl Recorder for ProverMetrics {
+    fn allocate(&mut self, buffer: &'static str, bytes: usize) {
+        ProverMetrics::allocate(self, buffer, bytes)
+    }
+
+    fn release(&mut self, bytes: usize) {
+        ProverMetrics::release(self, bytes)
+    }
+
+    fn time<R>(&mut self, phase: &'static str, f: impl FnOnce() -> R) -> R {
+        ProverMetrics::time(self, phase, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_test() {
+        let mut metrics = ProverMetrics::new();
+        metrics.record("a", Duration::from_millis(3));
+        metrics.record("b", Duration::from_millis(5));
+        metrics.record("a", Duration::from_millis(7));
+        assert_eq!(metrics.duration("a"), Duration::from_millis(10));
+        assert_eq!(metrics.duration("b"), Duration::from_millis(5));
+        assert_eq!(metrics.duration("c"), Duration::ZERO);
+        assert_eq!(metrics.total(), Duration::from_millis(15));
+
+        let value = metrics.time("c", || 42);
+        assert_eq!(value, 42);
+        assert_eq!(metrics.phases.len(), 4);
+        assert_eq!(metrics.phases[3].0, "c");
+
+        metrics.allocate("codewords", 100);
+        metrics.allocate("merkle_layers", 60);
+        metrics.release(60);
+        metrics.allocate("codewords", 50);
+        assert_eq!(metrics.allocated("codewords"), 150);
+        assert_eq!(metrics.allocated("merkle_layers"), 60);
+        assert_eq!(metrics.live_bytes, 150);
+        assert_eq!(metrics.peak_bytes, 160);
+    }
+}
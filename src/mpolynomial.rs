@@ -1,19 +1,54 @@
 use primitive_types::U256;
 
-use crate::{element::FieldElement, field::Field, polynomial::Polynomial, ONE, ZERO};
+use crate::{
+    element::FieldElement,
+    field::{FiniteField, FiniteFieldElement},
+    fri::FRI,
+    polynomial::Polynomial,
+    Error, Result, ONE, ZERO,
+};
 use std::{collections::HashMap, vec};
 
+pub struct SymbolicContext<'a, E: FiniteFieldElement = FieldElement> {
+    point: &'a [Polynomial<E>],
+    powers: HashMap<(usize, U256), Polynomial<E>>,
+}
+
+impl<'a, E: FiniteFieldElement> SymbolicContext<'a, E> {
+    pub fn new(point: &'a [Polynomial<E>]) -> Self {
+        SymbolicContext {
+            point,
+            powers: HashMap::new(),
+        }
+    }
+
+    pub fn power(&mut self, variable: usize, exponent: U256) -> &Polynomial<E> {
+        let point = self.point;
+        self.powers
+            .entry((variable, exponent))
+            .or_insert_with(|| &point[variable] ^ exponent)
+    }
+
+    pub fn len(&self) -> usize {
+        self.powers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.powers.is_empty()
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
-pub struct MPolynomial {
-    pub coefficients: HashMap<Vec<U256>, FieldElement>,
+pub struct MPolynomial<E: FiniteFieldElement = FieldElement> {
+    pub coefficients: HashMap<Vec<U256>, E>,
 }
 
-impl MPolynomial {
-    pub fn new(coefficients: HashMap<Vec<U256>, FieldElement>) -> Self {
+impl<E: FiniteFieldElement> MPolynomial<E> {
+    pub fn new(coefficients: HashMap<Vec<U256>, E>) -> Self {
         MPolynomial { coefficients }
     }
 
-    pub fn constant(element: FieldElement) -> Self {
+    pub fn constant(element: E) -> Self {
         let mut map = HashMap::new();
         map.insert(vec![ZERO], element);
         MPolynomial::new(map)
@@ -21,20 +56,18 @@ impl MPolynomial {
 
     pub fn is_zero(&self) -> bool {
         if self.coefficients.is_empty() {
-            return true;
+            true
         } else {
             self.coefficients.values().all(|v| v.is_zero())
         }
     }
 
-    pub fn variables(num_variables: usize, field: &Field) -> Vec<MPolynomial> {
+    pub fn variables<F: FiniteField<Element = E>>(num_variables: usize, field: &F) -> Vec<Self> {
         let mut variables = vec![];
         for i in 0..num_variables {
             let mut exponent = vec![ZERO; i];
             exponent.push(ONE);
-            for _ in 0..(num_variables - i - 1) {
-                exponent.push(ZERO);
-            }
+            exponent.resize(num_variables, ZERO);
             let mut map = HashMap::new();
             map.insert(exponent, field.one());
             variables.push(MPolynomial::new(map))
@@ -42,12 +75,12 @@ impl MPolynomial {
         variables
     }
 
-    pub fn lift(polynomial: &Polynomial, variable_index: usize) -> Self {
+    pub fn lift(polynomial: &Polynomial<E>, variable_index: usize) -> Self {
         let map = HashMap::new();
         if polynomial.is_zero() {
             return MPolynomial::new(map);
         }
-        let field = polynomial.coefficients[0].field;
+        let field = polynomial.coefficients[0].field();
         let variables = MPolynomial::variables(variable_index + 1, &field);
         let x = variables.last().unwrap();
         let mut acc = MPolynomial::new(map);
@@ -61,35 +94,183 @@ impl MPolynomial {
         acc
     }
 
-    pub fn evaluate(&self, point: &Vec<FieldElement>) -> FieldElement {
-        let mut acc = point[0].field.zero();
+    pub fn evaluate(&self, point: &[E]) -> E {
+        let mut acc = point[0].field().zero();
         self.coefficients.iter().for_each(|(k, v)| {
             let mut prod = *v;
             for i in 0..k.len() {
-                prod = &prod * &(&point[i] ^ k[i]);
+                prod *= point[i].pow(k[i]);
             }
-            acc = &acc + &prod;
+            acc += prod;
         });
         acc
     }
 
-    pub fn evaluate_symbolic(&self, point: &Vec<Polynomial>) -> Polynomial {
+    pub fn evaluate_over_domain(&self, columns: &[Vec<E>]) -> Result<Vec<E>> {
+        let num_rows = self.check_columns(columns)?;
+        Ok(self.evaluate_rows(columns, 0..num_rows))
+    }
+
+    pub fn evaluate_over_domain_parallel(
+        &self,
+        columns: &[Vec<E>],
+        threads: usize,
+    ) -> Result<Vec<E>> {
+        let num_rows = self.check_columns(columns)?;
+        let chunk = num_rows.div_ceil(threads.max(1)).max(1);
+        Ok(std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..num_rows)
+                .step_by(chunk)
+                .map(|start| {
+                    let end = (start + chunk).min(num_rows);
+                    scope.spawn(move || self.evaluate_rows(columns, start..end))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        }))
+    }
+
+    fn check_columns(&self, columns: &[Vec<E>]) -> Result<usize> {
+        let num_variables = self.coefficients.keys().map(Vec::len).max().unwrap_or(0);
+        if columns.len() < num_variables {
+            return Err(Error::LengthMismatch {
+                expected: num_variables,
+                actual: columns.len(),
+            });
+        }
+        let num_rows = columns.first().map_or(0, Vec::len);
+        if let Some(column) = columns.iter().find(|column| column.len() != num_rows) {
+            return Err(Error::LengthMismatch {
+                expected: num_rows,
+                actual: column.len(),
+            });
+        }
+        Ok(num_rows)
+    }
+
+    fn evaluate_rows(&self, columns: &[Vec<E>], rows: std::ops::Range<usize>) -> Vec<E> {
+        let terms: Vec<(Vec<(usize, U256)>, E)> = self
+            .coefficients
+            .iter()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(k, v)| {
+                let factors = k
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| !e.is_zero())
+                    .map(|(i, e)| (i, *e))
+                    .collect();
+                (factors, *v)
+            })
+            .collect();
+        rows.map(|row| {
+            let field = columns[0][row].field();
+            field.sum_of_products(terms.iter().map(|(factors, coefficient)| {
+                let monomial = factors.iter().fold(field.one(), |prod, (i, exponent)| {
+                    prod * columns[*i][row].pow(*exponent)
+                });
+                (*coefficient, monomial)
+            }))
+        })
+        .collect()
+    }
+
+    pub fn evaluate_symbolic(&self, point: &[Polynomial<E>]) -> Polynomial<E> {
+        self.evaluate_symbolic_with(&mut SymbolicContext::new(point))
+    }
+
+    pub fn evaluate_symbolic_with(&self, context: &mut SymbolicContext<E>) -> Polynomial<E> {
         let mut acc = Polynomial::new(vec![]);
         self.coefficients.iter().for_each(|(k, v)| {
             let mut prod = Polynomial::new(vec![*v]);
-            for i in 0..k.len() {
-                prod = &prod * &(&point[i] ^ k[i]);
+            for (i, exponent) in k.iter().enumerate() {
+                prod = &prod * context.power(i, *exponent);
             }
             acc = &acc + &prod;
         });
         acc
     }
+
+    /// Degree of the constraint once each variable is replaced by a polynomial
+    /// of the given degree, or `None` if that degree overflows `usize`.
+    pub fn degree_bound(&self, variable_degrees: &[usize]) -> Option<usize> {
+        self.coefficients
+            .iter()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(k, _)| {
+                k.iter()
+                    .zip(variable_degrees.iter())
+                    .filter(|(e, d)| !e.is_zero() && **d != 0)
+                    .try_fold(0usize, |acc, (e, d)| {
+                        let e = usize::try_from(*e).ok()?;
+                        acc.checked_add(e.checked_mul(*d)?)
+                    })
+            })
+            .try_fold(0, |max, degree| Some(max.max(degree?)))
+    }
+
+    pub fn max_degree_bound(
+        constraints: &[MPolynomial<E>],
+        variable_degrees: &[usize],
+    ) -> Option<usize> {
+        constraints
+            .iter()
+            .try_fold(0, |max, c| Some(max.max(c.degree_bound(variable_degrees)?)))
+    }
 }
 
-impl std::ops::Add<&MPolynomial> for &MPolynomial {
-    type Output = MPolynomial;
+/// Degrees the prover and verifier both derive from the transition constraints
+/// and the trace length, so they cannot disagree about them.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DegreeLayout {
+    pub quotient_degrees: Vec<usize>,
+    pub max_degree: usize,
+    pub adjustment_exponents: Vec<usize>,
+    pub fri_domain_length: usize,
+}
 
-    fn add(self, rhs: &MPolynomial) -> MPolynomial {
+impl DegreeLayout {
+    /// Constraints read the current and next row, `2 * num_registers`
+    /// variables interpolated to degree `trace_length - 1`, and are divided by
+    /// a transition zerofier of that same degree.
+    pub fn new<E: FiniteFieldElement>(
+        constraints: &[MPolynomial<E>],
+        num_registers: usize,
+        trace_length: usize,
+        expansion_factor: usize,
+    ) -> Result<Self> {
+        let overflow = || Error::InvalidParameters("constraint degree overflows usize".to_string());
+        let trace_degree = trace_length
+            .checked_sub(1)
+            .ok_or(Error::BadDomainSize(trace_length))?;
+        let variable_degrees = vec![trace_degree; 2 * num_registers];
+        let quotient_degrees = constraints
+            .iter()
+            .map(|constraint| {
+                let composed = constraint
+                    .degree_bound(&variable_degrees)
+                    .ok_or_else(overflow)?;
+                Ok(composed.saturating_sub(trace_degree))
+            })
+            .collect::<Result<Vec<usize>>>()?;
+        let max_degree = quotient_degrees.iter().copied().max().unwrap_or(0);
+        Ok(DegreeLayout {
+            adjustment_exponents: quotient_degrees.iter().map(|d| max_degree - d).collect(),
+            fri_domain_length: FRI::domain_length_for_degree(max_degree, expansion_factor)
+                .ok_or_else(overflow)?,
+            quotient_degrees,
+            max_degree,
+        })
+    }
+}
+
+impl<E: FiniteFieldElement> std::ops::Add<&MPolynomial<E>> for &MPolynomial<E> {
+    type Output = MPolynomial<E>;
+
+    fn add(self, rhs: &MPolynomial<E>) -> MPolynomial<E> {
         let mut map = HashMap::new();
         let self_keys = self
             .coefficients
@@ -107,18 +288,14 @@ impl std::ops::Add<&MPolynomial> for &MPolynomial {
 
         self.coefficients.iter().for_each(|e| {
             let mut v = e.0.clone();
-            for _ in 0..(num_variables - e.0.len()) {
-                v.push(ZERO);
-            }
+            v.resize(num_variables, ZERO);
             map.insert(v, *e.1);
         });
         rhs.coefficients.iter().for_each(|e| {
             let mut v = e.0.clone();
-            for _ in 0..(num_variables - e.0.len()) {
-                v.push(ZERO);
-            }
+            v.resize(num_variables, ZERO);
             if map.contains_key(&v) {
-                let element = &map[&v] + e.1;
+                let element = map[&v] + *e.1;
                 map.insert(v, element);
             } else {
                 map.insert(v, *e.1);
@@ -129,30 +306,30 @@ impl std::ops::Add<&MPolynomial> for &MPolynomial {
     }
 }
 
-impl std::ops::Neg for &MPolynomial {
-    type Output = MPolynomial;
+impl<E: FiniteFieldElement> std::ops::Neg for &MPolynomial<E> {
+    type Output = MPolynomial<E>;
 
-    fn neg(self) -> MPolynomial {
+    fn neg(self) -> MPolynomial<E> {
         let mut map = HashMap::new();
         self.coefficients.iter().for_each(|e| {
-            map.insert(e.0.clone(), -e.1);
+            map.insert(e.0.clone(), -*e.1);
         });
         MPolynomial::new(map)
     }
 }
 
-impl std::ops::Sub<&MPolynomial> for &MPolynomial {
-    type Output = MPolynomial;
+impl<E: FiniteFieldElement> std::ops::Sub<&MPolynomial<E>> for &MPolynomial<E> {
+    type Output = MPolynomial<E>;
 
-    fn sub(self, rhs: &MPolynomial) -> MPolynomial {
+    fn sub(self, rhs: &MPolynomial<E>) -> MPolynomial<E> {
         self + &(-rhs)
     }
 }
 
-impl std::ops::Mul<&MPolynomial> for &MPolynomial {
-    type Output = MPolynomial;
+impl<E: FiniteFieldElement> std::ops::Mul<&MPolynomial<E>> for &MPolynomial<E> {
+    type Output = MPolynomial<E>;
 
-    fn mul(self, rhs: &MPolynomial) -> MPolynomial {
+    fn mul(self, rhs: &MPolynomial<E>) -> MPolynomial<E> {
         let mut map = HashMap::new();
         let self_keys = self
             .coefficients
@@ -177,10 +354,10 @@ impl std::ops::Mul<&MPolynomial> for &MPolynomial {
                     exponent[i] += k1[i];
                 }
                 if map.contains_key(&exponent) {
-                    let element = &map[&exponent] + &(v0 * v1);
+                    let element = map[&exponent] + (*v0 * *v1);
                     map.insert(exponent, element);
                 } else {
-                    map.insert(exponent, v0 * v1);
+                    map.insert(exponent, *v0 * *v1);
                 }
             });
         });
@@ -188,15 +365,15 @@ impl std::ops::Mul<&MPolynomial> for &MPolynomial {
     }
 }
 
-impl std::ops::BitXor<U256> for &MPolynomial {
-    type Output = MPolynomial;
+impl<E: FiniteFieldElement> std::ops::BitXor<U256> for &MPolynomial<E> {
+    type Output = MPolynomial<E>;
 
-    fn bitxor(self, rhs: U256) -> MPolynomial {
+    fn bitxor(self, rhs: U256) -> MPolynomial<E> {
         let mut map = HashMap::new();
         if self.is_zero() {
             return MPolynomial::new(map);
         }
-        let field = self.coefficients.values().nth(0).unwrap().field;
+        let field = self.coefficients.values().nth(0).unwrap().field();
         let num_variables = self.coefficients.keys().nth(0).unwrap().len();
         let exp = vec![ZERO; num_variables];
 
@@ -216,7 +393,7 @@ impl std::ops::BitXor<U256> for &MPolynomial {
             i -= ONE;
             acc = &acc * &acc;
             if (rhs >> i) & ONE == ONE {
-                acc = &acc * &self;
+                acc = &acc * self;
             }
         }
 
@@ -238,7 +415,7 @@ mod tests {
         coefficients.insert(vec![ZERO, ZERO], f.zero());
 
         let mp = MPolynomial::new(coefficients);
-        assert_eq!(mp.is_zero(), false);
+        assert!(!mp.is_zero());
         assert_eq!(*mp.coefficients.get(&vec![*TWO, ONE]).unwrap(), f.one());
         assert_eq!(
             *mp.coefficients.get(&vec![ONE, *TWO]).unwrap(),
@@ -247,11 +424,11 @@ mod tests {
         assert_eq!(*mp.coefficients.get(&vec![ZERO, ZERO]).unwrap(), f.zero());
 
         let cp = MPolynomial::constant(f.one());
-        assert_eq!(cp.is_zero(), false);
+        assert!(!cp.is_zero());
         assert_eq!(*cp.coefficients.get(&vec![ZERO]).unwrap(), f.one());
 
         let zp = MPolynomial::constant(f.zero());
-        assert_eq!(zp.is_zero(), true);
+        assert!(zp.is_zero());
 
         let vars = MPolynomial::variables(3, &f);
         assert_eq!(vars.len(), 3);
@@ -259,7 +436,7 @@ mod tests {
             if v.coefficients.keys().len() != 1 {
                 return false;
             }
-            let k = v.coefficients.keys().nth(0).unwrap();
+            let k = v.coefficients.keys().next().unwrap();
             let mut expected_k = vec![ZERO; 3];
             expected_k[i] = ONE;
             *k == expected_k && *v.coefficients.get(k).unwrap() == f.one()
@@ -295,11 +472,11 @@ mod tests {
         assert_eq!(sum2.coefficients.keys().len(), 3);
         assert_eq!(
             *sum2.coefficients.get(&vec![ONE, *TWO]).unwrap(),
-            &f.generator() * &FieldElement::new(*TWO, f)
+            f.generator() * FieldElement::new(*TWO, f)
         );
         assert_eq!(
             *sum2.coefficients.get(&vec![*TWO, ONE]).unwrap(),
-            &f.one() * &FieldElement::new(*TWO, f)
+            f.one() * FieldElement::new(*TWO, f)
         );
         assert_eq!(
             *sum2.coefficients.get(&vec![ZERO, ZERO]).unwrap(),
@@ -312,7 +489,7 @@ mod tests {
         assert_eq!(*mul.coefficients.get(&vec![four, *TWO]).unwrap(), f.one());
         assert_eq!(
             *mul.coefficients.get(&vec![three, three]).unwrap(),
-            &f.generator() * &FieldElement::new(*TWO, f)
+            f.generator() * FieldElement::new(*TWO, f)
         );
         assert_eq!(
             *mul.coefficients.get(&vec![*TWO, ONE]).unwrap(),
@@ -320,11 +497,11 @@ mod tests {
         );
         assert_eq!(
             *mul.coefficients.get(&vec![*TWO, four]).unwrap(),
-            &f.generator() ^ *TWO
+            f.generator() ^ *TWO
         );
         assert_eq!(
             *mul.coefficients.get(&vec![ONE, *TWO]).unwrap(),
-            &f.generator() * &FieldElement::new(four, f)
+            f.generator() * FieldElement::new(four, f)
         );
         assert_eq!(
             *mul.coefficients.get(&vec![ZERO, ZERO]).unwrap(),
@@ -343,7 +520,7 @@ mod tests {
         assert_eq!(*sub.coefficients.get(&vec![four, *TWO]).unwrap(), f.one());
         assert_eq!(
             *sub.coefficients.get(&vec![three, three]).unwrap(),
-            &f.generator() * &FieldElement::new(*TWO, f)
+            f.generator() * FieldElement::new(*TWO, f)
         );
         assert_eq!(
             *sub.coefficients.get(&vec![*TWO, ONE]).unwrap(),
@@ -351,11 +528,11 @@ mod tests {
         );
         assert_eq!(
             *sub.coefficients.get(&vec![*TWO, four]).unwrap(),
-            &f.generator() ^ *TWO
+            f.generator() ^ *TWO
         );
         assert_eq!(
             *sub.coefficients.get(&vec![ONE, *TWO]).unwrap(),
-            &f.generator() * &FieldElement::new(three, f)
+            f.generator() * FieldElement::new(three, f)
         );
         assert_eq!(
             *sub.coefficients.get(&vec![ZERO, ZERO]).unwrap(),
@@ -363,6 +540,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn degree_bound_test() {
+        let f = Field::new(*PRIME);
+        let mut coefficients = HashMap::new();
+        coefficients.insert(vec![*TWO, ONE], f.one());
+        coefficients.insert(vec![ONE, *TWO], f.generator());
+        coefficients.insert(vec![ZERO, 4.into()], f.zero());
+        let mp = MPolynomial::new(coefficients);
+
+        assert_eq!(mp.degree_bound(&[1, 1]), Some(3));
+        assert_eq!(mp.degree_bound(&[1, 7]), Some(15));
+        assert_eq!(mp.degree_bound(&[7, 1]), Some(15));
+        assert_eq!(mp.degree_bound(&[5, 2]), Some(12));
+        assert_eq!(mp.degree_bound(&[usize::MAX, 1]), None);
+
+        let cp = MPolynomial::constant(f.one());
+        assert_eq!(cp.degree_bound(&[5, 2]), Some(0));
+        assert_eq!(
+            MPolynomial::max_degree_bound(&[mp.clone(), cp], &[5, 2]),
+            Some(12)
+        );
+        assert_eq!(
+            MPolynomial::<FieldElement>::max_degree_bound(&[], &[5, 2]),
+            Some(0)
+        );
+
+        let mut coefficients = HashMap::new();
+        coefficients.insert(vec![U256::MAX], f.one());
+        let huge = MPolynomial::new(coefficients);
+        assert_eq!(huge.degree_bound(&[2]), None);
+        assert_eq!(huge.degree_bound(&[0]), Some(0));
+        assert_eq!(MPolynomial::max_degree_bound(&[mp, huge], &[2, 2]), None);
+
+        let poly0 = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        let poly1 = Polynomial::new(vec![f.generator(), f.one()]);
+        let mut coefficients = HashMap::new();
+        coefficients.insert(vec![*TWO, ONE], f.one());
+        let mp = MPolynomial::new(coefficients);
+        assert_eq!(
+            mp.evaluate_symbolic(&[poly0, poly1]).degree(),
+            mp.degree_bound(&[2, 1]).unwrap() as i32
+        );
+    }
+
+    #[test]
+    fn degree_layout_test() {
+        let f = Field::new(*PRIME);
+        let v = MPolynomial::variables(4, &f);
+        // Fibonacci: one linear and one quadratic transition over two registers.
+        let constraints = vec![&v[2] - &v[1], &(&v[3] * &v[3]) - &(&v[0] * &v[1])];
+        let layout = DegreeLayout::new(&constraints, 2, 8, 4).unwrap();
+        assert_eq!(layout.quotient_degrees, vec![0, 7]);
+        assert_eq!(layout.max_degree, 7);
+        assert_eq!(layout.adjustment_exponents, vec![7, 0]);
+        assert_eq!(layout.fri_domain_length, 32);
+
+        assert!(matches!(
+            DegreeLayout::new(&constraints, 2, 0, 4),
+            Err(Error::BadDomainSize(0))
+        ));
+        assert!(matches!(
+            DegreeLayout::new(&constraints, 2, usize::MAX, 4),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+
     #[test]
     fn lift_test() {
         let f = Field::new(*PRIME);
@@ -377,6 +620,65 @@ mod tests {
         assert_eq!(lifted_expected, lifted);
     }
 
+    #[test]
+    fn small_field_test() {
+        use crate::field::mersenne31::{Mersenne31, MODULUS};
+
+        let f = Mersenne31;
+        let [x, y] = <[MPolynomial<_>; 2]>::try_from(MPolynomial::variables(2, &f)).unwrap();
+        let constraint = &(&x * &y) - &(&x ^ *TWO);
+        let point = [f.element(5), f.element(MODULUS as u64 - 3)];
+        assert_eq!(constraint.evaluate(&point), -f.element(40));
+        let columns = vec![vec![point[0], f.one()], vec![point[1], f.one()]];
+        assert_eq!(
+            constraint.evaluate_over_domain(&columns).unwrap(),
+            vec![constraint.evaluate(&point), f.zero()]
+        );
+    }
+
+    #[test]
+    fn evaluate_over_domain_test() {
+        let f = Field::new(*PRIME);
+        let v = MPolynomial::variables(3, &f);
+        let constraint =
+            &(&(&v[0] * &v[1]) - &(&v[2] ^ 3.into())) + &MPolynomial::constant(f.one());
+        let columns: Vec<Vec<FieldElement>> = (0..3)
+            .map(|c| f.powers(&FieldElement::new((c + 2).into(), f), 37))
+            .collect();
+
+        let evaluated = constraint.evaluate_over_domain(&columns).unwrap();
+        assert_eq!(evaluated.len(), 37);
+        for (row, value) in evaluated.iter().enumerate() {
+            let point: Vec<FieldElement> = columns.iter().map(|column| column[row]).collect();
+            assert_eq!(*value, constraint.evaluate(&point));
+        }
+        for threads in [1, 4, 64] {
+            assert_eq!(
+                constraint
+                    .evaluate_over_domain_parallel(&columns, threads)
+                    .unwrap(),
+                evaluated
+            );
+        }
+
+        assert!(matches!(
+            constraint.evaluate_over_domain(&columns[..2]),
+            Err(Error::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        let mut ragged = columns.clone();
+        ragged[1].pop();
+        assert!(matches!(
+            constraint.evaluate_over_domain(&ragged),
+            Err(Error::LengthMismatch {
+                expected: 37,
+                actual: 36
+            })
+        ));
+    }
+
     #[test]
     fn evaluate_test() {
         let f = Field::new(*PRIME);
@@ -388,14 +690,14 @@ mod tests {
         let mp = MPolynomial::new(coefficients);
 
         assert_eq!(
-            mp.evaluate(&vec![f.one(), f.generator(), f.zero()]),
+            mp.evaluate(&[f.one(), f.generator(), f.zero()]),
             FieldElement::new(*TWO, f)
         );
         assert_eq!(
-            mp.evaluate(&vec![f.one(), f.generator(), f.generator()]),
-            &(&(&(&f.generator() ^ 2.into()) + &(&f.generator() ^ 4.into()))
-                + &(&(&f.generator() ^ *TWO) * &FieldElement::new(*TWO, f)))
-                + &FieldElement::new(*TWO, f)
+            mp.evaluate(&[f.one(), f.generator(), f.generator()]),
+            (((f.generator() ^ 2.into()) + (f.generator() ^ 4.into()))
+                + ((f.generator() ^ *TWO) * FieldElement::new(*TWO, f)))
+                + FieldElement::new(*TWO, f)
         );
 
         let mut coefficients = HashMap::new();
@@ -412,13 +714,27 @@ mod tests {
             mp.evaluate_symbolic(&polys),
             Polynomial::new(vec![
                 FieldElement::new(*TWO, f),
-                FieldElement::new(4.into(), f),
-                &(&FieldElement::new(6.into(), f) * &f.generator()) + &FieldElement::new(*TWO, f),
-                &(&(&f.generator() ^ 2.into()) * &FieldElement::new(*TWO, f))
-                    + &FieldElement::new(4.into(), f),
-                &f.generator() * &FieldElement::new(3.into(), f),
+                f.element(4),
+                (f.element(6) * f.generator()) + FieldElement::new(*TWO, f),
+                ((f.generator() ^ 2.into()) * FieldElement::new(*TWO, f)) + f.element(4),
+                f.generator() * f.element(3),
                 f.one()
             ])
         );
+
+        let mut context = SymbolicContext::new(&polys);
+        let mut coefficients = HashMap::new();
+        coefficients.insert(vec![*TWO, *TWO], f.one());
+        let other = MPolynomial::new(coefficients);
+        assert_eq!(
+            mp.evaluate_symbolic_with(&mut context),
+            mp.evaluate_symbolic(&polys)
+        );
+        let cached = context.len();
+        assert_eq!(
+            other.evaluate_symbolic_with(&mut context),
+            other.evaluate_symbolic(&polys)
+        );
+        assert_eq!(context.len(), cached);
     }
 }
@@ -0,0 +1,870 @@
+use crate::{
+    domain::{evaluate_vanishing, Coset},
+    element::FieldElement,
+    field::{FiniteField, FiniteFieldElement},
+    mpolynomial::MPolynomial,
+    polynomial::{divide, multiply, Polynomial},
+    Error, Result,
+};
+use std::collections::HashSet;
+
+pub(crate) fn primitive_root<F: FiniteField>(field: F, order: usize) -> Option<F::Element> {
+    if !order.is_power_of_two() {
+        return None;
+    }
+    field.nth_root(order.into())
+}
+
+fn check_primitive_root<E: FiniteFieldElement>(
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<()> {
+    let one = primitive_root.field().one();
+    if root_order < 2
+        || !root_order.is_power_of_two()
+        || primitive_root.pow(root_order.into()) != one
+        || primitive_root.pow((root_order / 2).into()) == one
+    {
+        return Err(Error::BadDomainSize(root_order));
+    }
+    Ok(())
+}
+
+pub fn ntt<E: FiniteFieldElement>(primitive_root: &E, values: &[E]) -> Result<Vec<E>> {
+    let len = values.len();
+    if len & (len.wrapping_sub(1)) != 0 {
+        return Err(Error::BadDomainSize(len));
+    }
+    Ok(transform(primitive_root, values))
+}
+
+fn transform<E: FiniteFieldElement>(primitive_root: &E, values: &[E]) -> Vec<E> {
+    let len = values.len();
+    if len <= 1 {
+        return values.to_vec();
+    }
+
+    let half = len / 2;
+    let square = *primitive_root * *primitive_root;
+    let evens: Vec<E> = values.iter().step_by(2).copied().collect();
+    let odds: Vec<E> = values.iter().skip(1).step_by(2).copied().collect();
+    let evens = transform(&square, &evens);
+    let odds = transform(&square, &odds);
+
+    let mut power = primitive_root.field().one();
+    let mut transformed = Vec::with_capacity(len);
+    for i in 0..len {
+        transformed.push(evens[i % half] + (power * odds[i % half]));
+        power *= *primitive_root;
+    }
+    transformed
+}
+
+pub fn intt<E: FiniteFieldElement>(primitive_root: &E, values: &[E]) -> Result<Vec<E>> {
+    let len = values.len();
+    if len & (len.wrapping_sub(1)) != 0 {
+        return Err(Error::BadDomainSize(len));
+    }
+    if primitive_root.is_zero() {
+        return Err(Error::DivisionByZero);
+    }
+    Ok(inverse_transform(primitive_root, values))
+}
+
+fn inverse_transform<E: FiniteFieldElement>(primitive_root: &E, values: &[E]) -> Vec<E> {
+    if values.len() <= 1 {
+        return values.to_vec();
+    }
+
+    let field = primitive_root.field();
+    let ninv = field.integer(values.len()).inv();
+    transform(&primitive_root.inv(), values)
+        .iter()
+        .map(|v| ninv * *v)
+        .collect()
+}
+
+fn padded_coefficients<E: FiniteFieldElement>(polynomial: &Polynomial<E>, order: usize) -> Vec<E> {
+    let field = polynomial.coefficients[0].field();
+    let degree: usize = polynomial.degree().try_into().unwrap();
+    let mut coefficients = polynomial.coefficients[0..=degree].to_vec();
+    coefficients.resize(order, field.zero());
+    coefficients
+}
+
+pub fn fast_multiply<E: FiniteFieldElement>(
+    lhs: &Polynomial<E>,
+    rhs: &Polynomial<E>,
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<Polynomial<E>> {
+    check_primitive_root(primitive_root, root_order)?;
+    // A product with degree >= root_order would wrap around the NTT.
+    if i64::from(lhs.degree() + rhs.degree()) >= root_order as i64 {
+        return Err(Error::BadDomainSize(root_order));
+    }
+    Ok(multiply_with_root(lhs, rhs, primitive_root, root_order))
+}
+
+// The public entry points validate the root and the degree bound once; the
+// recursive helpers below trust them.
+fn multiply_with_root<E: FiniteFieldElement>(
+    lhs: &Polynomial<E>,
+    rhs: &Polynomial<E>,
+    primitive_root: &E,
+    root_order: usize,
+) -> Polynomial<E> {
+    if lhs.is_zero() || rhs.is_zero() {
+        return Polynomial::new(vec![]);
+    }
+
+    let degree: usize = (lhs.degree() + rhs.degree()).try_into().unwrap();
+    debug_assert!(degree < root_order);
+    if degree < 8 {
+        return multiply(lhs, rhs);
+    }
+
+    let mut root = *primitive_root;
+    let mut order = root_order;
+    while degree < order / 2 {
+        root = root * root;
+        order /= 2;
+    }
+
+    let lhs_codeword = transform(&root, &padded_coefficients(lhs, order));
+    let rhs_codeword = transform(&root, &padded_coefficients(rhs, order));
+    let hadamard_product: Vec<E> = lhs_codeword
+        .iter()
+        .zip(rhs_codeword.iter())
+        .map(|(l, r)| *l * *r)
+        .collect();
+
+    let mut product_coefficients = inverse_transform(&root, &hadamard_product);
+    product_coefficients.truncate(degree + 1);
+    Polynomial::new(product_coefficients)
+}
+
+// Every product in a zerofier tree over `domain` has degree at most its length.
+fn check_domain<E: FiniteFieldElement>(domain: &[E], root_order: usize) -> Result<()> {
+    if domain.len() >= root_order {
+        return Err(Error::BadDomainSize(root_order));
+    }
+    Ok(())
+}
+
+pub fn fast_zerofier<E: FiniteFieldElement>(
+    domain: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<Polynomial<E>> {
+    check_primitive_root(primitive_root, root_order)?;
+    check_domain(domain, root_order)?;
+    Ok(zerofier(domain, primitive_root, root_order))
+}
+
+fn zerofier<E: FiniteFieldElement>(
+    domain: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Polynomial<E> {
+    if domain.is_empty() {
+        return Polynomial::new(vec![]);
+    }
+    if domain.len() == 1 {
+        return Polynomial::new(vec![-domain[0], primitive_root.field().one()]);
+    }
+
+    let half = domain.len() / 2;
+    let left = zerofier(&domain[..half], primitive_root, root_order);
+    let right = zerofier(&domain[half..], primitive_root, root_order);
+    multiply_with_root(&left, &right, primitive_root, root_order)
+}
+
+pub fn fast_evaluate<E: FiniteFieldElement>(
+    polynomial: &Polynomial<E>,
+    domain: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<Vec<E>> {
+    check_primitive_root(primitive_root, root_order)?;
+    check_domain(domain, root_order)?;
+    Ok(evaluate(polynomial, domain, primitive_root, root_order))
+}
+
+fn evaluate<E: FiniteFieldElement>(
+    polynomial: &Polynomial<E>,
+    domain: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Vec<E> {
+    if domain.is_empty() {
+        return vec![];
+    }
+    let tree = ZerofierTree::new(domain, primitive_root, root_order);
+    let mut values = Vec::with_capacity(domain.len());
+    tree.evaluate(&polynomial.coefficients, &mut values);
+    values
+}
+
+// Zerofiers of the halves, quarters, ... of a domain, each computed once so
+// that evaluation only has to walk down the tree taking remainders.
+struct ZerofierTree<E: FiniteFieldElement> {
+    zerofier: Polynomial<E>,
+    children: Option<Box<[ZerofierTree<E>; 2]>>,
+}
+
+impl<E: FiniteFieldElement> ZerofierTree<E> {
+    fn new(domain: &[E], primitive_root: &E, root_order: usize) -> Self {
+        if domain.len() == 1 {
+            return ZerofierTree {
+                zerofier: Polynomial::new(vec![-domain[0], primitive_root.field().one()]),
+                children: None,
+            };
+        }
+        let half = domain.len() / 2;
+        let left = ZerofierTree::new(&domain[..half], primitive_root, root_order);
+        let right = ZerofierTree::new(&domain[half..], primitive_root, root_order);
+        ZerofierTree {
+            zerofier: multiply_with_root(
+                &left.zerofier,
+                &right.zerofier,
+                primitive_root,
+                root_order,
+            ),
+            children: Some(Box::new([left, right])),
+        }
+    }
+
+    fn evaluate(&self, coefficients: &[E], values: &mut Vec<E>) {
+        let remainder = reduce_monic(coefficients, &self.zerofier);
+        match &self.children {
+            None => values.push(
+                remainder
+                    .first()
+                    .copied()
+                    .unwrap_or(self.zerofier.coefficients[1].field().zero()),
+            ),
+            Some(children) => {
+                for child in children.iter() {
+                    child.evaluate(&remainder, values);
+                }
+            }
+        }
+    }
+}
+
+// Below this many quotient coefficients, or for moduli of lower degree, long
+// division beats the NTT products of a Newton inverse.
+const NEWTON_DIVISION: usize = 1024;
+
+// Remainder of `coefficients` modulo a monic polynomial. Large reductions take
+// the quotient from a Newton inverse of the reversed modulus, so each costs a
+// few NTT products and the tree walk stays quasi-linear; the rest use long
+// division, which needs no inverses because zerofiers are monic.
+fn reduce_monic<E: FiniteFieldElement>(coefficients: &[E], modulus: &Polynomial<E>) -> Vec<E> {
+    let degree = modulus.degree() as usize;
+    if coefficients.len() <= degree {
+        return coefficients.to_vec();
+    }
+    let quotient_len = coefficients.len() - degree;
+    if quotient_len.min(degree) >= NEWTON_DIVISION {
+        let order = (2 * quotient_len)
+            .max(coefficients.len())
+            .next_power_of_two();
+        if let Some(root) = primitive_root(modulus.coefficients[0].field(), order) {
+            return newton_reduce(coefficients, modulus, &root, order);
+        }
+    }
+    long_division(coefficients, modulus)
+}
+
+fn long_division<E: FiniteFieldElement>(coefficients: &[E], modulus: &Polynomial<E>) -> Vec<E> {
+    let degree = modulus.degree() as usize;
+    let mut remainder = coefficients.to_vec();
+    for i in (degree..remainder.len()).rev() {
+        let lead = remainder[i];
+        if lead.is_zero() {
+            continue;
+        }
+        for (j, coefficient) in modulus.coefficients[..degree].iter().enumerate() {
+            remainder[i - degree + j] -= lead * *coefficient;
+        }
+    }
+    remainder.truncate(degree);
+    remainder
+}
+
+// With n = len(f) and d = deg(m), the quotient reversed is
+// rev(f) * rev(m)^-1 mod x^(n - d); the remainder is then f - q * m, of which
+// only the low d coefficients survive. `order` must be at least 2 (n - d)
+// and n.
+fn newton_reduce<E: FiniteFieldElement>(
+    coefficients: &[E],
+    modulus: &Polynomial<E>,
+    primitive_root: &E,
+    order: usize,
+) -> Vec<E> {
+    let degree = modulus.degree() as usize;
+    let quotient_len = coefficients.len() - degree;
+    let reversed_modulus: Vec<E> = modulus.coefficients[..=degree]
+        .iter()
+        .rev()
+        .copied()
+        .collect();
+    let reversed_numerator: Vec<E> = coefficients
+        .iter()
+        .rev()
+        .take(quotient_len)
+        .copied()
+        .collect();
+
+    // Each Newton step g <- 2g - g^2 h doubles the precision of g = h^-1.
+    let field = primitive_root.field();
+    let two = field.one() + field.one();
+    let mut inverse = vec![field.one()];
+    while inverse.len() < quotient_len {
+        let precision = (2 * inverse.len()).min(quotient_len);
+        let head = &reversed_modulus[..precision.min(reversed_modulus.len())];
+        let error = truncated_product(head, &inverse, precision, primitive_root, order);
+        let correction = truncated_product(&inverse, &error, precision, primitive_root, order);
+        inverse.resize(precision, field.zero());
+        for (g, c) in inverse.iter_mut().zip(correction) {
+            *g = two * *g - c;
+        }
+    }
+
+    let mut quotient = truncated_product(
+        &reversed_numerator,
+        &inverse,
+        quotient_len,
+        primitive_root,
+        order,
+    );
+    quotient.reverse();
+    let product = truncated_product(
+        &quotient,
+        &modulus.coefficients[..degree],
+        degree,
+        primitive_root,
+        order,
+    );
+    coefficients[..degree]
+        .iter()
+        .zip(product)
+        .map(|(f, p)| *f - p)
+        .collect()
+}
+
+// The first `len` coefficients of `lhs * rhs`.
+fn truncated_product<E: FiniteFieldElement>(
+    lhs: &[E],
+    rhs: &[E],
+    len: usize,
+    primitive_root: &E,
+    order: usize,
+) -> Vec<E> {
+    let mut product = multiply_with_root(
+        &Polynomial::new(lhs.to_vec()),
+        &Polynomial::new(rhs.to_vec()),
+        primitive_root,
+        order,
+    )
+    .coefficients;
+    product.resize(len, primitive_root.field().zero());
+    product
+}
+
+pub fn fast_interpolate<E: FiniteFieldElement>(
+    domain: &[E],
+    values: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<Polynomial<E>> {
+    check_primitive_root(primitive_root, root_order)?;
+    check_domain(domain, root_order)?;
+    if domain.len() != values.len() {
+        return Err(Error::LengthMismatch {
+            expected: domain.len(),
+            actual: values.len(),
+        });
+    }
+    let mut seen = HashSet::with_capacity(domain.len());
+    if !domain.iter().all(|x| seen.insert(x.to_u256())) {
+        return Err(Error::DivisionByZero);
+    }
+    Ok(interpolate(domain, values, primitive_root, root_order))
+}
+
+fn interpolate<E: FiniteFieldElement>(
+    domain: &[E],
+    values: &[E],
+    primitive_root: &E,
+    root_order: usize,
+) -> Polynomial<E> {
+    if domain.is_empty() {
+        return Polynomial::new(vec![]);
+    }
+    if domain.len() == 1 {
+        return Polynomial::new(vec![values[0]]);
+    }
+
+    let half = domain.len() / 2;
+    let left_zerofier = zerofier(&domain[..half], primitive_root, root_order);
+    let right_zerofier = zerofier(&domain[half..], primitive_root, root_order);
+
+    let left_offset = evaluate(&right_zerofier, &domain[..half], primitive_root, root_order);
+    let right_offset = evaluate(&left_zerofier, &domain[half..], primitive_root, root_order);
+
+    let left_targets: Vec<E> = values[..half]
+        .iter()
+        .zip(left_offset.iter())
+        .map(|(n, d)| *n / *d)
+        .collect();
+    let right_targets: Vec<E> = values[half..]
+        .iter()
+        .zip(right_offset.iter())
+        .map(|(n, d)| *n / *d)
+        .collect();
+
+    let left_interpolant = interpolate(&domain[..half], &left_targets, primitive_root, root_order);
+    let right_interpolant =
+        interpolate(&domain[half..], &right_targets, primitive_root, root_order);
+
+    &multiply_with_root(
+        &left_interpolant,
+        &right_zerofier,
+        primitive_root,
+        root_order,
+    ) + &multiply_with_root(
+        &right_interpolant,
+        &left_zerofier,
+        primitive_root,
+        root_order,
+    )
+}
+
+pub fn fast_coset_evaluate<E: FiniteFieldElement>(
+    polynomial: &Polynomial<E>,
+    offset: &E,
+    generator: &E,
+    order: usize,
+) -> Result<Vec<E>> {
+    let mut coefficients = polynomial.scale(*offset).coefficients;
+    if coefficients.len() > order {
+        return Err(Error::BadDomainSize(order));
+    }
+    coefficients.resize(order, offset.field().zero());
+    ntt(generator, &coefficients)
+}
+
+pub fn fast_coset_divide<E: FiniteFieldElement>(
+    lhs: &Polynomial<E>,
+    rhs: &Polynomial<E>,
+    offset: &E,
+    primitive_root: &E,
+    root_order: usize,
+) -> Result<Polynomial<E>> {
+    check_primitive_root(primitive_root, root_order)?;
+    if rhs.is_zero() || offset.is_zero() {
+        return Err(Error::DivisionByZero);
+    }
+    if lhs.is_zero() {
+        return Ok(Polynomial::new(vec![]));
+    }
+    if rhs.degree() > lhs.degree() {
+        return Err(Error::InexactDivision);
+    }
+
+    let degree: usize = lhs.degree().try_into().unwrap();
+    if degree >= root_order {
+        return Err(Error::BadDomainSize(root_order));
+    }
+    if degree < 8 {
+        let (quotient, remainder) = divide(lhs, rhs)?;
+        if !remainder.is_zero() {
+            return Err(Error::InexactDivision);
+        }
+        return Ok(quotient);
+    }
+
+    let mut root = *primitive_root;
+    let mut order = root_order;
+    while degree < order / 2 {
+        root = root * root;
+        order /= 2;
+    }
+
+    let lhs_codeword = transform(&root, &padded_coefficients(&lhs.scale(*offset), order));
+    let rhs_codeword = transform(&root, &padded_coefficients(&rhs.scale(*offset), order));
+    // rhs may vanish somewhere on the coset, which no exact quotient survives.
+    let rhs_inverses = offset
+        .field()
+        .batch_inv(&rhs_codeword)
+        .ok_or(Error::DivisionByZero)?;
+    let quotient_codeword: Vec<E> = lhs_codeword
+        .iter()
+        .zip(rhs_inverses.iter())
+        .map(|(l, r)| *l * *r)
+        .collect();
+
+    let mut scaled_quotient_coefficients = inverse_transform(&root, &quotient_codeword);
+    // An exact quotient has degree lhs - rhs, so anything above it is remainder.
+    let quotient_len: usize = (lhs.degree() - rhs.degree() + 1).try_into().unwrap();
+    if !scaled_quotient_coefficients[quotient_len..]
+        .iter()
+        .all(E::is_zero)
+    {
+        return Err(Error::InexactDivision);
+    }
+    scaled_quotient_coefficients.truncate(quotient_len);
+    Ok(Polynomial::new(scaled_quotient_coefficients).scale(offset.inv()))
+}
+
+/// Evaluates each transition constraint pointwise over the `lde` coset and
+/// divides by the transition zerofier there, instead of composing the trace
+/// polynomials symbolically. Row `i` of the trace sits at `omicron^i`, and the
+/// constraints read the current row followed by the next one.
+pub fn transition_quotients(
+    trace_polynomials: &[Polynomial],
+    transition_constraints: &[MPolynomial],
+    omicron: &FieldElement,
+    num_rows: usize,
+    lde: &Coset,
+) -> Result<Vec<Polynomial>> {
+    if num_rows < 2 {
+        return Err(Error::BadDomainSize(num_rows));
+    }
+    let degrees: Vec<usize> = trace_polynomials
+        .iter()
+        .map(|polynomial| polynomial.degree().max(0) as usize)
+        .collect();
+    let degrees = [degrees.as_slice(), degrees.as_slice()].concat();
+    if degrees.iter().any(|degree| *degree >= lde.size)
+        || MPolynomial::max_degree_bound(transition_constraints, &degrees)
+            .is_none_or(|degree| degree >= lde.size)
+    {
+        return Err(Error::BadDomainSize(lde.size));
+    }
+
+    let current = trace_polynomials.iter().map(Polynomial::normalized);
+    let next = current.clone().map(|polynomial| polynomial.scale(*omicron));
+    let columns: Vec<Vec<FieldElement>> = current
+        .chain(next)
+        .map(|polynomial| fast_coset_evaluate(&polynomial, &lde.offset, &lde.generator, lde.size))
+        .collect::<Result<_>>()?;
+
+    let field = omicron.field;
+    let points = lde.elements();
+    let zerofier_evals = if omicron ^ num_rows.into() == field.one() {
+        // Over a full cycle the zerofier is (x^n - 1) / (x - omicron^(n - 1)).
+        let rows = Coset::new(field.one(), *omicron, num_rows);
+        let last = omicron ^ (num_rows - 1).into();
+        let denominators: Vec<FieldElement> = points.iter().map(|x| x - last).collect();
+        let denominators = field
+            .batch_inv(&denominators)
+            .ok_or(Error::DivisionByZero)?;
+        points
+            .iter()
+            .zip(denominators)
+            .map(|(x, denominator)| evaluate_vanishing(&rows, x) * denominator)
+            .collect()
+    } else {
+        let zerofier = Polynomial::zerofier_domain(&field.powers(omicron, num_rows - 1))?;
+        fast_coset_evaluate(&zerofier, &lde.offset, &lde.generator, lde.size)?
+    };
+
+    transition_constraints
+        .iter()
+        .map(|constraint| {
+            let values = constraint.evaluate_over_domain(&columns)?;
+            Polynomial::quotient_on_coset(&lde.offset, &lde.generator, &values, &zerofier_evals)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Thresholds,
+        consts::*,
+        field::Field,
+        trace::{interpolate_columns, ExecutionTrace},
+    };
+
+    fn sample_polynomial(f: Field, degree: usize) -> Polynomial {
+        Polynomial::new(
+            (0..=degree)
+                .map(|i| f.generator() ^ (3 * i + 1).into())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn ntt_test() {
+        let f = Field::new(*PRIME);
+        let n = 16usize;
+        let root = f.primitive_nth_root(n.into());
+        let values: Vec<FieldElement> = (0..n)
+            .map(|i| FieldElement::new((i * i + 7).into(), f))
+            .collect();
+
+        let poly = Polynomial::new(values.clone());
+        let domain: Vec<FieldElement> = (0..n).map(|i| root ^ i.into()).collect();
+        let transformed = ntt(&root, &values).unwrap();
+        assert_eq!(transformed, poly.evaluate_domain(&domain));
+        assert_eq!(intt(&root, &transformed).unwrap(), values);
+        assert!(matches!(
+            ntt(&root, &values[..12]),
+            Err(Error::BadDomainSize(12))
+        ));
+        assert!(matches!(
+            intt(&root, &values[..3]),
+            Err(Error::BadDomainSize(3))
+        ));
+    }
+
+    #[test]
+    fn fast_multiply_test() {
+        let f = Field::new(*PRIME);
+        let root = f.primitive_nth_root(64.into());
+        let lhs = sample_polynomial(f, 12);
+        let rhs = sample_polynomial(f, 20);
+        assert_eq!(fast_multiply(&lhs, &rhs, &root, 64).unwrap(), &lhs * &rhs);
+
+        let small = sample_polynomial(f, 2);
+        assert_eq!(
+            fast_multiply(&small, &small, &root, 64).unwrap(),
+            &small * &small
+        );
+
+        let large = sample_polynomial(f, Thresholds::default().multiply);
+        let product = &large * &large;
+        assert_eq!(product, multiply(&large, &large));
+        assert_eq!(
+            product.degree() as usize,
+            2 * Thresholds::default().multiply
+        );
+    }
+
+    #[test]
+    fn fast_multiply_root_order_test() {
+        let f = Field::new(*PRIME);
+        let root = f.primitive_nth_root(32.into());
+        let lhs = sample_polynomial(f, 12);
+        let rhs = sample_polynomial(f, 20);
+        assert!(matches!(
+            fast_multiply(&lhs, &rhs, &root, 32),
+            Err(Error::BadDomainSize(32))
+        ));
+        assert!(matches!(
+            fast_multiply(&lhs, &rhs, &root, 64),
+            Err(Error::BadDomainSize(64))
+        ));
+        assert!(matches!(
+            fast_zerofier(&[f.one(); 40], &root, 32),
+            Err(Error::BadDomainSize(32))
+        ));
+    }
+
+    #[test]
+    fn fast_interpolate_test() {
+        let f = Field::new(*PRIME);
+        let root = f.primitive_nth_root(32.into());
+        let domain: Vec<FieldElement> = (1..=12).map(|i| FieldElement::new(i.into(), f)).collect();
+        let poly = sample_polynomial(f, 9);
+
+        let zerofier = fast_zerofier(&domain, &root, 32).unwrap();
+        assert_eq!(
+            zerofier,
+            Polynomial::zerofier_domain(&domain.to_vec()).unwrap()
+        );
+
+        let values = fast_evaluate(&poly, &domain, &root, 32).unwrap();
+        assert_eq!(values, poly.evaluate_domain(&domain.to_vec()));
+
+        let interpolated = fast_interpolate(&domain, &values, &root, 32).unwrap();
+        assert_eq!(interpolated.evaluate_domain(&domain.to_vec()), values);
+        assert_eq!(interpolated.degree(), poly.degree());
+        assert!(matches!(
+            fast_interpolate(&domain, &values[1..], &root, 32),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            fast_interpolate(&[f.one(), f.one()], &values[..2], &root, 32),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn reduce_monic_test() {
+        let f = Field::new(*PRIME);
+        let domain: Vec<FieldElement> = (1..=100).map(|i| f.element(i)).collect();
+        let modulus = Polynomial::zerofier_domain(&domain).unwrap();
+        for degree in [120, 300, 1000] {
+            let coefficients = sample_polynomial(f, degree).coefficients;
+            let root = primitive_root(f, 2048).unwrap();
+            assert_eq!(
+                newton_reduce(&coefficients, &modulus, &root, 2048),
+                long_division(&coefficients, &modulus)
+            );
+        }
+
+        let poly = sample_polynomial(f, 1000);
+        let domain: Vec<FieldElement> = (1..=300).map(|i| f.element(i)).collect();
+        let root = f.primitive_nth_root(512.into());
+        assert_eq!(
+            fast_evaluate(&poly, &domain, &root, 512).unwrap(),
+            poly.evaluate_domain(&domain)
+        );
+    }
+
+    #[test]
+    fn interpolate_dispatch_test() {
+        let f = Field::new(*PRIME);
+        let n = Thresholds::default().interpolate;
+        let domain: Vec<FieldElement> = (1..=n).map(|i| FieldElement::new(i.into(), f)).collect();
+        let poly = sample_polynomial(f, n - 1);
+        let values = poly.evaluate_domain(&domain);
+        assert_eq!(
+            Polynomial::interpolate_domain(&domain, &values).unwrap(),
+            poly
+        );
+
+        let eager = Thresholds {
+            multiply: 0,
+            interpolate: 0,
+            evaluate: 0,
+        };
+        assert_eq!(poly.evaluate_domain_with(&domain, &eager), values);
+        assert_eq!(
+            Polynomial::interpolate_domain_with(&domain[..5], &values[..5], &eager).unwrap(),
+            Polynomial::interpolate_domain(&domain[..5], &values[..5]).unwrap()
+        );
+        assert_eq!(poly.multiply_with(&poly, &eager), multiply(&poly, &poly));
+
+        let small = Field::new(17.into());
+        let domain: Vec<FieldElement> = (1..=16)
+            .map(|i| FieldElement::new(i.into(), small))
+            .collect();
+        let values = vec![small.one(); 16];
+        let constant = Polynomial::interpolate_domain(&domain, &values).unwrap();
+        assert_eq!(constant.degree(), 0);
+        assert!(primitive_root(small, 16).is_some());
+        assert!(primitive_root(small, 32).is_none());
+    }
+
+    #[test]
+    fn fast_coset_test() {
+        let f = Field::new(*PRIME);
+        let root = f.primitive_nth_root(32.into());
+        let offset = f.generator();
+        let lhs = sample_polynomial(f, 6);
+        let rhs = sample_polynomial(f, 10);
+        let product = &lhs * &rhs;
+
+        let coset: Vec<FieldElement> = (0..32).map(|i| offset * (root ^ i.into())).collect();
+        assert_eq!(
+            fast_coset_evaluate(&product, &offset, &root, 32).unwrap(),
+            product.evaluate_domain(&coset)
+        );
+        assert!(matches!(
+            fast_coset_evaluate(&product, &offset, &root, 16),
+            Err(Error::BadDomainSize(16))
+        ));
+
+        assert_eq!(
+            fast_coset_divide(&product, &rhs, &offset, &root, 32).unwrap(),
+            lhs
+        );
+        assert_eq!(
+            fast_coset_divide(&product, &lhs, &offset, &root, 32).unwrap(),
+            rhs
+        );
+        assert!(matches!(
+            fast_coset_divide(&product, &Polynomial::new(vec![]), &offset, &root, 32),
+            Err(Error::DivisionByZero)
+        ));
+        assert!(matches!(
+            fast_coset_divide(&product, &rhs, &offset, &root, 16),
+            Err(Error::BadDomainSize(16))
+        ));
+        assert!(matches!(
+            fast_coset_divide(&product, &rhs, &offset, &f.one(), 32),
+            Err(Error::BadDomainSize(32))
+        ));
+    }
+
+    #[test]
+    fn fast_coset_divide_inexact_test() {
+        let f = Field::new(*PRIME);
+        let root = f.primitive_nth_root(32.into());
+        let lhs =
+            &(&sample_polynomial(f, 6) * &sample_polynomial(f, 10)) + &sample_polynomial(f, 0);
+        assert!(matches!(
+            fast_coset_divide(&lhs, &sample_polynomial(f, 10), &f.generator(), &root, 32),
+            Err(Error::InexactDivision)
+        ));
+        let small = sample_polynomial(f, 3);
+        assert!(matches!(
+            fast_coset_divide(&small, &sample_polynomial(f, 2), &f.generator(), &root, 32),
+            Err(Error::InexactDivision)
+        ));
+        assert!(matches!(
+            fast_coset_divide(&small, &lhs, &f.generator(), &root, 32),
+            Err(Error::InexactDivision)
+        ));
+    }
+
+    #[test]
+    fn transition_quotients_test() {
+        let f = Field::new(*PRIME);
+        let v = MPolynomial::variables(4, &f);
+        let constraints = vec![
+            &v[2] - &v[1],
+            &(&v[3] - &v[0]) - &v[1],
+            &(&v[2] - &v[1]) * &v[3],
+        ];
+        let fibonacci = |state: &[FieldElement]| vec![state[1], state[0] + state[1]];
+        let lde = Coset::new(f.generator(), f.primitive_nth_root(64.into()), 64);
+        for steps in [7, 10] {
+            let trace =
+                ExecutionTrace::from_transition(vec![f.one(), f.one()], steps, fibonacci).unwrap();
+            let omicron = f.primitive_nth_root(16.into()) ^ if steps == 7 { *TWO } else { ONE };
+            let polynomials = interpolate_columns(&trace, &omicron).unwrap();
+            let quotients =
+                transition_quotients(&polynomials, &constraints, &omicron, steps + 1, &lde)
+                    .unwrap();
+
+            let point: Vec<Polynomial> = polynomials
+                .iter()
+                .cloned()
+                .chain(polynomials.iter().map(|p| p.scale(omicron)))
+                .collect();
+            let zerofier =
+                Polynomial::zerofier_domain(&f.powers(&omicron, trace.num_rows() - 1)).unwrap();
+            for (constraint, quotient) in constraints.iter().zip(&quotients) {
+                let composed = constraint.evaluate_symbolic(&point);
+                assert_eq!(
+                    *quotient,
+                    composed.checked_div(&zerofier).unwrap().normalized()
+                );
+            }
+        }
+
+        let trace = ExecutionTrace::from_transition(vec![f.one(), f.one()], 7, fibonacci).unwrap();
+        let omicron = f.primitive_nth_root(8.into());
+        let polynomials = interpolate_columns(&trace, &omicron).unwrap();
+        let small = Coset::new(f.generator(), f.primitive_nth_root(8.into()), 8);
+        let squared = vec![&v[2] * &v[2]];
+        assert!(matches!(
+            transition_quotients(&polynomials, &squared, &omicron, 8, &small),
+            Err(Error::BadDomainSize(8))
+        ));
+        let overlapping = Coset::new(f.one(), f.primitive_nth_root(64.into()), 64);
+        assert!(matches!(
+            transition_quotients(&polynomials, &constraints, &omicron, 8, &overlapping),
+            Err(Error::DivisionByZero)
+        ));
+    }
+}
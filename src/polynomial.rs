@@ -1,22 +1,46 @@
-use crate::{element::FieldElement, ONE, ZERO};
+use crate::{
+    config::Thresholds,
+    element::FieldElement,
+    field::{FiniteField, FiniteFieldElement},
+    ntt, Error, Result, ONE, ZERO,
+};
 use primitive_types::U256;
+use std::collections::HashSet;
 
-#[derive(PartialEq, Debug, Clone)]
-pub struct Polynomial {
-    pub coefficients: Vec<FieldElement>,
+/// Coefficients from lowest to highest degree. Any `FiniteFieldElement` works
+/// as a coefficient; the prime-field `FieldElement` is the default.
+#[derive(Debug, Clone)]
+pub struct Polynomial<E: FiniteFieldElement = FieldElement> {
+    pub coefficients: Vec<E>,
 }
 
-fn divide(numerator: &Polynomial, denominator: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+impl<E: FiniteFieldElement> PartialEq for Polynomial<E> {
+    fn eq(&self, other: &Self) -> bool {
+        let degree = self.degree();
+        if degree != other.degree() {
+            return false;
+        }
+        let len = (degree + 1) as usize;
+        self.coefficients[..len] == other.coefficients[..len]
+    }
+}
+
+impl<E: FiniteFieldElement> Eq for Polynomial<E> {}
+
+pub(crate) fn divide<E: FiniteFieldElement>(
+    numerator: &Polynomial<E>,
+    denominator: &Polynomial<E>,
+) -> Result<(Polynomial<E>, Polynomial<E>)> {
     if denominator.degree() == -1 {
-        return None;
+        return Err(Error::DivisionByZero);
     }
     if numerator.degree() < denominator.degree() {
-        return Some((Polynomial::new(vec![]), numerator.clone()));
+        return Ok((Polynomial::new(vec![]), numerator.clone()));
     }
 
     let degree = numerator.degree() - denominator.degree() + 1;
 
-    let field = denominator.coefficients[0].field;
+    let field = denominator.coefficients[0].field();
     let mut remainder = numerator.clone();
     let mut quotient_coefficients = vec![field.zero(); degree.try_into().unwrap()];
 
@@ -24,13 +48,13 @@ fn divide(numerator: &Polynomial, denominator: &Polynomial) -> Option<(Polynomia
         if remainder.degree() < denominator.degree() {
             break;
         }
-        let coefficient = &(remainder.leading_coefficient()) / &(denominator.leading_coefficient());
+        let coefficient = (remainder.leading_coefficient()) / (denominator.leading_coefficient());
         let shift: usize = (remainder.degree() - denominator.degree())
             .try_into()
             .unwrap();
 
         let mut coeffs = vec![field.zero(); shift];
-        coeffs.push(coefficient.clone());
+        coeffs.push(coefficient);
 
         let subtractee = &Polynomial::new(coeffs) * denominator;
 
@@ -38,11 +62,31 @@ fn divide(numerator: &Polynomial, denominator: &Polynomial) -> Option<(Polynomia
         remainder = &remainder - &subtractee;
     }
     let quotient = Polynomial::new(quotient_coefficients);
-    return Some((quotient, remainder));
+    Ok((quotient, remainder))
+}
+
+pub(crate) fn multiply<E: FiniteFieldElement>(
+    lhs: &Polynomial<E>,
+    rhs: &Polynomial<E>,
+) -> Polynomial<E> {
+    if lhs.coefficients.is_empty() || rhs.coefficients.is_empty() {
+        return Polynomial::new(vec![]);
+    }
+    let field = lhs.coefficients[0].field();
+    let (l, r) = (&lhs.coefficients, &rhs.coefficients);
+    let size = l.len() + r.len() - 1;
+    let new_coeffs = (0..size)
+        .map(|k| {
+            let start = k.saturating_sub(r.len() - 1);
+            let end = k.min(l.len() - 1);
+            field.sum_of_products((start..=end).map(|i| (l[i], r[k - i])))
+        })
+        .collect();
+    Polynomial::new(new_coeffs)
 }
 
-impl Polynomial {
-    pub fn new(coefficients: Vec<FieldElement>) -> Self {
+impl<E: FiniteFieldElement> Polynomial<E> {
+    pub fn new(coefficients: Vec<E>) -> Self {
         Polynomial { coefficients }
     }
 
@@ -63,94 +107,280 @@ impl Polynomial {
         if zeros == len {
             return -1;
         }
-        return max_index.try_into().unwrap();
+        max_index.try_into().unwrap()
     }
 
     pub fn is_zero(&self) -> bool {
         self.degree() == -1
     }
 
-    pub fn leading_coefficient(&self) -> FieldElement {
+    pub fn normalize(&mut self) {
+        let len = (self.degree() + 1) as usize;
+        self.coefficients.truncate(len);
+    }
+
+    pub fn normalized(&self) -> Self {
+        let mut normalized = self.clone();
+        normalized.normalize();
+        normalized
+    }
+
+    pub fn leading_coefficient(&self) -> E {
         let index: usize = self.degree().try_into().unwrap();
-        FieldElement::new(
-            self.coefficients[index].value,
-            self.coefficients[index].field,
-        )
+        self.coefficients[index]
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self> {
+        let (quotient, remainder) = divide(self, rhs)?;
+        if !remainder.is_zero() {
+            return Err(Error::InexactDivision);
+        }
+        Ok(quotient)
     }
 
-    pub fn evaluate(&self, point: &FieldElement) -> FieldElement {
-        let mut xi: FieldElement = point.field.one();
-        let mut value: FieldElement = point.field.zero();
+    pub fn evaluate(&self, point: &E) -> E {
+        let mut xi = point.field().one();
+        let mut value = point.field().zero();
         self.coefficients.iter().for_each(|c| {
-            value = &value + &(c * &xi);
-            xi = &xi * point;
+            value += *c * xi;
+            xi *= *point;
         });
         value
     }
 
-    pub fn evaluate_domain(&self, domain: &Vec<FieldElement>) -> Vec<FieldElement> {
+    pub fn multiply_with(&self, rhs: &Self, thresholds: &Thresholds) -> Self {
+        if !self.is_zero() && !rhs.is_zero() {
+            let degree: usize = (self.degree() + rhs.degree()).try_into().unwrap();
+            if degree >= thresholds.multiply {
+                let order = (degree + 1).next_power_of_two();
+                if let Some(root) = ntt::primitive_root(self.coefficients[0].field(), order) {
+                    if let Ok(product) = ntt::fast_multiply(self, rhs, &root, order) {
+                        return product;
+                    }
+                }
+            }
+        }
+        multiply(self, rhs)
+    }
+
+    pub fn evaluate_domain(&self, domain: &[E]) -> Vec<E> {
+        self.evaluate_domain_with(domain, &Thresholds::new())
+    }
+
+    pub fn evaluate_domain_with(&self, domain: &[E], thresholds: &Thresholds) -> Vec<E> {
+        if !domain.is_empty() && domain.len() >= thresholds.evaluate {
+            let order = (domain.len() + 1).next_power_of_two();
+            if let Some(root) = ntt::primitive_root(domain[0].field(), order) {
+                if let Ok(values) = ntt::fast_evaluate(self, domain, &root, order) {
+                    return values;
+                }
+            }
+        }
         domain.iter().map(|point| self.evaluate(point)).collect()
     }
 
-    pub fn interpolate_domain(domain: &Vec<FieldElement>, values: &Vec<FieldElement>) -> Self {
-        assert!(domain.len() == values.len());
-        assert!(domain.len() > 0);
-        let field = domain[0].field;
-        let x = Polynomial::new(vec![field.zero(), field.one()]);
-        let mut acc = Polynomial::new(vec![]);
+    pub fn interpolate_domain(domain: &[E], values: &[E]) -> Result<Self> {
+        Self::interpolate_domain_with(domain, values, &Thresholds::new())
+    }
+
+    pub fn interpolate_domain_with(
+        domain: &[E],
+        values: &[E],
+        thresholds: &Thresholds,
+    ) -> Result<Self> {
+        if domain.len() != values.len() {
+            return Err(Error::LengthMismatch {
+                expected: domain.len(),
+                actual: values.len(),
+            });
+        }
+        if domain.is_empty() {
+            return Err(Error::BadDomainSize(0));
+        }
+        let field = domain[0].field();
+        if domain
+            .iter()
+            .chain(values.iter())
+            .any(|e| e.field() != field)
+        {
+            return Err(Error::FieldMismatch);
+        }
+        let mut seen = HashSet::with_capacity(domain.len());
+        if !domain.iter().all(|x| seen.insert(x.to_u256())) {
+            return Err(Error::DivisionByZero);
+        }
+        if domain.len() >= thresholds.interpolate {
+            let order = (domain.len() + 1).next_power_of_two();
+            if let Some(root) = ntt::primitive_root(field, order) {
+                return ntt::fast_interpolate(domain, values, &root, order);
+            }
+        }
+        let denominators: Vec<_> = domain
+            .iter()
+            .enumerate()
+            .map(|(i, xi)| {
+                domain
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(field.one(), |acc, (_, xj)| acc * (*xi - *xj))
+            })
+            .collect();
+        let weights = field
+            .batch_inv(&denominators)
+            .ok_or(Error::DivisionByZero)?;
+        let x = Self::new(vec![field.zero(), field.one()]);
+        let mut acc = Self::new(vec![]);
         for i in 0..domain.len() {
-            let mut prod = Polynomial::new(vec![values[i]]);
-            for j in 0..domain.len() {
+            let mut prod = Self::new(vec![values[i] * weights[i]]);
+            for (j, xj) in domain.iter().enumerate() {
                 if j == i {
                     continue;
                 }
-                prod = &(&prod * &(&x - &Polynomial::new(vec![domain[j]])))
-                    * &Polynomial::new(vec![(&domain[i] - &domain[j]).inv()]);
+                prod = &prod * &(&x - &Self::new(vec![*xj]));
             }
             acc = &acc + &prod;
         }
-        acc
+        Ok(acc)
     }
 
-    pub fn zerofier_domain(domain: &Vec<FieldElement>) -> Self {
-        assert!(domain.len() > 0);
-        let field = domain[0].field;
-        let x = Polynomial::new(vec![field.zero(), field.one()]);
-        let mut acc = Polynomial::new(vec![field.one()]);
-        for i in 0..domain.len() {
-            acc = &acc * &(&x - &Polynomial::new(vec![domain[i]]));
+    pub fn interpolate_coset(offset: &E, omega: &E, values: &[E]) -> Result<Self> {
+        let n = values.len();
+        if !n.is_power_of_two() {
+            return Err(Error::BadDomainSize(n));
         }
-        acc
+        let one = omega.field().one();
+        if omega.pow(n.into()) != one || (n > 1 && omega.pow((n / 2).into()) == one) {
+            return Err(Error::BadDomainSize(n));
+        }
+        if offset.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if values
+            .iter()
+            .chain([offset, omega])
+            .any(|e| e.field() != omega.field())
+        {
+            return Err(Error::FieldMismatch);
+        }
+        Ok(Self::new(ntt::intt(omega, values)?).scale(offset.inv()))
+    }
+
+    pub fn divide_on_domain(values: &[E], zerofier_evals: &[E]) -> Result<Vec<E>> {
+        if values.len() != zerofier_evals.len() {
+            return Err(Error::LengthMismatch {
+                expected: values.len(),
+                actual: zerofier_evals.len(),
+            });
+        }
+        values
+            .iter()
+            .zip(zerofier_evals)
+            .map(|(value, zerofier)| {
+                if value.field() != zerofier.field() {
+                    Err(Error::FieldMismatch)
+                } else if zerofier.is_zero() {
+                    Err(Error::DivisionByZero)
+                } else {
+                    Ok(*value / *zerofier)
+                }
+            })
+            .collect()
+    }
+
+    pub fn quotient_on_coset(
+        offset: &E,
+        omega: &E,
+        values: &[E],
+        zerofier_evals: &[E],
+    ) -> Result<Self> {
+        let quotient = Self::divide_on_domain(values, zerofier_evals)?;
+        Ok(Self::interpolate_coset(offset, omega, &quotient)?.normalized())
     }
 
-    pub fn scale(&self, factor: FieldElement) -> Self {
-        Polynomial::new(
+    pub fn zerofier_domain(domain: &[E]) -> Result<Self> {
+        if domain.is_empty() {
+            return Err(Error::BadDomainSize(0));
+        }
+        let field = domain[0].field();
+        if domain.iter().any(|e| e.field() != field) {
+            return Err(Error::FieldMismatch);
+        }
+        let x = Self::new(vec![field.zero(), field.one()]);
+        let mut acc = Self::new(vec![field.one()]);
+        for d in domain {
+            acc = &acc * &(&x - &Self::new(vec![*d]));
+        }
+        Ok(acc)
+    }
+
+    pub fn scale(&self, factor: E) -> Self {
+        Self::new(
             self.coefficients
                 .iter()
                 .enumerate()
-                .map(|(index, c)| &(&factor ^ index.into()) * c)
+                .map(|(index, c)| factor.pow(index.into()) * *c)
                 .collect(),
         )
     }
 
-    pub fn test_colinearity(points: &Vec<(FieldElement, FieldElement)>) -> bool {
-        let domain: Vec<FieldElement> = points.iter().map(|p| p.0).collect();
-        let values: Vec<FieldElement> = points.iter().map(|p| p.1).collect();
-        let poly = Polynomial::interpolate_domain(&domain, &values);
-        poly.degree() <= 1
+    pub fn test_colinearity(points: &[(E, E)]) -> bool {
+        let mut domain: Vec<E> = vec![];
+        let mut values: Vec<E> = vec![];
+        for (x, y) in points {
+            match domain.iter().position(|d| d == x) {
+                Some(i) if values[i] != *y => return false,
+                Some(_) => {}
+                None => {
+                    domain.push(*x);
+                    values.push(*y);
+                }
+            }
+        }
+        Self::interpolate_domain(&domain, &values).is_ok_and(|poly| poly.degree() <= 1)
+    }
+
+    pub fn test_colinearity_batch(triples: &[[(E, E); 3]]) -> bool {
+        let Some(field) = triples.first().map(|triple| triple[0].0.field()) else {
+            return true;
+        };
+        let (regular, degenerate): (Vec<&[_; 3]>, Vec<&[_; 3]>) = triples
+            .iter()
+            .partition(|[(x0, _), (x1, _), (x2, _)]| x0 != x1 && x0 != x2);
+        if !degenerate
+            .iter()
+            .all(|triple| Self::test_colinearity(&triple[..]))
+        {
+            return false;
+        }
+
+        let denominators: Vec<E> = regular
+            .iter()
+            .map(|[(x0, _), (x1, _), _]| *x1 - *x0)
+            .collect();
+        let Some(inverses) = field.batch_inv(&denominators) else {
+            return false;
+        };
+        regular
+            .iter()
+            .zip(&inverses)
+            .all(|([(x0, y0), (_, y1), (x2, y2)], inverse)| {
+                let slope = (*y1 - *y0) * *inverse;
+                *y2 == *y0 + (slope * (*x2 - *x0))
+            })
     }
 }
 
-impl std::ops::Add<&Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::Add<&Polynomial<E>> for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn add(self, rhs: &Polynomial) -> Polynomial {
+    fn add(self, rhs: &Polynomial<E>) -> Polynomial<E> {
         if self.degree() == -1 {
             return rhs.clone();
         } else if rhs.degree() == -1 {
             return self.clone();
         }
-        let field = self.coefficients[0].field;
+        let field = self.coefficients[0].field();
         let size = if self.coefficients.len() > rhs.coefficients.len() {
             self.coefficients.len()
         } else {
@@ -158,78 +388,63 @@ impl std::ops::Add<&Polynomial> for &Polynomial {
         };
         let mut new_coeffs = vec![field.zero(); size];
         self.coefficients.iter().enumerate().for_each(|(index, e)| {
-            new_coeffs[index] = &new_coeffs[index] + e;
+            new_coeffs[index] += *e;
         });
         rhs.coefficients.iter().enumerate().for_each(|(index, e)| {
-            new_coeffs[index] = &new_coeffs[index] + e;
+            new_coeffs[index] += *e;
         });
         Polynomial::new(new_coeffs)
     }
 }
 
-impl std::ops::Neg for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::Neg for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn neg(self) -> Polynomial {
-        let new_coeffs: Vec<FieldElement> = self.coefficients.iter().map(|e| -e).collect();
+    fn neg(self) -> Polynomial<E> {
+        let new_coeffs: Vec<E> = self.coefficients.iter().map(|e| -*e).collect();
         Polynomial::new(new_coeffs)
     }
 }
 
-impl std::ops::Sub<&Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::Sub<&Polynomial<E>> for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn sub(self, rhs: &Polynomial) -> Polynomial {
+    fn sub(self, rhs: &Polynomial<E>) -> Polynomial<E> {
         self + &(-rhs)
     }
 }
 
-impl std::ops::Mul<&Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::Mul<&Polynomial<E>> for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn mul(self, rhs: &Polynomial) -> Polynomial {
-        if self.coefficients.len() == 0 || rhs.coefficients.len() == 0 {
-            return Polynomial::new(vec![]);
-        }
-        let zero = self.coefficients[0].field.zero();
-        let size = rhs.coefficients.len() + self.coefficients.len() - 1;
-        let mut new_coeffs = vec![zero; size];
-        self.coefficients.iter().enumerate().for_each(|(i, e)| {
-            if !e.is_zero() {
-                rhs.coefficients.iter().enumerate().for_each(|(j, er)| {
-                    new_coeffs[i + j] = &new_coeffs[i + j] + &(e * er);
-                });
-            }
-        });
-        Polynomial::new(new_coeffs)
+    fn mul(self, rhs: &Polynomial<E>) -> Polynomial<E> {
+        self.multiply_with(rhs, &Thresholds::new())
     }
 }
 
-impl std::ops::Div<&Polynomial> for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::Div<&Polynomial<E>> for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn div(self, rhs: &Polynomial) -> Polynomial {
-        if let Some((quotient, remainder)) = divide(self, rhs) {
-            assert!(remainder.degree() != -1);
-            return quotient;
-        } else {
-            panic!("[Polynomial] Division error");
+    fn div(self, rhs: &Polynomial<E>) -> Polynomial<E> {
+        match self.checked_div(rhs) {
+            Ok(quotient) => quotient,
+            Err(e) => panic!("[Polynomial] {}", e),
         }
     }
 }
 
-impl std::ops::BitXor<U256> for &Polynomial {
-    type Output = Polynomial;
+impl<E: FiniteFieldElement> std::ops::BitXor<U256> for &Polynomial<E> {
+    type Output = Polynomial<E>;
 
-    fn bitxor(self, rhs: U256) -> Polynomial {
+    fn bitxor(self, rhs: U256) -> Polynomial<E> {
         if self.degree() == -1 {
             return Polynomial::new(vec![]);
         }
         if rhs == ZERO {
-            return Polynomial::new(vec![self.coefficients[0].field.one()]);
+            return Polynomial::new(vec![self.coefficients[0].field().one()]);
         }
 
-        let mut acc = Polynomial::new(vec![self.coefficients[0].field.one()]);
+        let mut acc = Polynomial::new(vec![self.coefficients[0].field().one()]);
 
         let mut i: U256 = 128.into();
         while i > ZERO {
@@ -244,11 +459,11 @@ impl std::ops::BitXor<U256> for &Polynomial {
             i -= ONE;
             acc = &acc * &acc;
             if (ONE << i) & rhs != ZERO {
-                acc = &acc * &self;
+                acc = &acc * self;
             }
         }
 
-        return acc;
+        acc
     }
 }
 
@@ -265,11 +480,21 @@ mod tests {
         assert_eq!(poly.degree(), 2);
         assert_eq!(poly.leading_coefficient(), f.generator());
 
-        let poly = Polynomial::new(vec![]);
+        let poly = Polynomial::<FieldElement>::new(vec![]);
         assert_eq!(poly.degree(), -1);
 
         let poly = Polynomial::new(vec![f.zero(), f.zero()]);
         assert_eq!(poly.degree(), -1);
+        assert_eq!(poly, Polynomial::new(vec![]));
+        assert!(poly.normalized().coefficients.is_empty());
+
+        let mut padded = Polynomial::new(vec![f.one(), f.generator(), f.zero()]);
+        let trimmed = Polynomial::new(vec![f.one(), f.generator()]);
+        assert_eq!(padded, trimmed);
+        assert_ne!(padded, Polynomial::new(vec![f.one()]));
+        assert_ne!(padded, Polynomial::new(vec![f.generator(), f.one()]));
+        padded.normalize();
+        assert_eq!(padded.coefficients, trimmed.coefficients);
     }
 
     #[test]
@@ -307,7 +532,7 @@ mod tests {
             (&poly1 * &poly2).coefficients,
             vec![
                 f.generator(),
-                FieldElement::new((*GENERATOR * *GENERATOR) % f.p + ONE, f),
+                FieldElement::new((*GENERATOR * *GENERATOR) % f.p() + ONE, f),
                 f.generator()
             ]
         );
@@ -317,31 +542,59 @@ mod tests {
         assert_eq!(&poly1 ^ *TWO, &poly1 * &poly1);
 
         assert_eq!(
-            (&poly1 / &poly2).coefficients,
-            vec![&poly1.leading_coefficient() / &poly2.leading_coefficient()]
+            divide(&poly1, &poly2).unwrap().0.coefficients,
+            vec![poly1.leading_coefficient() / poly2.leading_coefficient()]
         );
     }
 
+    #[test]
+    fn checked_div_test() {
+        let f = Field::new(*PRIME);
+        let lhs = Polynomial::new(vec![f.one(), f.generator()]);
+        let rhs = Polynomial::new(vec![f.element(5), f.zero(), f.one()]);
+        let product = &lhs * &rhs;
+        assert_eq!(product.checked_div(&rhs).unwrap(), lhs);
+        assert_eq!(&product / &lhs, rhs);
+
+        let remainder = &product + &Polynomial::new(vec![f.one()]);
+        assert!(matches!(
+            remainder.checked_div(&rhs),
+            Err(Error::InexactDivision)
+        ));
+        assert!(matches!(
+            product.checked_div(&Polynomial::new(vec![])),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero remainder")]
+    fn inexact_div_test() {
+        let f = Field::new(*PRIME);
+        let lhs = Polynomial::new(vec![f.one(), f.generator()]);
+        let _ = &lhs / &Polynomial::new(vec![f.generator(), f.one()]);
+    }
+
     #[test]
     fn evaluate_test() {
         let f = Field::new(*PRIME);
         let poly1 = Polynomial::new(vec![f.zero(), f.zero()]);
         let poly2 = Polynomial::new(vec![f.generator(), f.one(), FieldElement::new(*TWO, f)]);
 
-        let point1 = FieldElement::new(134.into(), f);
-        let point2 = FieldElement::new(1932.into(), f);
+        let point1 = f.element(134);
+        let point2 = f.element(1932);
         assert_eq!(poly1.evaluate(&point1), f.zero(),);
 
         assert_eq!(
             poly2.evaluate(&point1),
-            &(&(&FieldElement::new(*TWO, f) * &(&point1 ^ *TWO)) + &point1) + &f.generator(),
+            ((FieldElement::new(*TWO, f) * (point1 ^ *TWO)) + point1) + f.generator(),
         );
 
         assert_eq!(
-            poly2.evaluate_domain(&vec![point1, point2]),
+            poly2.evaluate_domain(&[point1, point2]),
             vec![
-                &(&(&FieldElement::new(*TWO, f) * &(&point1 ^ *TWO)) + &point1) + &f.generator(),
-                &(&(&FieldElement::new(*TWO, f) * &(&point2 ^ *TWO)) + &point2) + &f.generator()
+                ((FieldElement::new(*TWO, f) * (point1 ^ *TWO)) + point1) + f.generator(),
+                ((FieldElement::new(*TWO, f) * (point2 ^ *TWO)) + point2) + f.generator()
             ]
         );
     }
@@ -349,63 +602,165 @@ mod tests {
     #[test]
     fn interpolate_test() {
         let f = Field::new(*PRIME);
-        let point1 = FieldElement::new(134.into(), f);
-        let point2 = FieldElement::new(1932.into(), f);
+        let point1 = f.element(134);
+        let point2 = f.element(1932);
 
         let interpolated =
-            Polynomial::interpolate_domain(&vec![point1, point2], &vec![f.one(), f.generator()]);
+            Polynomial::interpolate_domain(&[point1, point2], &[f.one(), f.generator()]).unwrap();
         assert_eq!(
             interpolated,
             Polynomial::new(vec![
-                FieldElement::new(156715821677969870210199381849610144059u128.into(), f),
-                FieldElement::new(144172632631064309698331206458044765549u128.into(), f)
+                f.element(156715821677969870210199381849610144059u128),
+                f.element(144172632631064309698331206458044765549u128)
             ])
         );
         assert_eq!(interpolated.evaluate(&point1), f.one());
         assert_eq!(interpolated.evaluate(&point2), f.generator());
 
-        let zero_interpolated = Polynomial::zerofier_domain(&vec![point1, point2]);
+        let zero_interpolated = Polynomial::zerofier_domain(&[point1, point2]).unwrap();
         assert_eq!(
             zero_interpolated,
             Polynomial::new(vec![
-                FieldElement::new(258888.into(), f),
-                FieldElement::new(270497897142230380135924736767050119151u128.into(), f),
+                f.element(258888),
+                f.element(270497897142230380135924736767050119151u128),
                 f.one()
             ])
         );
         assert_eq!(zero_interpolated.evaluate(&point1), f.zero());
         assert_eq!(zero_interpolated.evaluate(&point2), f.zero());
+
+        assert!(matches!(
+            Polynomial::interpolate_domain(&[point1, point2], &[f.one()]),
+            Err(Error::LengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            Polynomial::interpolate_domain(&[point1, point1], &[f.one(), f.zero()]),
+            Err(Error::DivisionByZero)
+        ));
+        // Large enough to take the fast path.
+        let repeated = vec![f.element(5u64); 64];
+        assert!(matches!(
+            Polynomial::interpolate_domain(&repeated, &f.powers(&f.element(3u64), 64)),
+            Err(Error::DivisionByZero)
+        ));
+
+        let domain = f.powers(&f.generator(), 9);
+        let values = f.powers(&f.element(3), 9);
+        let interpolated = Polynomial::interpolate_domain(&domain, &values).unwrap();
+        assert!(interpolated.degree() <= 8);
+        assert_eq!(interpolated.evaluate_domain(&domain), values);
+        assert!(matches!(
+            Polynomial::<FieldElement>::zerofier_domain(&[]),
+            Err(Error::BadDomainSize(0))
+        ));
+        let other = FieldElement::new(3.into(), Field::new(17.into()));
+        assert!(matches!(
+            Polynomial::zerofier_domain(&[point1, other]),
+            Err(Error::FieldMismatch)
+        ));
+    }
+
+    #[test]
+    fn interpolate_coset_test() {
+        let f = Field::new(*PRIME);
+        let offset = f.generator();
+        let omega = f.primitive_nth_root(16.into());
+        let poly = Polynomial::new(
+            (0..10)
+                .map(|i| FieldElement::new((3 * i + 1).into(), f))
+                .collect(),
+        );
+        let coset: Vec<FieldElement> = (0..16).map(|i| offset * (omega ^ i.into())).collect();
+        let values = poly.evaluate_domain(&coset);
+
+        let interpolated = Polynomial::interpolate_coset(&offset, &omega, &values).unwrap();
+        assert_eq!(interpolated.degree(), poly.degree());
+        assert_eq!(interpolated.coefficients[..10], poly.coefficients[..]);
+
+        assert!(matches!(
+            Polynomial::interpolate_coset(&offset, &omega, &values[..12]),
+            Err(Error::BadDomainSize(12))
+        ));
+        assert!(matches!(
+            Polynomial::interpolate_coset(&offset, &(omega ^ *TWO), &values),
+            Err(Error::BadDomainSize(16))
+        ));
+        assert!(matches!(
+            Polynomial::interpolate_coset(&f.zero(), &omega, &values),
+            Err(Error::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn divide_on_domain_test() {
+        let f = Field::new(*PRIME);
+        let offset = f.generator();
+        let omega = f.primitive_nth_root(32.into());
+        let coset: Vec<FieldElement> = (0..32).map(|i| offset * (omega ^ i.into())).collect();
+        let roots: Vec<FieldElement> = (1..5).map(|i| FieldElement::new(i.into(), f)).collect();
+        let zerofier = Polynomial::zerofier_domain(&roots).unwrap();
+        let quotient = Polynomial::new(
+            (0..7)
+                .map(|i| FieldElement::new((5 * i + 2).into(), f))
+                .collect(),
+        );
+        let values = (&quotient * &zerofier).evaluate_domain(&coset);
+        let zerofier_evals = zerofier.evaluate_domain(&coset);
+
+        let divided = Polynomial::divide_on_domain(&values, &zerofier_evals).unwrap();
+        assert_eq!(divided, quotient.evaluate_domain(&coset));
+        let recovered =
+            Polynomial::quotient_on_coset(&offset, &omega, &values, &zerofier_evals).unwrap();
+        assert_eq!(recovered, quotient);
+        assert_eq!(recovered.coefficients.len(), 7);
+
+        assert!(matches!(
+            Polynomial::divide_on_domain(&values, &zerofier_evals[..31]),
+            Err(Error::LengthMismatch {
+                expected: 32,
+                actual: 31
+            })
+        ));
+        let mut vanishing = zerofier_evals.clone();
+        vanishing[3] = f.zero();
+        assert!(matches!(
+            Polynomial::divide_on_domain(&values, &vanishing),
+            Err(Error::DivisionByZero)
+        ));
     }
 
     #[test]
     fn scale_test() {
         let f = Field::new(*PRIME);
-        let point1 = FieldElement::new(134.into(), f);
-        let point2 = FieldElement::new(1932.into(), f);
-        let poly = Polynomial::zerofier_domain(&vec![point1, point2]);
+        let point1 = f.element(134);
+        let point2 = f.element(1932);
+        let poly = Polynomial::zerofier_domain(&[point1, point2]).unwrap();
 
         let scale = FieldElement::new(*TWO, f);
         let scaled_poly = poly.scale(scale);
 
         assert_eq!(scaled_poly.coefficients[0], poly.coefficients[0]);
-        assert_eq!(scaled_poly.coefficients[1], &poly.coefficients[1] * &scale);
+        assert_eq!(scaled_poly.coefficients[1], poly.coefficients[1] * scale);
         assert_eq!(
             scaled_poly.coefficients[2],
-            &(&poly.coefficients[2] * &scale) * &scale
+            (poly.coefficients[2] * scale) * scale
         );
 
         assert_eq!(
-            scaled_poly.evaluate(&(&point1 / &scale)),
+            scaled_poly.evaluate(&(point1 / scale)),
             poly.evaluate(&point1)
         );
 
         assert_eq!(
-            scaled_poly.evaluate(&(&point1 / &scale)),
+            scaled_poly.evaluate(&(point1 / scale)),
             poly.evaluate(&point2)
         );
 
         assert_eq!(
-            scaled_poly.evaluate(&(&f.generator() / &scale)),
+            scaled_poly.evaluate(&(f.generator() / scale)),
             poly.evaluate(&f.generator())
         );
     }
@@ -415,18 +770,52 @@ mod tests {
         let f = Field::new(*PRIME);
         let point1 = (f.one(), f.zero());
         let point2 = (FieldElement::new(*TWO, f), f.one());
-        let point3 = (FieldElement::new(3.into(), f), FieldElement::new(*TWO, f));
+        let point3 = (f.element(3), FieldElement::new(*TWO, f));
         let point4 = (f.generator(), f.one());
 
-        assert_eq!(Polynomial::test_colinearity(&vec![point1, point2]), true);
-        assert_eq!(Polynomial::test_colinearity(&vec![point1, point4]), true);
-        assert_eq!(
-            Polynomial::test_colinearity(&vec![point1, point2, point4]),
-            false
-        );
+        assert!(Polynomial::test_colinearity(&[point1, point2]));
+        assert!(Polynomial::test_colinearity(&[point1, point4]));
+        assert!(!Polynomial::test_colinearity(&[point1, point2, point4]));
+        assert!(Polynomial::test_colinearity(&[point1, point2, point3]));
+        assert!(Polynomial::test_colinearity(&[point1, point2, point1]));
+        assert!(!Polynomial::test_colinearity(&[
+            point1,
+            point2,
+            (point1.0, f.one())
+        ]));
+
+        assert!(Polynomial::<FieldElement>::test_colinearity_batch(&[]));
+        assert!(Polynomial::test_colinearity_batch(&[
+            [point1, point2, point3],
+            [point3, point1, point2],
+            [point1, point2, point1],
+        ]));
+        assert!(!Polynomial::test_colinearity_batch(&[
+            [point1, point2, point3],
+            [point1, point2, point4],
+        ]));
+        assert!(!Polynomial::test_colinearity_batch(&[
+            [point1, point2, point3],
+            [point1, point2, (point1.0, f.one())],
+        ]));
+    }
+
+    #[test]
+    fn binary_field_test() {
+        use crate::field::binary::BinaryTower;
+
+        let f = BinaryTower;
+        let domain: Vec<_> = [3, 1 << 70, 12345].map(|v| f.element(v)).to_vec();
+        let poly = Polynomial::new(vec![f.element(7), f.generator(), f.one()]);
+        let values = poly.evaluate_domain(&domain);
         assert_eq!(
-            Polynomial::test_colinearity(&vec![point1, point2, point3]),
-            true
+            Polynomial::interpolate_domain(&domain, &values).unwrap(),
+            poly
         );
+        // Characteristic 2: p + p vanishes and squaring is additive.
+        assert!((&poly + &poly).is_zero());
+        let x = Polynomial::new(vec![f.zero(), f.one()]);
+        let one = Polynomial::new(vec![f.one()]);
+        assert_eq!(&(&x + &one) ^ *TWO, &(&x * &x) + &one);
     }
 }
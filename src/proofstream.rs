@@ -1,27 +1,60 @@
+use crate::{
+    element::FieldElement,
+    field::Field,
+    hash::{HashKind, Hasher, MAX_DIGEST_LENGTH, MIN_DIGEST_LENGTH},
+    merkle::{Direction, MerkleProof},
+    Error, Result,
+};
+use primitive_types::U256;
 use serde::{Deserialize, Serialize};
-use sha3::digest::ExtendableOutput;
 
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub enum Object<T> {
     HASH(Vec<u8>),
-    PATH(Vec<Vec<u8>>),
+    PATH(MerkleProof),
     LEAF(T),
     OBJ(T),
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct ProofStream<T> {
+    pub hash: HashKind,
+    pub digest_length: usize,
     pub objects: Vec<Object<T>>,
     pub read_index: usize,
 }
 
+impl<'a, T: Clone + Serialize + Deserialize<'a>> Default for ProofStream<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a, T: Clone + Serialize + Deserialize<'a>> ProofStream<T> {
     pub fn new() -> Self {
+        ProofStream::with_hash(HashKind::default())
+    }
+
+    pub fn with_hash(hash: HashKind) -> Self {
+        ProofStream::with_hasher(hash.into())
+    }
+
+    pub fn with_hasher(hasher: Hasher) -> Self {
         ProofStream {
+            hash: hasher.kind,
+            digest_length: hasher.digest_length,
             objects: vec![],
             read_index: 0,
         }
     }
+
+    pub fn hasher(&self) -> Hasher {
+        Hasher {
+            kind: self.hash,
+            digest_length: self.digest_length,
+        }
+    }
+
     pub fn push(&mut self, obj: Object<T>) {
         self.objects.push(obj);
     }
@@ -34,7 +67,7 @@ impl<'a, T: Clone + Serialize + Deserialize<'a>> ProofStream<T> {
         self.objects.push(Object::OBJ(obj));
     }
 
-    pub fn push_path(&mut self, path: Vec<Vec<u8>>) {
+    pub fn push_path(&mut self, path: MerkleProof) {
         self.objects.push(Object::PATH(path));
     }
 
@@ -42,44 +75,272 @@ impl<'a, T: Clone + Serialize + Deserialize<'a>> ProofStream<T> {
         self.objects.push(Object::LEAF(leaf_index));
     }
 
-    pub fn pull(&mut self) -> Object<T> {
-        assert!(self.read_index < self.objects.len());
-        let obj = self.objects[self.read_index].clone();
+    pub(crate) fn pull(&mut self) -> Result<Object<T>> {
+        let obj = self
+            .objects
+            .get(self.read_index)
+            .cloned()
+            .ok_or(Error::MalformedProof("unexpected end of proof stream"))?;
         self.read_index += 1;
-        obj
+        Ok(obj)
     }
 
     pub fn serialize(&self) -> Vec<u8> {
-        serde_pickle::to_vec(&self.objects, Default::default()).unwrap()
+        let input = (self.hash, self.digest_length, &self.objects);
+        serde_pickle::to_vec(&input, Default::default()).unwrap()
     }
 
-    pub fn deserialize(data: &Vec<u8>) -> Self {
-        ProofStream {
-            objects: serde_pickle::from_slice(&data, Default::default()).unwrap(),
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        let (hash, digest_length, objects) = serde_pickle::from_slice(data, Default::default())?;
+        check_digest_length(digest_length)?;
+        Ok(ProofStream {
+            hash,
+            digest_length,
+            objects,
             read_index: 0,
+        })
+    }
+
+    pub(crate) fn prover_fiat_shamir(&self, num_bytes: usize) -> Vec<u8> {
+        self.hash.expand(&self.serialize(), num_bytes)
+    }
+
+    pub(crate) fn verifier_fiat_shamir(&self, num_bytes: usize) -> Vec<u8> {
+        let input = (
+            self.hash,
+            self.digest_length,
+            &self.objects[0..self.read_index],
+        );
+        let input = serde_pickle::to_vec(&input, Default::default()).unwrap();
+        self.hash.expand(&input, num_bytes)
+    }
+}
+
+impl ProofStream<Vec<FieldElement>> {
+    pub fn serialize_compact(&self) -> Result<Vec<u8>> {
+        let field = self.iter_elements().next().map(|element| element.field);
+        if self
+            .iter_elements()
+            .any(|element| Some(element.field) != field)
+        {
+            return Err(Error::FieldMismatch);
+        }
+        let width = field.map_or(0, |field| field.p().bits().div_ceil(8));
+
+        let mut bytes = vec![self.hash as u8, self.digest_length as u8, width as u8];
+        if let Some(field) = field {
+            bytes.extend(&be_bytes(field.p())[32 - width..]);
+        }
+        for object in &self.objects {
+            match object {
+                Object::HASH(hash) => {
+                    bytes.push(0);
+                    push_bytes(&mut bytes, hash);
+                }
+                Object::PATH(path) => {
+                    bytes.push(1);
+                    bytes.extend((path.nodes.len() as u32).to_be_bytes());
+                    for (direction, node) in &path.nodes {
+                        bytes.push(*direction as u8);
+                        push_bytes(&mut bytes, node);
+                    }
+                }
+                Object::LEAF(elements) | Object::OBJ(elements) => {
+                    bytes.push(if matches!(object, Object::LEAF(_)) {
+                        2
+                    } else {
+                        3
+                    });
+                    bytes.extend((elements.len() as u32).to_be_bytes());
+                    for element in elements {
+                        bytes.extend(&be_bytes(element.value)[32 - width..]);
+                    }
+                }
+            }
         }
+        Ok(bytes)
     }
 
-    pub fn prover_fiat_shamir(&self, num_bytes: usize) -> Vec<u8> {
-        let mut output = vec![0u8; num_bytes];
-        sha3::Shake256::digest_xof(&self.serialize(), &mut output);
-        output
+    pub fn deserialize_compact(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader { data };
+        let hash = match reader.u8()? {
+            0 => HashKind::Blake2b,
+            1 => HashKind::Blake3,
+            2 => HashKind::Sha3,
+            3 => HashKind::Rescue,
+            4 => HashKind::Legacy,
+            _ => return Err(Error::MalformedProof("unknown hash kind")),
+        };
+        let digest_length = reader.u8()? as usize;
+        check_digest_length(digest_length)?;
+        let width = reader.u8()? as usize;
+        if width > 32 {
+            return Err(Error::MalformedProof("element width exceeds 32 bytes"));
+        }
+        let field = match width {
+            0 => None,
+            _ => Some(
+                Field::try_new(U256::from_big_endian(reader.take(width)?))
+                    .map_err(|_| Error::MalformedProof("invalid modulus"))?,
+            ),
+        };
+
+        let mut stream = ProofStream::with_hasher(Hasher {
+            kind: hash,
+            digest_length,
+        });
+        while !reader.data.is_empty() {
+            let object = match reader.u8()? {
+                0 => Object::HASH(reader.bytes()?.to_vec()),
+                1 => {
+                    let len = reader.u32()?;
+                    let mut nodes = vec![];
+                    for _ in 0..len {
+                        let direction = match reader.u8()? {
+                            0 => Direction::Left,
+                            1 => Direction::Right,
+                            _ => return Err(Error::MalformedProof("unknown path direction")),
+                        };
+                        nodes.push((direction, reader.bytes()?.to_vec()));
+                    }
+                    Object::PATH(MerkleProof::new(nodes))
+                }
+                tag @ (2 | 3) => {
+                    let len = reader.u32()?;
+                    let mut elements = vec![];
+                    for _ in 0..len {
+                        let field = field.ok_or(Error::MalformedProof("element without field"))?;
+                        let value = U256::from_big_endian(reader.take(width)?);
+                        if value >= field.p() {
+                            return Err(Error::MalformedProof("element exceeds modulus"));
+                        }
+                        elements.push(FieldElement::new(value, field));
+                    }
+                    if tag == 2 {
+                        Object::LEAF(elements)
+                    } else {
+                        Object::OBJ(elements)
+                    }
+                }
+                _ => return Err(Error::MalformedProof("unknown object tag")),
+            };
+            stream.push(object);
+        }
+        Ok(stream)
     }
 
-    pub fn verifier_fiat_shamir(&self, num_bytes: usize) -> Vec<u8> {
-        let mut output = vec![0u8; num_bytes];
+    fn iter_elements(&self) -> impl Iterator<Item = &FieldElement> {
+        self.objects.iter().flat_map(|object| match object {
+            Object::LEAF(elements) | Object::OBJ(elements) => elements.as_slice(),
+            _ => &[],
+        })
+    }
+}
 
-        let input = &self.objects[0..self.read_index];
-        let input = serde_pickle::to_vec(&input, Default::default()).unwrap();
+fn check_digest_length(digest_length: usize) -> Result<()> {
+    if !(MIN_DIGEST_LENGTH..=MAX_DIGEST_LENGTH).contains(&digest_length) {
+        return Err(Error::MalformedProof("digest length out of range"));
+    }
+    Ok(())
+}
+
+fn be_bytes(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+fn push_bytes(bytes: &mut Vec<u8>, data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    bytes.extend(data);
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(Error::MalformedProof("truncated compact proof"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+impl<T> ProofStream<T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Object<T>> {
+        self.objects[self.read_index.min(self.objects.len())..].iter()
+    }
+
+    pub fn iter_hashes(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.iter().filter_map(|object| match object {
+            Object::HASH(hash) => Some(hash),
+            _ => None,
+        })
+    }
 
-        sha3::Shake256::digest_xof(input, &mut output);
-        output
+    pub fn iter_paths(&self) -> impl Iterator<Item = &MerkleProof> {
+        self.iter().filter_map(|object| match object {
+            Object::PATH(path) => Some(path),
+            _ => None,
+        })
+    }
+
+    pub fn iter_leafs(&self) -> impl Iterator<Item = &T> {
+        self.iter().filter_map(|object| match object {
+            Object::LEAF(leafs) => Some(leafs),
+            _ => None,
+        })
+    }
+
+    pub fn iter_objs(&self) -> impl Iterator<Item = &T> {
+        self.iter().filter_map(|object| match object {
+            Object::OBJ(obj) => Some(obj),
+            _ => None,
+        })
+    }
+}
+
+impl<'s, T> IntoIterator for &'s ProofStream<T> {
+    type Item = &'s Object<T>;
+    type IntoIter = std::slice::Iter<'s, Object<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for ProofStream<T> {
+    type Item = Object<T>;
+    type IntoIter = std::vec::IntoIter<Object<T>>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let read_index = self.read_index.min(self.objects.len());
+        self.objects.split_off(read_index).into_iter()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Object::OBJ, ProofStream};
+    use super::{HashKind, Hasher, Object, Object::OBJ, ProofStream};
+    use crate::merkle::{Direction, MerkleProof};
+    use crate::Error;
     use crate::{consts::*, element::FieldElement, field::Field};
 
     #[test]
@@ -88,10 +349,43 @@ mod tests {
         let mut ps = ProofStream::new();
         ps.push_obj(f.one());
         ps.push_obj(f.zero());
-        assert_eq!(ps.pull(), OBJ(f.one()));
+        assert_eq!(ps.pull().unwrap(), OBJ(f.one()));
         ps.push_obj(f.generator());
-        assert_eq!(ps.pull(), OBJ(f.zero()));
-        assert_eq!(ps.pull(), OBJ(f.generator()));
+        assert_eq!(ps.pull().unwrap(), OBJ(f.zero()));
+        assert_eq!(ps.pull().unwrap(), OBJ(f.generator()));
+        assert!(ps.pull().is_err());
+    }
+
+    #[test]
+    fn iterator_test() {
+        let f = Field::new(*PRIME);
+        let mut ps = ProofStream::new();
+        ps.push_hash(vec![1, 2]);
+        ps.push_obj(f.one());
+        ps.push_path(MerkleProof::default());
+        ps.push_leafs(f.zero());
+        ps.push_hash(vec![3]);
+
+        assert_eq!(ps.iter().len(), 5);
+        assert_eq!(
+            ps.iter_hashes().collect::<Vec<_>>(),
+            vec![&vec![1, 2], &vec![3]]
+        );
+        assert_eq!(ps.iter_objs().collect::<Vec<_>>(), vec![&f.one()]);
+        assert_eq!(ps.iter_leafs().collect::<Vec<_>>(), vec![&f.zero()]);
+        assert_eq!(ps.iter_paths().count(), 1);
+
+        ps.pull().unwrap();
+        ps.pull().unwrap();
+        assert_eq!(ps.iter().len(), 3);
+        assert_eq!((&ps).into_iter().count(), 3);
+        assert_eq!(ps.iter_hashes().collect::<Vec<_>>(), vec![&vec![3]]);
+        assert!(ps.iter_objs().next().is_none());
+        assert_eq!(ps.read_index, 2);
+
+        let remaining: Vec<Object<FieldElement>> = ps.into_iter().collect();
+        assert_eq!(remaining[1], Object::LEAF(f.zero()));
+        assert_eq!(remaining.len(), 3);
     }
 
     #[test]
@@ -103,8 +397,76 @@ mod tests {
         ps.push_obj(f.generator());
 
         let v = ps.serialize();
-        let d: ProofStream<FieldElement> = ProofStream::deserialize(&v);
+        let d: ProofStream<FieldElement> = ProofStream::deserialize(&v).unwrap();
         assert_eq!(ps, d);
+
+        assert!(ProofStream::<FieldElement>::deserialize(&v[1..]).is_err());
+
+        let mut ps = ProofStream::with_hash(HashKind::Sha3);
+        ps.push_obj(f.one());
+        let d: ProofStream<FieldElement> = ProofStream::deserialize(&ps.serialize()).unwrap();
+        assert_eq!(d.hash, HashKind::Sha3);
+        assert_eq!(ps, d);
+
+        let mut ps = ProofStream::with_hasher(Hasher::new(HashKind::Blake2b, 20).unwrap());
+        ps.push_obj(f.one());
+        let d: ProofStream<FieldElement> = ProofStream::deserialize(&ps.serialize()).unwrap();
+        assert_eq!(d.hasher(), ps.hasher());
+        ps.digest_length = 8;
+        assert!(matches!(
+            ProofStream::<FieldElement>::deserialize(&ps.serialize()),
+            Err(Error::MalformedProof(_))
+        ));
+    }
+
+    #[test]
+    fn compact_serialization_test() {
+        let f = Field::new(*PRIME);
+        let mut ps = ProofStream::with_hash(HashKind::Blake3);
+        ps.push_hash(vec![1, 2, 3]);
+        ps.push_obj(vec![f.one(), f.generator()]);
+        ps.push_path(MerkleProof::new(vec![
+            (Direction::Left, vec![4; 32]),
+            (Direction::Right, vec![5; 32]),
+        ]));
+        ps.push_leafs(vec![f.zero(), f.one(), f.generator()]);
+
+        let compact = ps.serialize_compact().unwrap();
+        assert!(compact.len() < ps.serialize().len() / 4);
+        let d = ProofStream::deserialize_compact(&compact).unwrap();
+        assert_eq!(d, ps);
+        assert_eq!(*d.iter_leafs().next().unwrap()[2].field, f);
+
+        let mut short = ps.clone();
+        short.digest_length = 16;
+        let d = ProofStream::deserialize_compact(&short.serialize_compact().unwrap()).unwrap();
+        assert_eq!(d.digest_length, 16);
+        let mut invalid = compact.clone();
+        invalid[1] = 65;
+        assert!(matches!(
+            ProofStream::deserialize_compact(&invalid),
+            Err(Error::MalformedProof(_))
+        ));
+
+        let mut composite = compact.clone();
+        let width = composite[2] as usize;
+        composite[2 + width] ^= 1;
+        assert!(matches!(
+            ProofStream::deserialize_compact(&composite),
+            Err(Error::MalformedProof(_))
+        ));
+
+        assert!(matches!(
+            ProofStream::deserialize_compact(&compact[..compact.len() - 1]),
+            Err(Error::MalformedProof(_))
+        ));
+
+        let empty: ProofStream<Vec<FieldElement>> = ProofStream::new();
+        let d = ProofStream::deserialize_compact(&empty.serialize_compact().unwrap()).unwrap();
+        assert_eq!(d, empty);
+
+        ps.push_obj(vec![Field::new(7.into()).one()]);
+        assert!(matches!(ps.serialize_compact(), Err(Error::FieldMismatch)));
     }
 
     #[test]
@@ -119,12 +481,20 @@ mod tests {
         let verify0 = ps.verifier_fiat_shamir(32);
         assert_ne!(prove0, verify0);
 
-        ps.pull();
-        ps.pull();
-        ps.pull();
+        ps.pull().unwrap();
+        ps.pull().unwrap();
+        ps.pull().unwrap();
         let prove1 = ps.prover_fiat_shamir(32);
         let verify1 = ps.verifier_fiat_shamir(32);
         assert_eq!(prove0, prove1);
         assert_eq!(prove1, verify1);
+
+        let mut blake3 = ProofStream::with_hash(HashKind::Blake3);
+        blake3.objects = ps.objects.clone();
+        assert_ne!(blake3.prover_fiat_shamir(32), prove1);
+
+        let mut short = ps.clone();
+        short.digest_length = 16;
+        assert_ne!(short.prover_fiat_shamir(32), prove1);
     }
 }
@@ -0,0 +1,408 @@
+use crate::{
+    channel::{ProverChannel, VerifierChannel},
+    element::FieldElement,
+    field::Field,
+    fri::FRI,
+    merkle::{Direction, Merkle, MerkleProof},
+    polynomial::Polynomial,
+    Error,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+#[pyclass(name = "Field", frozen, from_py_object)]
+#[derive(Clone, Copy)]
+pub struct PyField(pub Field);
+
+#[pymethods]
+impl PyField {
+    #[new]
+    fn new(p: u128) -> PyResult<Self> {
+        Ok(PyField(Field::try_new(p.into())?))
+    }
+
+    #[getter]
+    fn p(&self) -> u128 {
+        self.0.p().as_u128()
+    }
+
+    fn zero(&self) -> PyFieldElement {
+        PyFieldElement(self.0.zero())
+    }
+
+    fn one(&self) -> PyFieldElement {
+        PyFieldElement(self.0.one())
+    }
+
+    fn generator(&self) -> PyResult<PyFieldElement> {
+        match self.0.try_generator() {
+            Some(generator) => Ok(PyFieldElement(generator)),
+            None => Err(PyValueError::new_err("no two-adic generator")),
+        }
+    }
+
+    fn multiplicative_generator(&self) -> PyResult<PyFieldElement> {
+        match self.0.multiplicative_generator() {
+            Some(generator) => Ok(PyFieldElement(generator)),
+            None => Err(PyValueError::new_err("cannot factor p - 1")),
+        }
+    }
+
+    fn primitive_nth_root(&self, n: u128) -> PyResult<PyFieldElement> {
+        match self.0.nth_root(n.into()) {
+            Some(root) => Ok(PyFieldElement(root)),
+            None => Err(PyValueError::new_err("no primitive nth root of unity")),
+        }
+    }
+
+    fn sample(&self, byte_array: &[u8]) -> PyFieldElement {
+        PyFieldElement(self.0.sample(byte_array))
+    }
+
+    fn element(&self, value: u128) -> PyFieldElement {
+        PyFieldElement(FieldElement::new(value.into(), self.0))
+    }
+}
+
+#[pyclass(name = "FieldElement", frozen, from_py_object)]
+#[derive(Clone, Copy)]
+pub struct PyFieldElement(pub FieldElement);
+
+#[pymethods]
+impl PyFieldElement {
+    #[getter]
+    fn value(&self) -> u128 {
+        self.0.value.as_u128()
+    }
+
+    #[getter]
+    fn field(&self) -> PyField {
+        PyField(*self.0.field)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    fn inverse(&self) -> PyResult<PyFieldElement> {
+        if self.0.is_zero() {
+            return Err(PyValueError::new_err("division by zero"));
+        }
+        Ok(PyFieldElement(self.0.inv()))
+    }
+
+    fn __add__(&self, other: &PyFieldElement) -> PyFieldElement {
+        PyFieldElement(self.0 + other.0)
+    }
+
+    fn __sub__(&self, other: &PyFieldElement) -> PyFieldElement {
+        PyFieldElement(self.0 - other.0)
+    }
+
+    fn __mul__(&self, other: &PyFieldElement) -> PyFieldElement {
+        PyFieldElement(self.0 * other.0)
+    }
+
+    fn __truediv__(&self, other: &PyFieldElement) -> PyResult<PyFieldElement> {
+        if other.0.is_zero() {
+            return Err(PyValueError::new_err("division by zero"));
+        }
+        Ok(PyFieldElement(self.0 / other.0))
+    }
+
+    fn __neg__(&self) -> PyFieldElement {
+        PyFieldElement(-&self.0)
+    }
+
+    fn __xor__(&self, exponent: u128) -> PyFieldElement {
+        PyFieldElement(self.0 ^ exponent.into())
+    }
+
+    fn __eq__(&self, other: &PyFieldElement) -> bool {
+        self.0 == other.0
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FieldElement({})", self.0.value)
+    }
+}
+
+fn unwrap_elements(elements: Vec<PyFieldElement>) -> Vec<FieldElement> {
+    elements.into_iter().map(|e| e.0).collect()
+}
+
+fn wrap_elements(elements: Vec<FieldElement>) -> Vec<PyFieldElement> {
+    elements.into_iter().map(PyFieldElement).collect()
+}
+
+#[pyclass(name = "Polynomial", frozen, from_py_object)]
+#[derive(Clone)]
+pub struct PyPolynomial(pub Polynomial);
+
+#[pymethods]
+impl PyPolynomial {
+    #[new]
+    fn new(coefficients: Vec<PyFieldElement>) -> Self {
+        PyPolynomial(Polynomial::new(unwrap_elements(coefficients)))
+    }
+
+    #[getter]
+    fn coefficients(&self) -> Vec<PyFieldElement> {
+        wrap_elements(self.0.coefficients.clone())
+    }
+
+    fn degree(&self) -> i32 {
+        self.0.degree()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    fn leading_coefficient(&self) -> PyFieldElement {
+        PyFieldElement(self.0.leading_coefficient())
+    }
+
+    fn evaluate(&self, point: &PyFieldElement) -> PyFieldElement {
+        PyFieldElement(self.0.evaluate(&point.0))
+    }
+
+    fn evaluate_domain(&self, domain: Vec<PyFieldElement>) -> Vec<PyFieldElement> {
+        wrap_elements(self.0.evaluate_domain(&unwrap_elements(domain)))
+    }
+
+    #[staticmethod]
+    fn interpolate_domain(
+        domain: Vec<PyFieldElement>,
+        values: Vec<PyFieldElement>,
+    ) -> PyResult<PyPolynomial> {
+        Ok(PyPolynomial(Polynomial::interpolate_domain(
+            &unwrap_elements(domain),
+            &unwrap_elements(values),
+        )?))
+    }
+
+    #[staticmethod]
+    fn zerofier_domain(domain: Vec<PyFieldElement>) -> PyResult<PyPolynomial> {
+        Ok(PyPolynomial(Polynomial::zerofier_domain(
+            &unwrap_elements(domain),
+        )?))
+    }
+
+    fn scale(&self, factor: &PyFieldElement) -> PyPolynomial {
+        PyPolynomial(self.0.scale(factor.0))
+    }
+
+    #[staticmethod]
+    fn test_colinearity(points: Vec<(PyFieldElement, PyFieldElement)>) -> bool {
+        let points: Vec<(FieldElement, FieldElement)> =
+            points.into_iter().map(|(x, y)| (x.0, y.0)).collect();
+        Polynomial::test_colinearity(&points)
+    }
+
+    fn __add__(&self, other: &PyPolynomial) -> PyPolynomial {
+        PyPolynomial(&self.0 + &other.0)
+    }
+
+    fn __sub__(&self, other: &PyPolynomial) -> PyPolynomial {
+        PyPolynomial(&self.0 - &other.0)
+    }
+
+    fn __mul__(&self, other: &PyPolynomial) -> PyPolynomial {
+        PyPolynomial(&self.0 * &other.0)
+    }
+
+    fn __neg__(&self) -> PyPolynomial {
+        PyPolynomial(-&self.0)
+    }
+
+    fn __xor__(&self, exponent: u128) -> PyPolynomial {
+        PyPolynomial(&self.0 ^ exponent.into())
+    }
+
+    fn __eq__(&self, other: &PyPolynomial) -> bool {
+        self.0 == other.0
+    }
+}
+
+fn wrap_proof(proof: MerkleProof) -> Vec<(String, Vec<u8>)> {
+    proof
+        .nodes
+        .into_iter()
+        .map(|(direction, node)| match direction {
+            Direction::Left => ("left".to_string(), node),
+            Direction::Right => ("right".to_string(), node),
+        })
+        .collect()
+}
+
+fn unwrap_proof(path: Vec<(String, Vec<u8>)>) -> PyResult<MerkleProof> {
+    let nodes = path
+        .into_iter()
+        .map(|(direction, node)| match direction.as_str() {
+            "left" => Ok((Direction::Left, node)),
+            "right" => Ok((Direction::Right, node)),
+            _ => Err(PyValueError::new_err("direction must be 'left' or 'right'")),
+        })
+        .collect::<PyResult<Vec<(Direction, Vec<u8>)>>>()?;
+    Ok(MerkleProof::new(nodes))
+}
+
+#[pyclass(name = "Merkle", frozen)]
+pub struct PyMerkle;
+
+#[pymethods]
+impl PyMerkle {
+    #[staticmethod]
+    fn commit(data_array: Vec<Vec<u8>>) -> Vec<u8> {
+        Merkle::commit(&data_array)
+    }
+
+    #[staticmethod]
+    fn open(index: usize, data_array: Vec<Vec<u8>>) -> PyResult<Vec<(String, Vec<u8>)>> {
+        Ok(wrap_proof(Merkle::open(index, &data_array)?))
+    }
+
+    #[staticmethod]
+    fn verify(
+        root: &[u8],
+        index: usize,
+        path: Vec<(String, Vec<u8>)>,
+        data_element: Vec<u8>,
+    ) -> PyResult<bool> {
+        Ok(Merkle::verify(
+            root,
+            index,
+            &unwrap_proof(path)?,
+            &data_element,
+        ))
+    }
+
+    #[staticmethod]
+    fn verify_membership(
+        root: &[u8],
+        path: Vec<(String, Vec<u8>)>,
+        data_element: Vec<u8>,
+    ) -> PyResult<bool> {
+        Ok(Merkle::verify_membership(
+            root,
+            &unwrap_proof(path)?,
+            &data_element,
+        ))
+    }
+}
+
+#[pyclass(name = "Fri", frozen)]
+pub struct PyFri(pub FRI);
+
+#[pymethods]
+impl PyFri {
+    #[new]
+    fn new(
+        offset: &PyFieldElement,
+        omega: &PyFieldElement,
+        initial_domain_length: usize,
+        expansion_factor: usize,
+        num_colinearity_tests: usize,
+    ) -> PyResult<Self> {
+        Ok(PyFri(FRI::try_new(
+            offset.0,
+            omega.0,
+            initial_domain_length,
+            expansion_factor,
+            num_colinearity_tests,
+        )?))
+    }
+
+    fn num_rounds(&self) -> usize {
+        self.0.num_rounds()
+    }
+
+    fn eval_domain(&self) -> Vec<PyFieldElement> {
+        wrap_elements(self.0.eval_domain())
+    }
+
+    fn prove(&self, codeword: Vec<PyFieldElement>) -> PyResult<Vec<u8>> {
+        let mut channel = ProverChannel::new();
+        self.0.prove(&unwrap_elements(codeword), &mut channel)?;
+        Ok(channel.serialize())
+    }
+
+    fn verify(&self, proof: &[u8]) -> PyResult<bool> {
+        let mut channel = VerifierChannel::deserialize(proof)?;
+        Ok(self.0.verify(&mut channel, vec![])?)
+    }
+}
+
+#[pymodule]
+fn anatomy_of_stark(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyField>()?;
+    m.add_class::<PyFieldElement>()?;
+    m.add_class::<PyPolynomial>()?;
+    m.add_class::<PyMerkle>()?;
+    m.add_class::<PyFri>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+
+    #[test]
+    fn python_bindings_test() {
+        let f = PyField::new(PRIME.as_u128()).unwrap();
+        assert_eq!(f.p(), PRIME.as_u128());
+        assert_eq!(f.generator().ok().unwrap().value(), GENERATOR.as_u128());
+
+        let a = f.element(3);
+        let b = f.element(5);
+        assert_eq!((a.__mul__(&b)).value(), 15);
+        assert!(a.__truediv__(&b).ok().unwrap().__mul__(&b).__eq__(&a));
+        assert!(a.__truediv__(&f.zero()).is_err());
+        assert!(a.inverse().ok().unwrap().__mul__(&a).__eq__(&f.one()));
+        assert!(f.zero().inverse().is_err());
+        assert!(f.primitive_nth_root(3).is_err());
+
+        let poly = PyPolynomial::new(vec![f.one(), f.zero(), f.generator().ok().unwrap()]);
+        assert_eq!(poly.degree(), 2);
+        let domain = vec![a, b, f.one()];
+        let values = poly.evaluate_domain(domain.clone());
+        let interpolated = PyPolynomial::interpolate_domain(domain, values)
+            .ok()
+            .unwrap();
+        assert_eq!(interpolated.0.degree(), 2);
+
+        let leafs = vec![vec![1u8], vec![2u8], vec![3u8], vec![4u8]];
+        let root = PyMerkle::commit(leafs.clone());
+        let path = PyMerkle::open(2, leafs).ok().unwrap();
+        assert!(PyMerkle::verify(&root, 2, path.clone(), vec![3u8])
+            .ok()
+            .unwrap());
+        assert!(!PyMerkle::verify(&root, 9, path.clone(), vec![3u8])
+            .ok()
+            .unwrap());
+        assert!(PyMerkle::verify_membership(&root, path, vec![3u8])
+            .ok()
+            .unwrap());
+        assert!(PyMerkle::verify(&root, 2, vec![("up".to_string(), vec![])], vec![3u8]).is_err());
+
+        let hard = PyField::new(0x4000_0000_0000_0007_3fff_ffff_ffff_ffb7).unwrap();
+        assert!(hard.generator().is_ok());
+        assert!(hard.multiplicative_generator().is_err());
+
+        let f = PyField::new(17).unwrap();
+        assert!(PyFri::new(&f.one(), &f.element(6), 16, 2, 4).is_err());
+        assert!(PyFri::new(&f.one(), &f.element(4), 16, 2, 2).is_err());
+        let fri = PyFri::new(&f.one(), &f.element(6), 16, 2, 2).unwrap();
+        let codeword =
+            PyPolynomial::new(vec![f.one(), f.zero(), f.one()]).evaluate_domain(fri.eval_domain());
+        let proof = fri.prove(codeword).ok().unwrap();
+        assert!(fri.verify(&proof).ok().unwrap());
+    }
+}
@@ -0,0 +1,12 @@
+// Prover instrumentation hooks; `()` records nothing so plain proving pays nothing.
+pub(crate) trait Recorder {
+    fn allocate(&mut self, _buffer: &'static str, _bytes: usize) {}
+
+    fn release(&mut self, _bytes: usize) {}
+
+    fn time<R>(&mut self, _phase: &'static str, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+impl Recorder for () {}
@@ -0,0 +1,423 @@
+use crate::{
+    consts::*,
+    element::FieldElement,
+    field::{montgomery::Montgomery, Field},
+    hash, Error, Result,
+};
+use once_cell::sync::Lazy;
+use primitive_types::{U256, U512};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+/// A Rescue-Prime instance. The caller picks the field, state width,
+/// capacity and security level; the S-box exponent, round count, MDS matrix
+/// and round constants are derived as in the Rescue-Prime reference code.
+///
+/// The permutation and sponge run on Montgomery-form words with branchless
+/// reductions and a fixed-window `x^alpha_inv`, so their timing does not
+/// depend on the state. The derivation in `new` works on public values only.
+pub struct RescueParameters {
+    pub field: Field,
+    pub state_width: usize,
+    pub capacity: usize,
+    pub security_level: usize,
+    pub alpha: u64,
+    pub num_rounds: usize,
+    alpha_inv: U256,
+    montgomery: Montgomery,
+    montgomery_mds: Vec<Vec<U256>>,
+    montgomery_constants: Vec<U256>,
+}
+
+/// The Python tutorial's instance: state width 2 and capacity 1 over the
+/// tutorial field, which derives alpha = 3 and 27 rounds.
+pub static TUTORIAL: Lazy<RescueParameters> = Lazy::new(|| {
+    RescueParameters::new(Field::new(*PRIME), 2, 1, hash::SECURITY_BITS)
+        .expect("the tutorial parameters are valid")
+});
+
+impl RescueParameters {
+    pub fn new(
+        field: Field,
+        state_width: usize,
+        capacity: usize,
+        security_level: usize,
+    ) -> Result<Self> {
+        if capacity == 0 || capacity >= state_width {
+            return Err(Error::InvalidParameters(format!(
+                "capacity {} does not leave a rate in a state of width {}",
+                capacity, state_width
+            )));
+        }
+        if security_level == 0 {
+            return Err(Error::InvalidParameters(
+                "security level must be positive".to_string(),
+            ));
+        }
+        if field.p().bits() <= 8 {
+            return Err(Error::InvalidParameters(format!(
+                "p = {} is too small to absorb bytes",
+                field.p()
+            )));
+        }
+        let montgomery = Montgomery::new(field.p()).ok_or_else(|| {
+            Error::InvalidParameters(format!("p = {} is not an odd prime", field.p()))
+        })?;
+        let order = field.p() - ONE;
+        let alpha = (3u64..1 << 16)
+            .find(|alpha| crate::xgcd((*alpha).into(), order).2 == ONE)
+            .ok_or_else(|| {
+                Error::InvalidParameters("no S-box exponent is coprime to p - 1".to_string())
+            })?;
+        let num_rounds = num_rounds(state_width, capacity, security_level, alpha);
+        let mds = mds_matrix(field, state_width)?;
+        let round_constants =
+            round_constants(field, state_width, capacity, security_level, num_rounds);
+        let to_montgomery = |x: &FieldElement| montgomery.to_montgomery(x.value);
+        Ok(RescueParameters {
+            field,
+            state_width,
+            capacity,
+            security_level,
+            alpha,
+            num_rounds,
+            alpha_inv: inverse_exponent(alpha, order),
+            montgomery_mds: mds
+                .iter()
+                .map(|row| row.iter().map(to_montgomery).collect())
+                .collect(),
+            montgomery_constants: round_constants.iter().map(to_montgomery).collect(),
+            montgomery,
+        })
+    }
+
+    pub fn rate(&self) -> usize {
+        self.state_width - self.capacity
+    }
+
+    pub fn mds(&self) -> Vec<Vec<FieldElement>> {
+        self.montgomery_mds
+            .iter()
+            .map(|row| row.iter().map(|word| self.element(*word)).collect())
+            .collect()
+    }
+
+    /// Two rows of `state_width` constants per round.
+    pub fn round_constants(&self) -> Vec<FieldElement> {
+        self.montgomery_constants
+            .iter()
+            .map(|word| self.element(*word))
+            .collect()
+    }
+
+    fn element(&self, word: U256) -> FieldElement {
+        FieldElement::new(self.montgomery.from_montgomery(word), self.field)
+    }
+
+    // Bytes absorbed per field element; this many bytes always fit below p.
+    fn chunk_bytes(&self) -> usize {
+        (self.field.p().bits() - 1) / 8
+    }
+
+    fn mix(&self, state: &[U256], constants: &[U256]) -> Vec<U256> {
+        let montgomery = &self.montgomery;
+        self.montgomery_mds
+            .iter()
+            .zip(constants)
+            .map(|(row, constant)| {
+                row.iter().zip(state).fold(*constant, |acc, (entry, x)| {
+                    montgomery.add(acc, montgomery.mul(*entry, *x))
+                })
+            })
+            .collect()
+    }
+
+    pub fn permute(&self, state: &mut [FieldElement]) -> Result<()> {
+        if state.len() != self.state_width {
+            return Err(Error::LengthMismatch {
+                expected: self.state_width,
+                actual: state.len(),
+            });
+        }
+        if state.iter().any(|x| *x.field != self.field) {
+            return Err(Error::FieldMismatch);
+        }
+        let mut words: Vec<_> = state
+            .iter()
+            .map(|x| self.montgomery.to_montgomery(x.value))
+            .collect();
+        self.permute_unchecked(&mut words);
+        for (x, word) in state.iter_mut().zip(words) {
+            *x = self.element(word);
+        }
+        Ok(())
+    }
+
+    // Runs on Montgomery-form words.
+    fn permute_unchecked(&self, state: &mut [U256]) {
+        let m = self.state_width;
+        let montgomery = &self.montgomery;
+        for round in 0..self.num_rounds {
+            let constants = &self.montgomery_constants[2 * m * round..];
+            let forward: Vec<_> = state
+                .iter()
+                .map(|x| montgomery.pow_fixed(*x, self.alpha.into()))
+                .collect();
+            let forward = self.mix(&forward, &constants[..m]);
+            let backward: Vec<_> = forward
+                .iter()
+                .map(|x| montgomery.pow_fixed(*x, self.alpha_inv))
+                .collect();
+            state.copy_from_slice(&self.mix(&backward, &constants[m..2 * m]));
+        }
+    }
+
+    /// The tutorial's single-element hash: absorb into the rate, permute once
+    /// and read the first state element.
+    pub fn hash_element(&self, element: &FieldElement) -> FieldElement {
+        let mut state = vec![ZERO; self.state_width];
+        // Montgomery reduction also brings values from larger fields below p.
+        state[0] = self.montgomery.to_montgomery(element.value);
+        self.permute_unchecked(&mut state);
+        self.element(state[0])
+    }
+
+    /// Sponge over bytes. Input is padded with 0x01 and zeros to whole
+    /// blocks of `rate` chunks, each chunk short enough to sit below p; each
+    /// squeeze yields the low chunk bytes of every rate element.
+    pub fn hash_bytes(&self, data: &[u8], num_bytes: usize) -> Vec<u8> {
+        let chunk_bytes = self.chunk_bytes();
+        let block_bytes = chunk_bytes * self.rate();
+        let mut padded = data.to_vec();
+        padded.push(1);
+        padded.resize(padded.len().div_ceil(block_bytes) * block_bytes, 0);
+
+        let montgomery = &self.montgomery;
+        let mut state = vec![ZERO; self.state_width];
+        for block in padded.chunks(block_bytes) {
+            for (word, chunk) in state.iter_mut().zip(block.chunks(chunk_bytes)) {
+                let chunk = montgomery.to_montgomery(U256::from_big_endian(chunk));
+                *word = montgomery.add(*word, chunk);
+            }
+            self.permute_unchecked(&mut state);
+        }
+
+        let mut output = Vec::with_capacity(num_bytes.next_multiple_of(block_bytes));
+        loop {
+            for word in &state[..self.rate()] {
+                let mut bytes = [0u8; 32];
+                montgomery.from_montgomery(*word).to_big_endian(&mut bytes);
+                output.extend_from_slice(&bytes[32 - chunk_bytes..]);
+            }
+            if output.len() >= num_bytes {
+                break;
+            }
+            self.permute_unchecked(&mut state);
+        }
+        output.truncate(num_bytes);
+        output
+    }
+}
+
+// alpha^-1 mod p - 1, so that x -> x^alpha_inv undoes x -> x^alpha.
+fn inverse_exponent(alpha: u64, order: U256) -> U256 {
+    let (s, _, _, s_neg, _) = crate::xgcd(alpha.into(), order);
+    if s_neg {
+        order - s
+    } else {
+        s
+    }
+}
+
+// The reference's Groebner-basis bound: the smallest l1 with
+// C(v + d, v)^2 > 2^security, raised to at least 5 and padded by 50%.
+fn num_rounds(state_width: usize, capacity: usize, security_level: usize, alpha: u64) -> usize {
+    let rate = state_width - capacity;
+    let log2_binomial = |n: usize, k: usize| {
+        (1..=k)
+            .map(|i| ((n - k + i) as f64 / i as f64).log2())
+            .sum::<f64>()
+    };
+    let l1 = (1..25)
+        .find(|l1| {
+            let degree = (alpha as usize - 1) * state_width * (l1 - 1) / 2 + 2;
+            let variables = state_width * (l1 - 1) + rate;
+            2.0 * log2_binomial(variables + degree, variables) > security_level as f64
+        })
+        .unwrap_or(24);
+    (3 * l1.max(5)).div_ceil(2)
+}
+
+// Round constants as in the Rescue-Prime reference: little-endian integers
+// read from SHAKE256 of the parameter string, reduced mod p.
+fn round_constants(
+    field: Field,
+    state_width: usize,
+    capacity: usize,
+    security_level: usize,
+    num_rounds: usize,
+) -> Vec<FieldElement> {
+    let p = field.p();
+    let bytes_per_int = p.bits().div_ceil(8) + 1;
+    let seed = format!(
+        "Rescue-XLIX({},{},{},{})",
+        p, state_width, capacity, security_level
+    );
+    let mut shake = sha3::Shake256::default();
+    shake.update(seed.as_bytes());
+    let mut reader = shake.finalize_xof();
+    (0..2 * state_width * num_rounds)
+        .map(|_| {
+            let mut wide = [0u8; 64];
+            reader.read(&mut wide[..bytes_per_int]);
+            let value = U512::from_little_endian(&wide) % U512::from(p);
+            FieldElement::new(U256::try_from(value).unwrap(), field)
+        })
+        .collect()
+}
+
+// The transposed right half of the reduced echelon form of the m x 2m
+// Vandermonde matrix over the smallest primitive element.
+fn mds_matrix(field: Field, state_width: usize) -> Result<Vec<Vec<FieldElement>>> {
+    let g = field
+        .multiplicative_generator()
+        .ok_or_else(|| Error::InvalidParameters("cannot find a primitive element".to_string()))?;
+    let mut rows: Vec<Vec<FieldElement>> = (0..state_width)
+        .map(|i| {
+            (0..2 * state_width)
+                .map(|j| g ^ ((i * j) as u64).into())
+                .collect()
+        })
+        .collect();
+    for pivot in 0..state_width {
+        let swap = (pivot..state_width)
+            .find(|row| !rows[*row][pivot].is_zero())
+            .ok_or_else(|| {
+                Error::InvalidParameters(format!(
+                    "state width {} is too large for the field",
+                    state_width
+                ))
+            })?;
+        rows.swap(pivot, swap);
+        let inverse = rows[pivot][pivot].inv();
+        rows[pivot] = rows[pivot].iter().map(|x| x * inverse).collect();
+        for row in 0..state_width {
+            if row != pivot {
+                let factor = rows[row][pivot];
+                rows[row] = rows[row]
+                    .iter()
+                    .zip(&rows[pivot])
+                    .map(|(x, y)| *x - factor * *y)
+                    .collect();
+            }
+        }
+    }
+    Ok((0..state_width)
+        .map(|i| (0..state_width).map(|j| rows[j][state_width + i]).collect())
+        .collect())
+}
+
+/// `RescueParameters::hash_element` on the tutorial instance.
+pub fn hash_element(element: &FieldElement) -> FieldElement {
+    TUTORIAL.hash_element(element)
+}
+
+/// `RescueParameters::hash_bytes` on the tutorial instance.
+pub fn hash_bytes(data: &[u8], num_bytes: usize) -> Vec<u8> {
+    TUTORIAL.hash_bytes(data, num_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::presets::Preset;
+
+    #[test]
+    fn rescue_test() {
+        let parameters = &*TUTORIAL;
+        let f = parameters.field;
+        assert_eq!(parameters.alpha, 3);
+        assert_eq!(parameters.num_rounds, 27);
+        let x = f.element(123456789);
+        assert_eq!((x ^ 3.into()) ^ parameters.alpha_inv, x);
+        assert_eq!(
+            parameters.round_constants().len(),
+            2 * parameters.state_width * parameters.num_rounds
+        );
+
+        // Known answers from the Python tutorial's `RescuePrime().hash(...)`,
+        // which also pins alpha^-1, the MDS matrix and the first constant.
+        let element = |value: &str| FieldElement::new(U256::from_dec_str(value).unwrap(), f);
+        assert_eq!(
+            parameters.alpha_inv,
+            U256::from_dec_str("180331931428153586757283157844700080811").unwrap()
+        );
+        assert_eq!(
+            parameters.mds(),
+            vec![
+                vec![-&f.element(3), f.element(4)],
+                vec![-&f.element(12), f.element(13)]
+            ]
+        );
+        assert_eq!(
+            parameters.round_constants()[0],
+            element("174420698556543096520990950387834928928")
+        );
+        assert_eq!(
+            hash_element(&f.one()),
+            element("244180265933090377212304188905974087294")
+        );
+        assert_eq!(
+            hash_element(&element("57322816861100832358702415967512842988")),
+            element("89633745865384635541695204788332415101")
+        );
+
+        assert_eq!(hash_element(&x), hash_element(&x));
+        assert_ne!(hash_element(&x), hash_element(&f.element(123456790)));
+        assert_ne!(hash_element(&x), x);
+
+        let digest = hash_bytes(b"abc", 32);
+        assert_eq!(digest.len(), 32);
+        assert_eq!(hash_bytes(b"abc", 20), digest[..20]);
+        assert_ne!(hash_bytes(b"abc\0", 32), digest);
+        assert_ne!(hash_bytes(b"", 32), hash_bytes(&[1], 32));
+    }
+
+    #[test]
+    fn parameters_test() {
+        let f = Field::new(*PRIME);
+        let wide = RescueParameters::new(f, 4, 2, 128).unwrap();
+        assert_eq!(wide.rate(), 2);
+        assert_eq!(wide.num_rounds, 14);
+        assert_eq!(wide.round_constants().len(), 2 * 4 * 14);
+        assert!(wide.mds().iter().flatten().all(|entry| !entry.is_zero()));
+        let mut state = vec![f.one(), f.zero(), f.zero(), f.one()];
+        wide.permute(&mut state).unwrap();
+        assert_ne!(state, vec![f.one(), f.zero(), f.zero(), f.one()]);
+        let digest = wide.hash_bytes(b"abc", 40);
+        assert_eq!(digest.len(), 40);
+        assert_ne!(digest[..32], hash_bytes(b"abc", 32));
+
+        let goldilocks =
+            RescueParameters::new(Field::from_preset(Preset::Goldilocks), 12, 4, 128).unwrap();
+        assert_eq!(goldilocks.alpha, 7);
+        assert_eq!(goldilocks.num_rounds, 8);
+        assert_eq!(goldilocks.chunk_bytes(), 7);
+        assert_eq!(goldilocks.hash_bytes(b"abc", 16).len(), 16);
+
+        assert!(matches!(
+            RescueParameters::new(f, 2, 2, 128),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            RescueParameters::new(f, 2, 0, 128),
+            Err(Error::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            wide.permute(&mut [f.one(); 2]),
+            Err(Error::LengthMismatch {
+                expected: 4,
+                actual: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{hash::HashKind, Error, Result};
+
+pub const MAX_ATTEMPTS: usize = 64;
+
+pub struct Sampler<'a> {
+    hash: HashKind,
+    seed: &'a [u8],
+    counter: u64,
+}
+
+impl<'a> Sampler<'a> {
+    pub fn new(hash: HashKind, seed: &'a [u8]) -> Self {
+        Sampler {
+            hash,
+            seed,
+            counter: 0,
+        }
+    }
+
+    fn next_word(&mut self) -> u64 {
+        let mut bytes = self.seed.to_vec();
+        bytes.extend(self.counter.to_be_bytes());
+        self.counter += 1;
+        let digest = self.hash.digest(&bytes);
+        u64::from_be_bytes(digest[..8].try_into().unwrap())
+    }
+
+    pub fn uniform(&mut self, bound: usize) -> Result<usize> {
+        if bound == 0 {
+            return Err(Error::BadDomainSize(bound));
+        }
+        let bound = bound as u64;
+        let limit = u64::MAX - u64::MAX % bound;
+        for _ in 0..MAX_ATTEMPTS {
+            let word = self.next_word();
+            if word < limit {
+                return Ok((word % bound) as usize);
+            }
+        }
+        Err(Error::SamplingFailed {
+            attempts: MAX_ATTEMPTS,
+        })
+    }
+}
+
+/// Samples `number` indices in `0..size` whose residues modulo `reduced_size`
+/// are distinct. The residues are a uniformly random ordered selection without
+/// replacement from `0..reduced_size`, and each index is lifted to a uniformly
+/// random element of its residue class. Draws hash `seed || counter` with the
+/// counter as 8 big-endian bytes, and reject the biased tail of each 64-bit word.
+pub fn sample_indices(
+    hash: HashKind,
+    seed: &[u8],
+    size: usize,
+    reduced_size: usize,
+    number: usize,
+) -> Result<Vec<usize>> {
+    if number > reduced_size {
+        return Err(Error::TooManySamples {
+            number,
+            size: reduced_size,
+        });
+    }
+    if reduced_size == 0 || !size.is_multiple_of(reduced_size) {
+        return Err(Error::BadDomainSize(size));
+    }
+
+    let mut sampler = Sampler::new(hash, seed);
+    let mut residues: Vec<usize> = (0..reduced_size).collect();
+    let mut indices = Vec::with_capacity(number);
+    for i in 0..number {
+        let j = i + sampler.uniform(reduced_size - i)?;
+        residues.swap(i, j);
+        let lift = sampler.uniform(size / reduced_size)?;
+        indices.push(residues[i] + lift * reduced_size);
+    }
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_indices_test() {
+        let seed = b"sampling";
+        let indices = sample_indices(HashKind::Blake2b, seed, 64, 16, 16).unwrap();
+        assert_eq!(indices.len(), 16);
+        assert!(indices.iter().all(|i| *i < 64));
+        let mut residues: Vec<usize> = indices.iter().map(|i| i % 16).collect();
+        residues.sort();
+        assert_eq!(residues, (0..16).collect::<Vec<usize>>());
+
+        assert_eq!(
+            indices,
+            sample_indices(HashKind::Blake2b, seed, 64, 16, 16).unwrap()
+        );
+        assert_ne!(
+            indices,
+            sample_indices(HashKind::Sha3, seed, 64, 16, 16).unwrap()
+        );
+        assert!(sample_indices(HashKind::Blake2b, seed, 64, 16, 0)
+            .unwrap()
+            .is_empty());
+
+        assert!(matches!(
+            sample_indices(HashKind::Blake2b, seed, 64, 16, 17),
+            Err(Error::TooManySamples {
+                number: 17,
+                size: 16
+            })
+        ));
+        assert!(matches!(
+            sample_indices(HashKind::Blake2b, seed, 48, 32, 4),
+            Err(Error::BadDomainSize(48))
+        ));
+    }
+
+    #[test]
+    fn uniform_test() {
+        let mut sampler = Sampler::new(HashKind::Blake3, b"uniform");
+        let mut counts = [0usize; 3];
+        for _ in 0..3000 {
+            counts[sampler.uniform(3).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|c| (900..1100).contains(c)));
+        assert!(matches!(sampler.uniform(0), Err(Error::BadDomainSize(0))));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    element::FieldElement, field::Field, mpolynomial::MPolynomial, polynomial::Polynomial,
+    trace::ExecutionTrace,
+};
+use primitive_types::U256;
+use proptest::{collection::vec, prelude::*};
+use std::collections::HashMap;
+
+pub fn field_element(field: Field) -> impl Strategy<Value = FieldElement> {
+    any::<[u8; 32]>()
+        .prop_map(move |bytes| FieldElement::new(U256::from_big_endian(&bytes) % field.p(), field))
+}
+
+pub fn nonzero_field_element(field: Field) -> impl Strategy<Value = FieldElement> {
+    field_element(field).prop_filter("element must be nonzero", |e| !e.is_zero())
+}
+
+pub fn polynomial(field: Field, max_degree: usize) -> impl Strategy<Value = Polynomial> {
+    vec(field_element(field), 0..=max_degree + 1).prop_map(Polynomial::new)
+}
+
+pub fn mpolynomial(
+    field: Field,
+    num_variables: usize,
+    max_exponent: u64,
+    max_terms: usize,
+) -> impl Strategy<Value = MPolynomial> {
+    vec(
+        (vec(0..=max_exponent, num_variables), field_element(field)),
+        0..=max_terms,
+    )
+    .prop_map(|terms| {
+        let coefficients: HashMap<Vec<U256>, FieldElement> = terms
+            .into_iter()
+            .map(|(exponents, c)| (exponents.into_iter().map(U256::from).collect(), c))
+            .collect();
+        MPolynomial::new(coefficients)
+    })
+}
+
+pub fn execution_trace(
+    field: Field,
+    max_width: usize,
+    max_rows: usize,
+) -> impl Strategy<Value = ExecutionTrace> {
+    (1..=max_width, 1..=max_rows)
+        .prop_flat_map(move |(width, num_rows)| vec(vec(field_element(field), width), num_rows))
+        .prop_map(|rows| ExecutionTrace::new(rows).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        channel::{ProverChannel, VerifierChannel},
+        consts::*,
+        fri::FRI,
+        trace::{evaluate_columns, interpolate_columns},
+    };
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn field_identities_test(
+            a in field_element(Field::new(*PRIME)),
+            b in nonzero_field_element(Field::new(*PRIME)),
+        ) {
+            prop_assert_eq!((a * b) / b, a);
+            prop_assert_eq!((a + b) - b, a);
+            prop_assert_eq!(b * b.inv(), b.field.one());
+            prop_assert_eq!(a + (-&a), a.field.zero());
+        }
+
+        #[test]
+        fn polynomial_identities_test(
+            p in polynomial(Field::new(*PRIME), 6),
+            q in polynomial(Field::new(*PRIME), 6),
+            x in field_element(Field::new(*PRIME)),
+        ) {
+            prop_assert_eq!((&p * &q).evaluate(&x), p.evaluate(&x) * q.evaluate(&x));
+            prop_assert_eq!((&p + &q).evaluate(&x), p.evaluate(&x) + q.evaluate(&x));
+        }
+
+        #[test]
+        fn mpolynomial_identities_test(
+            p in mpolynomial(Field::new(*PRIME), 3, 3, 4),
+            q in mpolynomial(Field::new(*PRIME), 3, 3, 4),
+            point in vec(field_element(Field::new(*PRIME)), 3),
+        ) {
+            prop_assert_eq!(
+                (&p * &q).evaluate(&point),
+                p.evaluate(&point) * q.evaluate(&point)
+            );
+            prop_assert_eq!(
+                (&p - &q).evaluate(&point),
+                p.evaluate(&point) - q.evaluate(&point)
+            );
+        }
+
+        #[test]
+        fn trace_round_trip_test(trace in execution_trace(Field::new(*PRIME), 3, 8)) {
+            let omicron = Field::new(*PRIME).primitive_nth_root(8.into());
+            let polynomials = interpolate_columns(&trace, &omicron).unwrap();
+            prop_assert_eq!(polynomials.len(), trace.width());
+            prop_assert_eq!(
+                evaluate_columns(&polynomials, &omicron, trace.num_rows()).unwrap(),
+                trace
+            );
+        }
+
+        #[test]
+        fn fri_round_trip_test(p in polynomial(Field::new(*PRIME), 7)) {
+            let f = Field::new(*PRIME);
+            let fri = FRI::new(f.generator(), f.primitive_nth_root(32.into()), 32, 4, 2);
+            let codeword = p.evaluate_domain(&fri.eval_domain());
+            let mut prover = ProverChannel::new();
+            fri.prove(&codeword, &mut prover).unwrap();
+            let mut verifier = VerifierChannel::from(prover);
+            prop_assert!(fri.verify(&mut verifier, vec![]).unwrap());
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    element::FieldElement, mpolynomial::MPolynomial, ntt, polynomial::Polynomial, Error, Result,
+};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ExecutionTrace {
+    rows: Vec<Vec<FieldElement>>,
+}
+
+impl ExecutionTrace {
+    pub fn new(rows: Vec<Vec<FieldElement>>) -> Result<Self> {
+        let width = match rows.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => {
+                return Err(Error::InvalidParameters(
+                    "trace needs at least one non-empty row".to_string(),
+                ))
+            }
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != width) {
+            return Err(Error::LengthMismatch {
+                expected: width,
+                actual: row.len(),
+            });
+        }
+        Ok(ExecutionTrace { rows })
+    }
+
+    pub fn from_transition<F>(
+        initial_state: Vec<FieldElement>,
+        steps: usize,
+        mut transition: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&[FieldElement]) -> Vec<FieldElement>,
+    {
+        let width = initial_state.len();
+        let mut rows = Vec::with_capacity(steps + 1);
+        rows.push(initial_state);
+        for _ in 0..steps {
+            let next = transition(rows.last().unwrap());
+            if next.len() != width {
+                return Err(Error::LengthMismatch {
+                    expected: width,
+                    actual: next.len(),
+                });
+            }
+            rows.push(next);
+        }
+        ExecutionTrace::new(rows)
+    }
+
+    pub fn from_transition_checked<F>(
+        initial_state: Vec<FieldElement>,
+        steps: usize,
+        transition_constraints: &[MPolynomial],
+        transition: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&[FieldElement]) -> Vec<FieldElement>,
+    {
+        let trace = ExecutionTrace::from_transition(initial_state, steps, transition)?;
+        if cfg!(debug_assertions) {
+            trace.check_transitions(transition_constraints)?;
+        }
+        Ok(trace)
+    }
+
+    pub fn check_transitions(&self, transition_constraints: &[MPolynomial]) -> Result<()> {
+        for (row, pair) in self.rows.windows(2).enumerate() {
+            let point = [pair[0].as_slice(), pair[1].as_slice()].concat();
+            if let Some(constraint) = transition_constraints
+                .iter()
+                .position(|constraint| !constraint.evaluate(&point).is_zero())
+            {
+                return Err(Error::ConstraintViolation { row, constraint });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn rows(&self) -> &[Vec<FieldElement>] {
+        &self.rows
+    }
+
+    pub fn column(&self, index: usize) -> Result<Vec<FieldElement>> {
+        if index >= self.width() {
+            return Err(Error::IndexOutOfRange {
+                index,
+                len: self.width(),
+            });
+        }
+        Ok(self.rows.iter().map(|row| row[index]).collect())
+    }
+}
+
+fn row_domain(omicron: &FieldElement, num_rows: usize) -> Result<Vec<FieldElement>> {
+    let domain = omicron.field.powers(omicron, num_rows);
+    if domain
+        .iter()
+        .skip(1)
+        .any(|point| *point == omicron.field.one())
+    {
+        return Err(Error::BadDomainSize(num_rows));
+    }
+    Ok(domain)
+}
+
+pub fn interpolate_columns(
+    trace: &ExecutionTrace,
+    omicron: &FieldElement,
+) -> Result<Vec<Polynomial>> {
+    let num_rows = trace.num_rows();
+    let domain = row_domain(omicron, num_rows)?;
+    let one = omicron.field.one();
+    let cyclic = num_rows.is_power_of_two() && omicron ^ num_rows.into() == one;
+    (0..trace.width())
+        .map(|index| {
+            let column = trace.column(index)?;
+            let polynomial = if cyclic {
+                Polynomial::interpolate_coset(&one, omicron, &column)?
+            } else {
+                Polynomial::interpolate_domain(&domain, &column)?
+            };
+            Ok(polynomial.normalized())
+        })
+        .collect()
+}
+
+pub fn evaluate_columns(
+    polynomials: &[Polynomial],
+    omicron: &FieldElement,
+    num_rows: usize,
+) -> Result<ExecutionTrace> {
+    let domain = row_domain(omicron, num_rows)?;
+    let one = omicron.field.one();
+    let cyclic = num_rows.is_power_of_two() && omicron ^ num_rows.into() == one;
+    let columns: Vec<Vec<FieldElement>> = polynomials
+        .iter()
+        .map(|polynomial| {
+            if cyclic && polynomial.coefficients.len() <= num_rows {
+                ntt::fast_coset_evaluate(polynomial, &one, omicron, num_rows)
+            } else {
+                Ok(polynomial.evaluate_domain(&domain))
+            }
+        })
+        .collect::<Result<_>>()?;
+    ExecutionTrace::new(
+        (0..num_rows)
+            .map(|row| columns.iter().map(|column| column[row]).collect())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{consts::*, field::Field};
+
+    #[test]
+    fn from_transition_test() {
+        let f = Field::new(*PRIME);
+        let trace = ExecutionTrace::from_transition(vec![f.one(), f.one()], 6, |state| {
+            vec![state[1], state[0] + state[1]]
+        })
+        .unwrap();
+        assert_eq!(trace.num_rows(), 7);
+        assert_eq!(trace.width(), 2);
+        assert_eq!(trace.rows()[6][1], f.element(21));
+        assert_eq!(trace.column(0).unwrap().len(), 7);
+        assert!(matches!(
+            trace.column(2),
+            Err(Error::IndexOutOfRange { index: 2, len: 2 })
+        ));
+
+        let result =
+            ExecutionTrace::from_transition(vec![f.one()], 2, |state| vec![state[0], state[0]]);
+        assert!(matches!(
+            result,
+            Err(Error::LengthMismatch {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn interpolate_columns_test() {
+        let f = Field::new(*PRIME);
+        let fibonacci = |state: &[FieldElement]| vec![state[1], state[0] + state[1]];
+        for steps in [7, 11] {
+            let trace =
+                ExecutionTrace::from_transition(vec![f.one(), f.one()], steps, fibonacci).unwrap();
+            let omicron = f.primitive_nth_root(16.into());
+            let polynomials = interpolate_columns(&trace, &omicron).unwrap();
+            assert_eq!(polynomials.len(), 2);
+            for (index, polynomial) in polynomials.iter().enumerate() {
+                assert!(polynomial.degree() < trace.num_rows() as i32);
+                assert_eq!(
+                    polynomial.evaluate(&(omicron ^ 3.into())),
+                    trace.rows()[3][index]
+                );
+            }
+            let evaluated = evaluate_columns(&polynomials, &omicron, trace.num_rows()).unwrap();
+            assert_eq!(evaluated, trace);
+        }
+
+        let trace =
+            ExecutionTrace::from_transition(vec![f.one()], 7, |state| vec![state[0]]).unwrap();
+        let polynomials = interpolate_columns(&trace, &f.primitive_nth_root(8.into())).unwrap();
+        assert_eq!(polynomials[0], Polynomial::new(vec![f.one()]));
+        assert!(matches!(
+            interpolate_columns(&trace, &f.primitive_nth_root(4.into())),
+            Err(Error::BadDomainSize(8))
+        ));
+    }
+
+    #[test]
+    fn check_transitions_test() {
+        let f = Field::new(*PRIME);
+        let v = MPolynomial::variables(4, &f);
+        let constraints = vec![&v[2] - &v[1], &v[3] - &(&v[0] + &v[1])];
+
+        let fibonacci = |state: &[FieldElement]| vec![state[1], state[0] + state[1]];
+        let trace = ExecutionTrace::from_transition_checked(
+            vec![f.one(), f.one()],
+            8,
+            &constraints,
+            fibonacci,
+        )
+        .unwrap();
+        assert!(trace.check_transitions(&constraints).is_ok());
+
+        let broken = ExecutionTrace::from_transition(vec![f.one(), f.one()], 8, |state| {
+            vec![state[1], (state[0] + state[1]) + f.one()]
+        })
+        .unwrap();
+        assert!(matches!(
+            broken.check_transitions(&constraints),
+            Err(Error::ConstraintViolation {
+                row: 0,
+                constraint: 1
+            })
+        ));
+    }
+}
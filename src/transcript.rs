@@ -0,0 +1,73 @@
+use crate::{hash::HashKind, proofstream::Object, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Absorb { kind: String, digest: String },
+    Squeeze { num_bytes: usize, challenge: String },
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Divergence {
+    pub index: usize,
+    pub prover: Option<Event>,
+    pub verifier: Option<Event>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    pub events: Vec<Event>,
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Transcript { events: vec![] }
+    }
+
+    pub fn absorb<T: Serialize>(&mut self, hash: HashKind, object: &Object<T>) {
+        let kind = match object {
+            Object::HASH(_) => "hash",
+            Object::PATH(_) => "path",
+            Object::LEAF(_) => "leaf",
+            Object::OBJ(_) => "obj",
+        };
+        let bytes = serde_pickle::to_vec(object, Default::default()).unwrap();
+        self.events.push(Event::Absorb {
+            kind: kind.to_string(),
+            digest: hex::encode(hash.digest(&bytes)),
+        });
+    }
+
+    pub fn squeeze(&mut self, challenge: &[u8]) {
+        self.events.push(Event::Squeeze {
+            num_bytes: challenge.len(),
+            challenge: hex::encode(challenge),
+        });
+    }
+
+    pub fn diff(prover: &Transcript, verifier: &Transcript) -> Option<Divergence> {
+        let len = prover.events.len().max(verifier.events.len());
+        (0..len)
+            .map(|index| Divergence {
+                index,
+                prover: prover.events.get(index).cloned(),
+                verifier: verifier.events.get(index).cloned(),
+            })
+            .find(|divergence| divergence.prover != divergence.verifier)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
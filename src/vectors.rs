@@ -0,0 +1,319 @@
+use crate::{
+    channel::{ProverChannel, VerifierChannel},
+    consts::*,
+    element::FieldElement,
+    field::Field,
+    fri::FRI,
+    merkle::{self, Direction, Merkle},
+    polynomial::Polynomial,
+    Error, Result,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+pub const VERSION: u32 = 9;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct FieldVector {
+    pub a: String,
+    pub b: String,
+    pub sum: String,
+    pub difference: String,
+    pub product: String,
+    pub quotient: String,
+    pub inverse: String,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct MerkleVector {
+    pub leafs: Vec<String>,
+    pub root: String,
+    pub index: usize,
+    pub path: Vec<(Direction, String)>,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct FriVector {
+    pub domain_length: usize,
+    pub expansion_factor: usize,
+    pub num_colinearity_tests: usize,
+    pub coefficients: Vec<String>,
+    pub indices: Vec<usize>,
+    pub proof: String,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct TestVectors {
+    pub version: u32,
+    pub prime: String,
+    pub field: Vec<FieldVector>,
+    pub merkle: Vec<MerkleVector>,
+    pub fri: Vec<FriVector>,
+}
+
+fn seeded_elements(field: Field, seed: &str, count: usize) -> Vec<FieldElement> {
+    (0..count)
+        .map(|i| {
+            let mut bytes = seed.as_bytes().to_vec();
+            bytes.extend(i.to_be_bytes());
+            field.sample(&merkle::hash(&bytes)[0..16])
+        })
+        .collect()
+}
+
+fn parse_element(value: &str, field: Field) -> Result<FieldElement> {
+    U256::from_dec_str(value)
+        .map(|v| FieldElement::new(v, field))
+        .map_err(|_| Error::MalformedVector(format!("invalid field element {:?}", value)))
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value)
+        .map_err(|_| Error::MalformedVector(format!("invalid hex string {:?}", value)))
+}
+
+fn field_vector(a: FieldElement, b: FieldElement) -> FieldVector {
+    FieldVector {
+        a: a.value.to_string(),
+        b: b.value.to_string(),
+        sum: (a + b).value.to_string(),
+        difference: (a - b).value.to_string(),
+        product: (a * b).value.to_string(),
+        quotient: (a / b).value.to_string(),
+        inverse: b.inv().value.to_string(),
+    }
+}
+
+fn merkle_vector(leafs: Vec<Vec<u8>>, index: usize) -> Result<MerkleVector> {
+    let path = Merkle::open(index, &leafs)?;
+    Ok(MerkleVector {
+        leafs: leafs.iter().map(hex::encode).collect(),
+        root: hex::encode(Merkle::commit(&leafs)),
+        index,
+        path: path
+            .nodes
+            .iter()
+            .map(|(direction, node)| (*direction, hex::encode(node)))
+            .collect(),
+    })
+}
+
+fn fri_for(
+    field: Field,
+    domain_length: usize,
+    expansion_factor: usize,
+    tests: usize,
+) -> Result<FRI> {
+    let malformed = |reason: &str| Error::MalformedVector(format!("fri parameters: {}", reason));
+    let offset = field
+        .try_generator()
+        .ok_or_else(|| malformed("the field has no known generator"))?;
+    let omega = field
+        .nth_root(domain_length.into())
+        .ok_or_else(|| malformed("no root of unity of the domain length"))?;
+    FRI::try_new(offset, omega, domain_length, expansion_factor, tests)
+        .map_err(|e| malformed(&e.to_string()))
+}
+
+fn fri_vector(
+    field: Field,
+    coefficients: Vec<FieldElement>,
+    domain_length: usize,
+    expansion_factor: usize,
+    num_colinearity_tests: usize,
+) -> Result<FriVector> {
+    let fri = fri_for(
+        field,
+        domain_length,
+        expansion_factor,
+        num_colinearity_tests,
+    )?;
+    let codeword = Polynomial::new(coefficients.clone()).evaluate_domain(&fri.eval_domain());
+    let mut channel = ProverChannel::new();
+    let indices = fri.prove(&codeword, &mut channel)?;
+    Ok(FriVector {
+        domain_length,
+        expansion_factor,
+        num_colinearity_tests,
+        coefficients: coefficients.iter().map(|c| c.value.to_string()).collect(),
+        indices,
+        proof: hex::encode(channel.serialize()),
+    })
+}
+
+pub fn generate() -> TestVectors {
+    let f = Field::new(*PRIME);
+
+    let lhs = seeded_elements(f, "field-lhs", 8);
+    let rhs = seeded_elements(f, "field-rhs", 8);
+    let mut field: Vec<FieldVector> = lhs
+        .iter()
+        .zip(rhs.iter())
+        .map(|(a, b)| field_vector(*a, *b))
+        .collect();
+    field.push(field_vector(f.zero(), f.one()));
+    field.push(field_vector(
+        FieldElement::new(*PRIME - ONE, f),
+        FieldElement::new(*PRIME - ONE, f),
+    ));
+
+    let merkle = vec![
+        merkle_vector(vec![vec![1], vec![2], vec![3], vec![4]], 1).unwrap(),
+        merkle_vector((0..8u8).map(|i| vec![i; 3]).collect(), 6).unwrap(),
+        merkle_vector((0..5u8).map(|i| vec![i]).collect(), 4).unwrap(),
+    ];
+
+    let fri = vec![
+        fri_vector(f, seeded_elements(f, "fri-0", 8), 32, 4, 2).unwrap(),
+        fri_vector(f, seeded_elements(f, "fri-1", 16), 64, 4, 4).unwrap(),
+    ];
+
+    TestVectors {
+        version: VERSION,
+        prime: PRIME.to_string(),
+        field,
+        merkle,
+        fri,
+    }
+}
+
+pub fn to_json(vectors: &TestVectors) -> String {
+    serde_json::to_string_pretty(vectors).unwrap()
+}
+
+pub fn from_json(json: &str) -> Result<TestVectors> {
+    Ok(serde_json::from_str(json)?)
+}
+
+pub fn replay(vectors: &TestVectors) -> Result<()> {
+    if vectors.version != VERSION {
+        return Err(Error::MalformedVector(format!(
+            "unsupported version {} (expected {})",
+            vectors.version, VERSION
+        )));
+    }
+    let prime = U256::from_dec_str(&vectors.prime)
+        .map_err(|_| Error::MalformedVector("invalid prime".to_string()))?;
+    let f = Field::try_new(prime)?;
+
+    for (i, v) in vectors.field.iter().enumerate() {
+        let a = parse_element(&v.a, f)?;
+        let b = parse_element(&v.b, f)?;
+        if b.is_zero() {
+            return Err(Error::MalformedVector(format!(
+                "field vector {} divides by zero",
+                i
+            )));
+        }
+        if field_vector(a, b) != *v {
+            return Err(Error::VectorMismatch {
+                kind: "field",
+                index: i,
+            });
+        }
+    }
+
+    for (i, v) in vectors.merkle.iter().enumerate() {
+        let leafs = v
+            .leafs
+            .iter()
+            .map(|l| parse_hex(l))
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+        if merkle_vector(leafs, v.index)? != *v {
+            return Err(Error::VectorMismatch {
+                kind: "merkle",
+                index: i,
+            });
+        }
+    }
+
+    for (i, v) in vectors.fri.iter().enumerate() {
+        let coefficients = v
+            .coefficients
+            .iter()
+            .map(|c| parse_element(c, f))
+            .collect::<Result<Vec<FieldElement>>>()?;
+        let recomputed = fri_vector(
+            f,
+            coefficients,
+            v.domain_length,
+            v.expansion_factor,
+            v.num_colinearity_tests,
+        )?;
+        if recomputed != *v {
+            return Err(Error::VectorMismatch {
+                kind: "fri",
+                index: i,
+            });
+        }
+
+        let fri = fri_for(
+            f,
+            v.domain_length,
+            v.expansion_factor,
+            v.num_colinearity_tests,
+        )?;
+        let malformed =
+            |e: Error| Error::MalformedVector(format!("fri vector {} has a bad proof: {}", i, e));
+        let mut channel = VerifierChannel::deserialize(&parse_hex(&v.proof)?).map_err(malformed)?;
+        if !fri.verify(&mut channel, vec![]).map_err(malformed)? {
+            return Err(Error::VectorMismatch {
+                kind: "fri",
+                index: i,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_answer_test() {
+        let vectors = from_json(include_str!("../tests/vectors/kat.json")).unwrap();
+        replay(&vectors).unwrap();
+        assert_eq!(vectors, generate());
+    }
+
+    #[test]
+    fn tampered_vector_test() {
+        let mut vectors = generate();
+        vectors.field[0].product = vectors.field[0].sum.clone();
+        assert!(matches!(
+            replay(&vectors),
+            Err(Error::VectorMismatch {
+                kind: "field",
+                index: 0
+            })
+        ));
+
+        let mut vectors = generate();
+        vectors.merkle[1].root = vectors.merkle[0].root.clone();
+        assert!(replay(&vectors).is_err());
+
+        let mut vectors = generate();
+        vectors.fri[0].indices.reverse();
+        vectors.fri[0].indices.push(0);
+        assert!(replay(&vectors).is_err());
+
+        let mut vectors = generate();
+        vectors.field[0].b = "0".to_string();
+        assert!(matches!(replay(&vectors), Err(Error::MalformedVector(_))));
+
+        let mut vectors = generate();
+        vectors.fri[0].domain_length = 24;
+        assert!(matches!(replay(&vectors), Err(Error::MalformedVector(_))));
+
+        let mut vectors = generate();
+        vectors.fri[0].num_colinearity_tests = 100;
+        assert!(matches!(replay(&vectors), Err(Error::MalformedVector(_))));
+
+        let mut vectors = generate();
+        vectors.version += 1;
+        assert!(matches!(replay(&vectors), Err(Error::MalformedVector(_))));
+        assert!(matches!(from_json("{"), Err(Error::Json(_))));
+    }
+}
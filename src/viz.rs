@@ -0,0 +1,209 @@
+use crate::{
+    channel::VerifierChannel,
+    element::FieldElement,
+    fri::FRI,
+    hash::Hasher,
+    merkle::Merkle,
+    proofstream::{Object, ProofStream},
+    Error, Result,
+};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fmt::Write as _,
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+fn short_hex(bytes: &[u8]) -> String {
+    hex::encode(&bytes[..bytes.len().min(4)])
+}
+
+pub fn merkle_dot<T: Serialize>(data_array: &[T], opened: &[usize]) -> String {
+    let hash = Hasher::default();
+    let mut layers = vec![Merkle::hash_data_array(hash, data_array)];
+    while layers.last().unwrap().len() > 1 {
+        let layer = layers.last().unwrap();
+        let next = layer
+            .chunks(2)
+            .map(|pair| {
+                let mut combined = pair[0].clone();
+                combined.extend(&pair[1]);
+                hash.digest(&combined)
+            })
+            .collect();
+        layers.push(next);
+    }
+
+    let mut on_path = HashSet::new();
+    let mut siblings = HashSet::new();
+    for index in opened {
+        let mut i = *index;
+        for level in 0..layers.len() {
+            on_path.insert((level, i));
+            if level + 1 < layers.len() {
+                siblings.insert((level, i ^ 1));
+            }
+            i >>= 1;
+        }
+    }
+
+    let mut dot = String::from("digraph merkle {\n    node [shape=box, fontname=\"monospace\"];\n");
+    for (level, layer) in layers.iter().enumerate().rev() {
+        for (i, node) in layer.iter().enumerate() {
+            let style = if on_path.contains(&(level, i)) {
+                ", style=filled, fillcolor=lightblue"
+            } else if siblings.contains(&(level, i)) {
+                ", style=filled, fillcolor=orange"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "    n{}_{} [label=\"{}\"{}];",
+                level,
+                i,
+                short_hex(node),
+                style
+            )
+            .unwrap();
+            if level > 0 {
+                for child in [2 * i, 2 * i + 1] {
+                    writeln!(dot, "    n{}_{} -> n{}_{};", level, i, level - 1, child).unwrap();
+                }
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn fri_dot(fri: &FRI, proof_stream: &ProofStream<Vec<FieldElement>>) -> Result<String> {
+    let num_rounds = fri.num_rounds();
+    let mut channel = VerifierChannel::new(proof_stream.clone());
+    if !matches!(channel.pull()?, Object::HASH(_)) {
+        return Err(Error::MalformedProof("expected parameter digest"));
+    }
+    let commitments = fri.pull_commitments(&mut channel)?;
+    let mut dot = String::from(
+        "digraph fri {\n    rankdir=LR;\n    node [shape=record, fontname=\"monospace\"];\n",
+    );
+    for (r, root) in commitments.roots.iter().enumerate() {
+        writeln!(
+            dot,
+            "    r{} [label=\"round {}|domain {}|root {}\"];",
+            r,
+            r,
+            fri.domain_length >> r,
+            short_hex(root)
+        )
+        .unwrap();
+
+        if r + 1 < num_rounds {
+            writeln!(
+                dot,
+                "    r{} -> r{} [label=\"alpha {}\"];",
+                r,
+                r + 1,
+                commitments.alphas[r].value
+            )
+            .unwrap();
+        }
+    }
+
+    let last_codeword = match channel.pull()? {
+        Object::OBJ(codeword) => codeword,
+        _ => return Err(Error::MalformedProof("expected object")),
+    };
+    writeln!(
+        dot,
+        "    last [label=\"last codeword|{} values\"];\n    r{} -> last;",
+        last_codeword.len(),
+        num_rounds - 1
+    )
+    .unwrap();
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+pub fn render_svg(dot: &str) -> io::Result<String> {
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(dot.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "dot exited with {}",
+            output.status
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        channel::ProverChannel, consts::*, explain::Step, field::Field, polynomial::Polynomial,
+    };
+
+    #[test]
+    fn merkle_dot_test() {
+        let leafs = vec![vec![1u8], vec![2], vec![3], vec![4]];
+        let dot = merkle_dot(&leafs, &[1]);
+        let root = short_hex(&Merkle::commit(&leafs));
+        assert!(dot.contains(&format!(
+            "n2_0 [label=\"{}\", style=filled, fillcolor=lightblue]",
+            root
+        )));
+        assert!(dot.contains("n0_1 [label="));
+        assert!(dot.contains("fillcolor=orange"));
+        assert_eq!(dot.matches(" -> ").count(), 6);
+    }
+
+    #[test]
+    fn fri_dot_test() {
+        let f = Field::new(*PRIME);
+        let fri = || FRI::new(f.generator(), f.primitive_nth_root(32.into()), 32, 4, 2);
+        let p = Polynomial::new(vec![f.one(), f.generator(), f.one()]);
+        // A max degree below the domain bound adds the degree-correction root.
+        for fri in [fri(), fri().with_max_degree(2).unwrap()] {
+            let mut prover = ProverChannel::new();
+            let (_, explanation) = fri
+                .prove_explained(&p.evaluate_domain(&fri.eval_domain()), &mut prover)
+                .unwrap();
+            let mut ps = prover.into_proof();
+
+            let dot = fri_dot(&fri, &ps).unwrap();
+            assert!(dot.contains("round 0|domain 32"));
+            assert!(dot.contains("last codeword|"));
+            let mut alphas = 0;
+            for step in &explanation.steps {
+                match step {
+                    Step::Commit { round, root, .. } => {
+                        assert!(dot.contains(&format!("round {}|", round)));
+                        assert!(dot.contains(&format!("root {}", &root[..8])));
+                    }
+                    Step::Challenge { round, alpha } => {
+                        assert!(dot.contains(&format!(
+                            "r{} -> r{} [label=\"alpha {}\"]",
+                            round,
+                            round + 1,
+                            alpha
+                        )));
+                        alphas += 1;
+                    }
+                    _ => {}
+                }
+            }
+            assert_eq!(alphas, fri.num_rounds() - 1);
+            assert_eq!(dot.matches("alpha").count(), alphas);
+
+            ps.objects.clear();
+            assert!(fri_dot(&fri, &ps).is_err());
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    channel::VerifierChannel, element::FieldElement, field::Field, fri::FRI,
+    proofstream::ProofStream,
+};
+use primitive_types::U256;
+use wasm_bindgen::prelude::*;
+
+fn parse_u256(value: &str) -> Result<U256, JsValue> {
+    U256::from_dec_str(value).map_err(|_| JsValue::from_str("invalid decimal integer"))
+}
+
+#[wasm_bindgen]
+pub struct FriProof {
+    proof_stream: ProofStream<Vec<FieldElement>>,
+}
+
+#[wasm_bindgen]
+impl FriProof {
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<FriProof, JsValue> {
+        let proof_stream = ProofStream::deserialize(bytes)
+            .map_err(|e| JsValue::from_str(&format!("malformed proof: {}", e)))?;
+        Ok(FriProof { proof_stream })
+    }
+
+    #[wasm_bindgen(js_name = numObjects)]
+    pub fn num_objects(&self) -> usize {
+        self.proof_stream.objects.len()
+    }
+}
+
+#[wasm_bindgen]
+pub struct FriVerifier {
+    fri: FRI,
+}
+
+#[wasm_bindgen]
+impl FriVerifier {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        prime: &str,
+        offset: &str,
+        omega: &str,
+        domain_length: usize,
+        expansion_factor: usize,
+        num_colinearity_tests: usize,
+    ) -> Result<FriVerifier, JsValue> {
+        let field =
+            Field::try_new(parse_u256(prime)?).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let offset = FieldElement::new(parse_u256(offset)?, field);
+        let omega = FieldElement::new(parse_u256(omega)?, field);
+        let fri = FRI::try_new(
+            offset,
+            omega,
+            domain_length,
+            expansion_factor,
+            num_colinearity_tests,
+        )
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(FriVerifier { fri })
+    }
+
+    pub fn verify(&self, proof: &FriProof) -> Result<bool, JsValue> {
+        let mut channel = VerifierChannel::new(ProofStream {
+            hash: proof.proof_stream.hash,
+            digest_length: proof.proof_stream.digest_length,
+            objects: proof.proof_stream.objects.clone(),
+            read_index: 0,
+        });
+        self.fri
+            .verify(&mut channel, vec![])
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{channel::ProverChannel, polynomial::Polynomial};
+
+    #[test]
+    fn wasm_verify_test() {
+        let f = Field::new(17.into());
+        let fri = FRI::new(f.element(1), f.element(6), 16, 2, 2);
+        let p = Polynomial::new(vec![f.one(), f.zero(), f.one()]);
+        let codeword = p.evaluate_domain(&fri.eval_domain());
+        let mut channel = ProverChannel::new();
+        fri.prove(&codeword, &mut channel).unwrap();
+
+        let proof = FriProof::from_bytes(&channel.serialize()).ok().unwrap();
+        assert_eq!(proof.num_objects(), channel.proof().objects.len());
+
+        let verifier = FriVerifier::new("17", "1", "6", 16, 2, 2).ok().unwrap();
+        assert!(verifier.verify(&proof).ok().unwrap());
+        assert!(verifier.verify(&proof).ok().unwrap());
+    }
+}
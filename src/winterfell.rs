@@ -0,0 +1,89 @@
+use crate::{element::FieldElement, field::Field};
+use winter_math::StarkField;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum WinterfellConversionError {
+    FieldMismatch,
+    ValueOutOfRange,
+}
+
+pub fn field<E>() -> Field
+where
+    E: StarkField,
+    E::PositiveInteger: Into<u128>,
+{
+    Field::new(E::MODULUS.into().into())
+}
+
+pub fn to_winterfell<E>(element: &FieldElement) -> Result<E, WinterfellConversionError>
+where
+    E: StarkField + TryFrom<u128>,
+    E::PositiveInteger: Into<u128>,
+{
+    if *element.field != field::<E>() {
+        return Err(WinterfellConversionError::FieldMismatch);
+    }
+    if element.value >= element.field.p() {
+        return Err(WinterfellConversionError::ValueOutOfRange);
+    }
+    E::try_from(element.value.as_u128()).map_err(|_| WinterfellConversionError::ValueOutOfRange)
+}
+
+pub fn from_winterfell<E>(element: E) -> FieldElement
+where
+    E: StarkField,
+    E::PositiveInteger: Into<u128>,
+{
+    FieldElement::new(element.as_int().into().into(), field::<E>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+    use winter_math::{
+        fields::{f128, f64},
+        FieldElement as WinterFieldElement,
+    };
+
+    #[test]
+    fn f64_conversion_test() {
+        let f = field::<f64::BaseElement>();
+        assert_eq!(f.p(), 0xffffffff00000001u64.into());
+
+        let a = FieldElement::new(0xfedcba9876543210u64.into(), f);
+        let b = f.element(1234567);
+        let wa: f64::BaseElement = to_winterfell(&a).unwrap();
+        let wb: f64::BaseElement = to_winterfell(&b).unwrap();
+
+        assert_eq!(from_winterfell(wa), a);
+        assert_eq!(from_winterfell(wa * wb), a * b);
+        assert_eq!(from_winterfell(wa - wb), a - b);
+        assert_eq!(from_winterfell(wb.inv()), b.inv());
+
+        assert_eq!(
+            to_winterfell::<f64::BaseElement>(&Field::new(*PRIME).one()),
+            Err(WinterfellConversionError::FieldMismatch)
+        );
+        assert_eq!(
+            to_winterfell::<f64::BaseElement>(&FieldElement {
+                value: f.p(),
+                ..f.one()
+            }),
+            Err(WinterfellConversionError::ValueOutOfRange)
+        );
+    }
+
+    #[test]
+    fn f128_conversion_test() {
+        let f = field::<f128::BaseElement>();
+        let a = FieldElement::new((u128::MAX / 3).into(), f);
+        let b = f.element(987654321);
+        let wa: f128::BaseElement = to_winterfell(&a).unwrap();
+        let wb: f128::BaseElement = to_winterfell(&b).unwrap();
+
+        assert_eq!(from_winterfell(wa + wb), a + b);
+        assert_eq!(from_winterfell(wa * wb), a * b);
+        assert_eq!(from_winterfell(wa / wb), a / b);
+    }
+}